@@ -1,28 +1,221 @@
+use std::io;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[cfg(feature = "gui")]
+use tic_tac_toe_rust::frontend::opponent::Opponent;
+#[cfg(feature = "tui")]
+use tic_tac_toe_rust::frontend::tui::Tui;
 use tic_tac_toe_rust::{
-    frontend::console::{players::ConsolePlayer, renderers::ConsoleRenderer},
-    game::{DumbPlayer, MinimaxPlayer, Player, Renderer},
-    logic::Mark,
+    frontend::{
+        console::{
+            errors::ErrorFormat,
+            players::ConsolePlayer,
+            renderers::{BoardLayout, ColoredConsoleRenderer, ConsoleRenderer, RendererStyle},
+        },
+        json::JsonRenderer,
+        network::{players::NetworkPlayer, telnet::TelnetPlayer},
+    },
+    game::{
+        AiBuilder, DumbPlayer, ExternalProcessPlayer, GameControl, GameStore, HeuristicPlayer,
+        InvalidMovePolicy, Learner, LearningPlayer, PerfectPlayer, Player, Renderer, TimeControl,
+        TimedPlayer, TimeoutPolicy, Tournament,
+    },
+    logic::{errors::Error, Mark, Variant},
 };
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "Tic Tac Toe")]
 #[command(author, version, about, long_about = None)]
 pub(super) struct Cli {
-    #[arg(short='1', long, value_enum, default_value_t = PlayerType::Human)]
-    player1: PlayerType,
-    #[arg(short='2', long, value_enum, default_value_t = PlayerType::Human)]
-    player2: PlayerType,
+    /// The first player: a player type, `external:<command>` to play an external executable,
+    /// `network:<address>` to wait for a remote player joining on the address,
+    /// or `telnet:<address>` to seat a remote player typing in telnet or netcat
+    #[arg(short='1', long, value_parser = parse_player_arg, default_value = "human")]
+    player1: PlayerArg,
+    /// The second player: a player type, `external:<command>` to play an external executable,
+    /// `network:<address>` to wait for a remote player joining on the address,
+    /// or `telnet:<address>` to seat a remote player typing in telnet or netcat
+    #[arg(short='2', long, value_parser = parse_player_arg, default_value = "human")]
+    player2: PlayerArg,
+    /// The name shown for the first player, when human
+    #[arg(long, value_name = "NAME")]
+    name1: Option<String>,
+    /// The name shown for the second player, when human
+    #[arg(long, value_name = "NAME")]
+    name2: Option<String>,
     #[arg(short, long, value_enum, default_value_t = StartingMark::Cross)]
     starting_mark: StartingMark,
+    #[arg(short, long, value_enum, default_value_t = GameVariant::Standard)]
+    variant: GameVariant,
+    #[arg(short, long, value_enum, default_value_t = ErrorOutput::Text)]
+    errors: ErrorOutput,
+    /// How the game is shown: `colored` colors the marks when the terminal supports it,
+    /// `json` writes each position as a line of JSON for other tools
+    #[arg(long, value_enum, default_value_t = RendererType::Plain)]
+    renderer: RendererType,
+    /// Append each frame of the game after the previous one instead of clearing the screen,
+    /// e.g. to keep the scrollback or to write the game to a file
+    #[arg(long)]
+    no_clear: bool,
+    /// Draw the grid in plain ASCII, the default when the terminal is not expected to draw Unicode
+    #[arg(long)]
+    ascii: bool,
+    /// How the cells are designated: `numpad` numbers them from 1 to 9 like a phone keypad,
+    /// and the human players type these numbers
+    #[arg(long, value_enum, default_value_t = LayoutArg::Coordinates)]
+    layout: LayoutArg,
+    /// Where the game is played: `tui` plays it full-screen, moving a cursor with the arrow keys,
+    /// when built with the `tui` feature, and `gui` in a window against the second player,
+    /// when built with the `gui` feature
+    #[arg(long, value_enum, default_value_t = FrontendArg::Console)]
+    frontend: FrontendArg,
+    #[arg(long, value_name = "GAMES")]
+    pub(super) train: Option<usize>,
+    /// The time in milliseconds an external, network or telnet player has for each move,
+    /// a random move is played when it runs out
+    #[arg(long, value_name = "MILLISECONDS")]
+    move_time: Option<u64>,
+    /// The number of invalid moves in a row a player can make before forfeiting the game,
+    /// 3 when not given; a player whose connection or pipe is closed forfeits at once
+    #[arg(long, value_name = "MOVES")]
+    max_invalid_moves: Option<usize>,
+    /// The time in seconds each player has for the whole game, followed by the seconds
+    /// added after each move, e.g. `300+2`; a player whose time runs out loses
+    #[arg(long, value_name = "SECONDS[+INCREMENT]", value_parser = parse_time_control)]
+    time_control: Option<TimeControl>,
+    /// Join the game hosted on the address, playing the moves of the second player
+    #[arg(long, value_name = "ADDRESS")]
+    pub(super) join: Option<String>,
+    /// The value table of the learning player, trained with `--train`
+    /// and updated after each game the learning player plays
+    #[arg(long, value_name = "PATH", default_value = "learning_table.txt")]
+    pub(super) table: PathBuf,
+    /// Resume the game saved to the file with the `save` command
+    #[arg(long, value_name = "PATH")]
+    resume: Option<PathBuf>,
+    /// Store every finished game in the JSON-lines file, see the `history` command
+    #[arg(long, value_name = "PATH")]
+    history: Option<PathBuf>,
+    /// The number of games of the match, the players take turns to start
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    games: usize,
+    /// Write the record of the game to the file at the end of the game, the last game of a match
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+    #[command(subcommand)]
+    pub(super) command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub(super) enum Command {
+    /// Replay a game recorded with `--record`, move by move
+    Replay {
+        /// The file of the game record
+        path: PathBuf,
+        /// The delay in milliseconds between two moves, each move waits for Enter when not given
+        #[arg(long, value_name = "MILLISECONDS")]
+        delay: Option<u64>,
+    },
+    /// Show the outcome of every move of the position with perfect play
+    Analyze {
+        /// The position in the compact text notation, e.g. `X.O/.X./..O X`
+        position: String,
+    },
+    /// Play games between the first and the second player without showing them,
+    /// and report the games per second, the nodes searched and the results
+    Bench {
+        /// The number of games
+        #[arg(long, value_name = "N", default_value_t = 100)]
+        games: usize,
+    },
+    /// List the games stored with `--history`, or show one of them
+    History {
+        /// The JSON-lines file of the history
+        path: PathBuf,
+        /// The number of the game to show
+        #[arg(long, value_name = "ID")]
+        show: Option<usize>,
+    },
+    /// Rank the players of the games stored with `--history` by win rate and rating
+    Leaderboard {
+        /// The JSON-lines file of the history
+        path: PathBuf,
+    },
+    /// Host a playable board on a web server, the opponent is picked on the page for each game
+    Serve {
+        /// The address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: String,
+    },
+    /// Play a round-robin tournament between computer players and show the standings
+    Tournament {
+        /// The computer players, at least two
+        #[arg(value_enum, required = true, num_args = 2..)]
+        players: Vec<PlayerType>,
+        /// The number of games between every two players
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        games: usize,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
-enum PlayerType {
+pub(super) enum PlayerType {
     Human,
     ComputerMinimax,
+    ComputerCasual,
+    ComputerBlitz,
+    ComputerMcts,
     ComputerRandom,
+    ComputerPerfect,
+    ComputerHeuristic,
+    ComputerLearning,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum PlayerArg {
+    BuiltIn(PlayerType),
+    External(String),
+    Network(String),
+    Telnet(String),
+}
+
+fn parse_player_arg(value: &str) -> Result<PlayerArg, String> {
+    match value.strip_prefix("external:") {
+        Some(command) if command.trim().is_empty() => {
+            Err(String::from("the external player needs a command"))
+        }
+        Some(command) => Ok(PlayerArg::External(command.to_string())),
+        None => match value.strip_prefix("network:") {
+            Some(address) if address.trim().is_empty() => {
+                Err(String::from("the network player needs an address"))
+            }
+            Some(address) => Ok(PlayerArg::Network(address.to_string())),
+            None => match value.strip_prefix("telnet:") {
+                Some(address) if address.trim().is_empty() => {
+                    Err(String::from("the telnet player needs an address"))
+                }
+                Some(address) => Ok(PlayerArg::Telnet(address.to_string())),
+                None => PlayerType::from_str(value, true).map(PlayerArg::BuiltIn),
+            },
+        },
+    }
+}
+
+fn parse_time_control(value: &str) -> Result<TimeControl, String> {
+    let (initial, increment) = value.split_once('+').unwrap_or((value, "0"));
+    let seconds = |text: &str| {
+        text.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+            .map(Duration::from_secs_f64)
+            .ok_or_else(|| format!("`{}` is not a number of seconds", text))
+    };
+    Ok(TimeControl::new(seconds(initial)?).with_increment(seconds(increment)?))
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -31,46 +224,398 @@ enum StartingMark {
     Naught,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum GameVariant {
+    Standard,
+    Wild,
+    Notakto,
+    Gravity,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum RendererType {
+    Plain,
+    Colored,
+    Json,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum LayoutArg {
+    Coordinates,
+    Numpad,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum FrontendArg {
+    Console,
+    #[cfg(feature = "tui")]
+    Tui,
+    #[cfg(feature = "gui")]
+    Gui,
+}
+
+/// The frontend of the human players.
+enum HumanFrontend {
+    /// The players type the cells in the layout.
+    Console(BoardLayout),
+    /// The players move a cursor on the full screen.
+    #[cfg(feature = "tui")]
+    Tui(Tui),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum ErrorOutput {
+    Text,
+    Json,
+}
+
+/// The game played from the command line, with its players and its renderer.
 pub(super) struct GameConfig {
     pub(super) player1: Box<dyn Player>,
     pub(super) player2: Box<dyn Player>,
     pub(super) renderer: Box<dyn Renderer>,
     pub(super) starting_mark: Mark,
+    pub(super) variant: Variant,
+    pub(super) resume: Option<PathBuf>,
+    pub(super) record: Option<PathBuf>,
+    pub(super) invalid_move_policy: InvalidMovePolicy,
+    pub(super) time_control: Option<TimeControl>,
+    pub(super) games: usize,
+    pub(super) history: Option<GameStore>,
+    /// The learners of the learning players, saving their table to `table` after the games.
+    pub(super) learners: Vec<Learner>,
+    pub(super) table: PathBuf,
+    pub(super) control: GameControl,
+}
+
+/// The player joining a game hosted on another machine, and the renderer of the game.
+pub(super) struct Seat {
+    pub(super) player: Box<dyn Player>,
+    pub(super) renderer: Box<dyn Renderer>,
+}
+
+/// Returns the error format asked on the command line, for the errors met while parsing it.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the command line.
+pub(super) fn requested_error_format(args: impl IntoIterator<Item = String>) -> ErrorFormat {
+    let mut format = ErrorFormat::Text;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "-e" | "--errors" => args.next(),
+            _ => arg
+                .strip_prefix("--errors=")
+                .or_else(|| arg.strip_prefix("-e"))
+                .map(str::to_string),
+        };
+        if let Some(value) = value {
+            format = match value.as_str() {
+                "json" => ErrorFormat::Json,
+                _ => ErrorFormat::Text,
+            };
+        }
+    }
+    format
 }
 
-pub(super) fn parse_cli(cli: Cli) -> GameConfig {
-    let player1;
+impl Cli {
+    pub(super) fn error_format(&self) -> ErrorFormat {
+        match self.errors {
+            ErrorOutput::Text => ErrorFormat::Text,
+            ErrorOutput::Json => ErrorFormat::Json,
+        }
+    }
+
+    pub(super) fn variant(&self) -> Variant {
+        match self.variant {
+            GameVariant::Standard => Variant::Standard,
+            GameVariant::Wild => Variant::Wild,
+            GameVariant::Notakto => Variant::Notakto,
+            GameVariant::Gravity => Variant::Gravity,
+        }
+    }
 
-    if let PlayerType::Human = cli.player1 {
-        player1 = Box::new(ConsolePlayer::new(Mark::Cross)) as Box<dyn Player>;
-    } else if let PlayerType::ComputerMinimax = cli.player1 {
-        player1 = Box::new(MinimaxPlayer::new(Mark::Cross)) as Box<dyn Player>;
-    } else {
-        player1 = Box::new(DumbPlayer::new(Mark::Cross)) as Box<dyn Player>;
+    /// Returns the opponent of the game played in a window, none to play in the terminal.
+    /// Returns an error if the window cannot play against the second player.
+    #[cfg(feature = "gui")]
+    pub(super) fn gui_opponent(&self) -> Result<Option<Opponent>, Error> {
+        if self.frontend != FrontendArg::Gui {
+            return Ok(None);
+        }
+        let opponent = match &self.player2 {
+            PlayerArg::BuiltIn(PlayerType::Human) => Opponent::Human,
+            PlayerArg::BuiltIn(PlayerType::ComputerRandom) => Opponent::Random,
+            PlayerArg::BuiltIn(PlayerType::ComputerCasual) => Opponent::Casual,
+            PlayerArg::BuiltIn(PlayerType::ComputerHeuristic) => Opponent::Heuristic,
+            PlayerArg::BuiltIn(PlayerType::ComputerPerfect) => Opponent::Perfect,
+            _ => {
+                return Err(Error::ConfigError(String::from(
+                    "the window plays against human, computer-random, computer-casual, \
+                     computer-heuristic or computer-perfect only",
+                )))
+            }
+        };
+        Ok(Some(opponent))
     }
 
-    let player2;
+    /// Returns the renderer of the terminal, for the commands showing a game without playing it.
+    pub(super) fn console_renderer(&self) -> Box<dyn Renderer> {
+        self.renderer(&HumanFrontend::Console(self.layout()))
+    }
+
+    /// Creates the frontend of the human players, taking the terminal over with the `tui` frontend,
+    /// and the control of the games, interrupted when a player quits the full screen.
+    fn frontend(&self) -> Result<(HumanFrontend, GameControl), Error> {
+        let control = GameControl::new();
+        let frontend = match self.frontend {
+            FrontendArg::Console => HumanFrontend::Console(self.layout()),
+            #[cfg(feature = "tui")]
+            FrontendArg::Tui => HumanFrontend::Tui(
+                Tui::new(control.clone())
+                    .map_err(|error| Error::io("cannot start the full-screen frontend", &error))?,
+            ),
+            // The window has its own players, picked from the second player.
+            #[cfg(feature = "gui")]
+            FrontendArg::Gui => HumanFrontend::Console(self.layout()),
+        };
+        Ok((frontend, control))
+    }
+
+    fn layout(&self) -> BoardLayout {
+        match self.layout {
+            LayoutArg::Coordinates => BoardLayout::Coordinates,
+            LayoutArg::Numpad => BoardLayout::Numpad,
+        }
+    }
+
+    fn renderer(&self, frontend: &HumanFrontend) -> Box<dyn Renderer> {
+        let layout = match frontend {
+            HumanFrontend::Console(layout) => *layout,
+            #[cfg(feature = "tui")]
+            HumanFrontend::Tui(tui) => return Box::new(tui.renderer()),
+        };
+        let style = if self.ascii {
+            RendererStyle::ascii()
+        } else {
+            RendererStyle::detect()
+        }
+        .with_layout(layout);
+        match self.renderer {
+            RendererType::Plain => Box::new(
+                ConsoleRenderer::new()
+                    .with_style(style)
+                    .with_clear_screen(!self.no_clear),
+            ),
+            RendererType::Colored => Box::new(
+                ColoredConsoleRenderer::new()
+                    .with_style(style)
+                    .with_clear_screen(!self.no_clear),
+            ),
+            RendererType::Json => Box::new(JsonRenderer::new(io::stdout())),
+        }
+    }
 
-    if let PlayerType::Human = cli.player2 {
-        player2 = Box::new(ConsolePlayer::new(Mark::Naught)) as Box<dyn Player>;
-    } else if let PlayerType::ComputerMinimax = cli.player2 {
-        player2 = Box::new(MinimaxPlayer::new(Mark::Naught)) as Box<dyn Player>;
-    } else {
-        player2 = Box::new(DumbPlayer::new(Mark::Naught)) as Box<dyn Player>;
+    /// Creates the player of the mark, the first player for the crosses and the second for the naughts.
+    /// The learner of a learning player is added to the learners.
+    fn player(
+        &self,
+        mark: Mark,
+        frontend: &HumanFrontend,
+        learners: &mut Vec<Learner>,
+    ) -> Result<Box<dyn Player>, Error> {
+        let (player_arg, name) = match mark {
+            Mark::Cross => (&self.player1, self.name1.as_deref()),
+            Mark::Naught => (&self.player2, self.name2.as_deref()),
+        };
+        let move_time = self.move_time.map(Duration::from_millis);
+        create_player(
+            player_arg,
+            mark,
+            name,
+            move_time,
+            &self.table,
+            frontend,
+            learners,
+        )
     }
 
-    let starting_mark = if let StartingMark::Cross = cli.starting_mark {
-        Mark::Cross
-    } else {
-        Mark::Naught
+    /// Creates the player of the mark for the benchmark, which is not shown.
+    pub(super) fn bench_player(&self, mark: Mark) -> Result<Box<dyn Player>, Error> {
+        self.player(mark, &HumanFrontend::Console(self.layout()), &mut vec![])
+    }
+
+    /// Creates the second player and the renderer, to join a game hosted on another machine.
+    pub(super) fn joining_seat(&self) -> Result<Seat, Error> {
+        let (frontend, _) = self.frontend()?;
+        Ok(Seat {
+            player: self.player(Mark::Naught, &frontend, &mut vec![])?,
+            renderer: self.renderer(&frontend),
+        })
+    }
+
+    /// Creates the round-robin tournament between the computer players.
+    pub(super) fn tournament(
+        &self,
+        player_types: &[PlayerType],
+        games: usize,
+    ) -> Result<Tournament, Error> {
+        create_tournament(player_types, games, self.variant(), &self.table)
+    }
+
+    /// Creates the game to play, with its players and its renderer.
+    pub(super) fn game_config(self) -> Result<GameConfig, Error> {
+        let (frontend, control) = self.frontend()?;
+        let mut learners = vec![];
+        let player1 = self.player(Mark::Cross, &frontend, &mut learners)?;
+        let player2 = self.player(Mark::Naught, &frontend, &mut learners)?;
+        let starting_mark = match self.starting_mark {
+            StartingMark::Cross => Mark::Cross,
+            StartingMark::Naught => Mark::Naught,
+        };
+
+        Ok(GameConfig {
+            player1,
+            player2,
+            renderer: self.renderer(&frontend),
+            starting_mark,
+            variant: self.variant(),
+            resume: self.resume,
+            record: self.record,
+            invalid_move_policy: self
+                .max_invalid_moves
+                .map_or(InvalidMovePolicy::default(), InvalidMovePolicy::Retry),
+            time_control: self.time_control,
+            games: self.games,
+            history: self.history.as_deref().map(GameStore::open),
+            learners,
+            table: self.table,
+            control,
+        })
+    }
+}
+
+fn create_tournament(
+    player_types: &[PlayerType],
+    games: usize,
+    variant: Variant,
+    table: &Path,
+) -> Result<Tournament, Error> {
+    let mut tournament = Tournament::new(games).with_variant(variant);
+    for player_type in player_types {
+        if *player_type == PlayerType::Human {
+            return Err(Error::ConfigError(String::from(
+                "only computer players can enter a tournament",
+            )));
+        }
+        let name = player_type
+            .to_possible_value()
+            .map_or_else(String::new, |value| value.get_name().to_string());
+        let (player_type, table) = (*player_type, table.to_path_buf());
+        let create = move |mark| {
+            create_player(
+                &PlayerArg::BuiltIn(player_type),
+                mark,
+                None,
+                None,
+                &table,
+                &HumanFrontend::Console(BoardLayout::default()),
+                &mut vec![],
+            )
+        };
+        // A player which cannot be created, e.g. with an invalid table, is reported before the games.
+        create(Mark::Cross)?;
+        tournament = tournament.entrant(&name, move |mark| {
+            create(mark).expect("The player was created before the tournament")
+        });
+    }
+    Ok(tournament)
+}
+
+fn create_player(
+    player_arg: &PlayerArg,
+    mark: Mark,
+    name: Option<&str>,
+    move_time: Option<Duration>,
+    table: &Path,
+    frontend: &HumanFrontend,
+    learners: &mut Vec<Learner>,
+) -> Result<Box<dyn Player>, Error> {
+    let player_type = match player_arg {
+        PlayerArg::BuiltIn(player_type) => *player_type,
+        PlayerArg::External(command) => {
+            let mut words = command.split_whitespace();
+            let program = words.next().unwrap_or_default();
+            let args: Vec<&str> = words.collect();
+            return ExternalProcessPlayer::spawn(mark, program, &args)
+                .map(|player| time_limited(Box::new(player), move_time))
+                .map_err(|error| Error::io(&format!("cannot start `{}`", command), &error));
+        }
+        PlayerArg::Network(address) => {
+            println!("Waiting for a player to join on {}...", address);
+            return TcpListener::bind(address)
+                .and_then(|listener| NetworkPlayer::accept(mark, &listener))
+                .map(|player| time_limited(Box::new(player), move_time))
+                .map_err(|error| Error::io(&format!("cannot host on `{}`", address), &error));
+        }
+        PlayerArg::Telnet(address) => {
+            println!(
+                "Waiting for the {} player to connect with `telnet` on {}...",
+                mark, address
+            );
+            return TcpListener::bind(address)
+                .and_then(|listener| TelnetPlayer::accept(mark, &listener))
+                .map(|player| time_limited(Box::new(player), move_time))
+                .map_err(|error| Error::io(&format!("cannot host on `{}`", address), &error));
+        }
     };
 
-    let renderer = Box::new(ConsoleRenderer {}) as Box<dyn Renderer>;
+    Ok(match player_type {
+        PlayerType::Human => match frontend {
+            HumanFrontend::Console(layout) => {
+                let player = ConsolePlayer::new(mark).with_layout(*layout);
+                match name {
+                    Some(name) => Box::new(player.with_name(name)),
+                    None => Box::new(player),
+                }
+            }
+            #[cfg(feature = "tui")]
+            HumanFrontend::Tui(tui) => {
+                let player = tui.player(mark);
+                match name {
+                    Some(name) => Box::new(player.with_name(name)),
+                    None => Box::new(player),
+                }
+            }
+        },
+        PlayerType::ComputerMinimax => AiBuilder::perfect().build(mark),
+        PlayerType::ComputerCasual => AiBuilder::casual().build(mark),
+        PlayerType::ComputerBlitz => AiBuilder::blitz().build(mark),
+        PlayerType::ComputerMcts => AiBuilder::experimental_mcts().build(mark),
+        PlayerType::ComputerRandom => Box::new(DumbPlayer::new(mark)),
+        PlayerType::ComputerPerfect => Box::new(PerfectPlayer::new(mark)),
+        PlayerType::ComputerHeuristic => Box::new(HeuristicPlayer::new(mark)),
+        PlayerType::ComputerLearning => {
+            let player = LearningPlayer::load_or_new(mark, table).map_err(|error| {
+                Error::io(
+                    &format!("cannot load the learning table `{}`", table.display()),
+                    &error,
+                )
+            })?;
+            learners.push(player.learner());
+            Box::new(player)
+        }
+    })
+}
 
-    GameConfig {
-        player1,
-        player2,
-        renderer,
-        starting_mark,
+fn time_limited(player: Box<dyn Player + Send>, move_time: Option<Duration>) -> Box<dyn Player> {
+    match move_time {
+        Some(move_time) => Box::new(TimedPlayer::new(
+            player,
+            move_time,
+            TimeoutPolicy::RandomMove,
+        )),
+        None => player,
     }
 }
@@ -0,0 +1,148 @@
+//! The reporting of errors on the standard error of the cli
+//! Errors are either printed as text or as one JSON object per line,
+//! so wrappers can present them without parsing the text
+
+use crate::{
+    game::json::escape_json,
+    logic::errors::{Error, MoveError, ValidationError},
+};
+
+/// The format used to report errors on the standard error.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub enum ErrorFormat {
+    /// A human readable message.
+    #[default]
+    Text,
+    /// A JSON object with the `code`, `message` and `context` of the error.
+    Json,
+}
+
+/// Print the error on the standard error with the given format
+///
+/// # Arguments
+///
+/// * error - The `Error` to report
+/// * format - The `ErrorFormat` of the report
+pub fn report_error(error: &Error, format: ErrorFormat) {
+    match format {
+        ErrorFormat::Text => eprintln!("{}", error_message(error)),
+        ErrorFormat::Json => eprintln!("{}", error_to_json(error)),
+    }
+}
+
+/// Format the error as a single line JSON object
+///
+/// # Arguments
+///
+/// * error - The `Error` to format
+pub fn error_to_json(error: &Error) -> String {
+    let context = error_context(error)
+        .iter()
+        .map(|(key, value)| format!("\"{}\":\"{}\"", key, escape_json(value)))
+        .collect::<Vec<String>>()
+        .join(",");
+    format!(
+        "{{\"code\":\"{}\",\"message\":\"{}\",\"context\":{{{}}}}}",
+        error.code(),
+        escape_json(&error_message(error)),
+        context
+    )
+}
+
+/// Returns the message of the error, including the message of the inner error.
+fn error_message(error: &Error) -> String {
+    match error {
+        Error::ConfigError(_) | Error::IoError(_) => error.to_string(),
+        Error::MoveError(inner) => format!("{}: {}", error, inner),
+        Error::ValidationError(inner) => format!("{}: {}", error, inner),
+    }
+}
+
+/// Returns the values attached to the error as key value pairs.
+fn error_context(error: &Error) -> Vec<(&'static str, String)> {
+    match error {
+        Error::ConfigError(_)
+        | Error::IoError(_)
+        | Error::MoveError(MoveError::NoPossibleMoves)
+        | Error::ValidationError(ValidationError::GameNotOver) => vec![],
+        Error::MoveError(MoveError::NotYourTurn(mark)) => vec![("mark", mark.to_string())],
+        Error::MoveError(MoveError::CellAlreadyMarked(index)) => {
+            vec![("cell_index", index.to_string())]
+        }
+        Error::MoveError(MoveError::MarkNotAllowed(mark)) => vec![("mark", mark.to_string())],
+        Error::MoveError(MoveError::CellNotSupported(index))
+        | Error::MoveError(MoveError::CellOutOfRange(index))
+        | Error::ValidationError(ValidationError::FloatingMark(index)) => {
+            vec![("cell_index", index.to_string())]
+        }
+        Error::MoveError(MoveError::ColumnFull(column)) => vec![("column", column.to_string())],
+        Error::ValidationError(ValidationError::WrongNumberOfNaughtsAndCrosses(
+            naught_count,
+            cross_count,
+        )) => vec![
+            ("naught_count", naught_count.to_string()),
+            ("cross_count", cross_count.to_string()),
+        ],
+        Error::ValidationError(ValidationError::WrongStartingMark(mark))
+        | Error::ValidationError(ValidationError::WrongWinnerMark(mark))
+        | Error::ValidationError(ValidationError::MarkNotAllowed(mark)) => {
+            vec![("mark", mark.to_string())]
+        }
+        Error::ValidationError(ValidationError::InvalidNotation(notation)) => {
+            vec![("notation", notation.clone())]
+        }
+        Error::ValidationError(ValidationError::InvalidCode(code)) => {
+            vec![("code", code.to_string())]
+        }
+        Error::ValidationError(ValidationError::InvalidMark(mark)) => {
+            vec![("mark", mark.clone())]
+        }
+        Error::ValidationError(ValidationError::InvalidGrid(grid)) => {
+            vec![("grid", grid.clone())]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::logic::Mark;
+
+    #[test]
+    fn test_error_to_json_move_error() {
+        let error = Error::MoveError(MoveError::CellAlreadyMarked(4));
+        assert_eq!(
+            error_to_json(&error),
+            r#"{"code":"cell_already_marked","message":"Move Error: Cell `4`  is already marked","context":{"cell_index":"4"}}"#
+        );
+    }
+
+    #[test]
+    fn test_error_to_json_config_error() {
+        let error = Error::ConfigError(String::from("same \"mark\": X"));
+        assert_eq!(
+            error_to_json(&error),
+            r#"{"code":"config_error","message":"Configuration Error: `same \"mark\": X`","context":{}}"#
+        );
+    }
+
+    #[test]
+    fn test_error_to_json_io_error() {
+        let error = Error::io(
+            "cannot save the game to `game.txt`",
+            &io::Error::new(io::ErrorKind::PermissionDenied, "permission denied"),
+        );
+        assert_eq!(
+            error_to_json(&error),
+            r#"{"code":"io_error","message":"I/O Error: `cannot save the game to `game.txt`: permission denied`","context":{}}"#
+        );
+    }
+
+    #[test]
+    fn test_error_to_json_validation_error() {
+        let error = Error::ValidationError(ValidationError::WrongStartingMark(Mark::Naught));
+        assert!(error_to_json(&error).contains(r#""context":{"mark":"O"}"#));
+    }
+}
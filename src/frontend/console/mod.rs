@@ -1,5 +1,7 @@
 //! The frontend to be used when played using cli
 //! Contain a part for the player using the cli
 //! And contain the renderer for the cli
+//! And contain the reporting of errors for the cli
+pub mod errors;
 pub mod players;
 pub mod renderers;
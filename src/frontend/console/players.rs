@@ -1,19 +1,114 @@
 //! The player used in the cli
 
 use std::io;
+use std::path::PathBuf;
 
 use crate::{
-    game::players::Player,
-    logic::{GameMove, GameState, Mark},
+    frontend::console::renderers::BoardLayout,
+    game::players::{Player, PlayerCommand},
+    logic::{GameMove, GameState, Mark, Variant},
 };
 
+/// The file the game is saved to when the `save` command has no path
+const DEFAULT_SAVE_PATH: &str = "saved_game.txt";
+
 pub struct ConsolePlayer {
     mark: Mark,
+    name: Option<String>,
+    command: Option<PlayerCommand>,
+    layout: BoardLayout,
 }
 
 impl ConsolePlayer {
     pub fn new(mark: Mark) -> Self {
-        ConsolePlayer { mark }
+        ConsolePlayer {
+            mark,
+            name: None,
+            command: None,
+            layout: BoardLayout::Coordinates,
+        }
+    }
+
+    /// Set the name shown for the player
+    ///
+    /// # Arguments
+    ///
+    /// * name - The name of the player
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Set how the player designates the cells, by their coordinates, e.g. `B2`, or by their number, e.g. `5`
+    /// The coordinates are still accepted in the `BoardLayout::Numpad` layout
+    ///
+    /// # Arguments
+    ///
+    /// * layout - The layout of the board
+    pub fn with_layout(mut self, layout: BoardLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Get the index of the cell designated by the input of the player, in the layout of the player
+    ///
+    /// # Arguments
+    ///
+    /// * input - The input of the player, e.g. `B2`, or `5` in the `BoardLayout::Numpad` layout
+    fn cell_index(&self, input: &str) -> Option<usize> {
+        match self.layout {
+            BoardLayout::Coordinates => coord_to_index(input),
+            BoardLayout::Numpad => digit_to_index(input).or_else(|| coord_to_index(input)),
+        }
+    }
+
+    /// Get the column where the player drops their mark
+    /// Using the standard input, for the `Variant::Gravity` rules
+    ///
+    /// # Arguments
+    ///
+    /// * game_state - The curent `GameState` of the game
+    fn get_column_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        while !game_state.game_over() {
+            println!("{}'s move (column A, B or C): ", self.mark);
+
+            let Some(input_string) = read_input() else {
+                return self.send_command(PlayerCommand::Resign);
+            };
+
+            if let Some(command) = parse_command(input_string.trim()) {
+                return self.send_command(command);
+            }
+
+            match column_to_index(input_string.trim()) {
+                Some(column) => {
+                    if let Ok(next_move) = game_state.make_move_to_column(column) {
+                        return Some(next_move);
+                    };
+                    println!("That column is full.");
+                }
+                None => {
+                    println!("Invalid input. Try again. The input shall be a column A, B or C.");
+                }
+            }
+        }
+        None
+    }
+
+    /// Ask the engine to run the command instead of making a move
+    ///
+    /// # Arguments
+    ///
+    /// * command - The command typed by the player
+    fn send_command(&mut self, command: PlayerCommand) -> Option<GameMove> {
+        match &command {
+            PlayerCommand::Save(path) => println!("Saving the game to {}", path.display()),
+            PlayerCommand::Undo => println!("Taking back your last move"),
+            PlayerCommand::Redo => println!("Playing the moves taken back again"),
+            PlayerCommand::Resign => println!("The input is closed, leaving the game"),
+        }
+        self.command = Some(command);
+        None
     }
 }
 
@@ -24,20 +119,45 @@ impl Player for ConsolePlayer {
     /// # Arguments
     ///
     /// * game_state - The curent `GameState` of the game
-    fn get_move(&self, game_state: &GameState) -> Option<GameMove> {
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        if game_state.variant() == Variant::Gravity {
+            return self.get_column_move(game_state);
+        }
         while !game_state.game_over() {
-            let mut input_string = String::new();
+            if game_state.variant() == Variant::Wild {
+                let example = match self.layout {
+                    BoardLayout::Coordinates => "A1O",
+                    BoardLayout::Numpad => "1O",
+                };
+                println!(
+                    "{}'s move (add X or O to choose the mark, e.g. {}): ",
+                    self.mark, example
+                );
+            } else {
+                println!("{}'s move: ", self.mark);
+            }
 
-            println!("{}'s move: ", self.mark);
+            let Some(input_string) = read_input() else {
+                return self.send_command(PlayerCommand::Resign);
+            };
 
-            io::stdin()
-                .read_line(&mut input_string)
-                .expect("Failed to read input.");
+            if let Some(command) = parse_command(input_string.trim()) {
+                return self.send_command(command);
+            }
 
-            match coord_to_index(input_string.trim()) {
+            let (coord, placed_mark) = match game_state.variant() {
+                Variant::Wild => split_placed_mark(input_string.trim()),
+                _ => (input_string.trim(), None),
+            };
+
+            match self.cell_index(coord) {
                 Some(input) => {
                     if (0..9).contains(&input) {
-                        if let Ok(next_move) = game_state.make_move_to(input) {
+                        let next_move = match placed_mark {
+                            Some(mark) => game_state.make_move_with(input, mark),
+                            None => game_state.make_move_to(input),
+                        };
+                        if let Ok(next_move) = next_move {
                             return Some(next_move);
                         };
                         println!("That cell is already occupied.");
@@ -45,11 +165,14 @@ impl Player for ConsolePlayer {
                         println!("Invalid input. Try again. ");
                     }
                 }
-                None => {
-                    println!(
+                None => match self.layout {
+                    BoardLayout::Coordinates => println!(
                         "Invalid input. Try again. The input shall be in the format A1 or 1A."
-                    );
-                }
+                    ),
+                    BoardLayout::Numpad => println!(
+                        "Invalid input. Try again. The input shall be a number from 1 to 9 or in the format A1."
+                    ),
+                },
             }
         }
         None
@@ -58,9 +181,73 @@ impl Player for ConsolePlayer {
     fn get_mark(&self) -> Mark {
         self.mark
     }
+
+    fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.kind().to_string())
+    }
+
+    fn kind(&self) -> &'static str {
+        "Human"
+    }
+
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        self.command.take()
+    }
 }
 
-fn coord_to_index(coord: &str) -> Option<usize> {
+/// Read a line typed by the player
+/// Returns `None` if the input is closed or cannot be read
+fn read_input() -> Option<String> {
+    let mut input_string = String::new();
+    match io::stdin().read_line(&mut input_string) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input_string),
+    }
+}
+
+/// Get the command of the player, if the input is one
+/// The commands are `undo`, `redo` and `save`, optionally followed by the path of the file, e.g. `save my_game.txt`
+///
+/// # Arguments
+///
+/// * input - The input of the player
+fn parse_command(input: &str) -> Option<PlayerCommand> {
+    match input.split_once(' ') {
+        Some(("save", path)) => Some(PlayerCommand::Save(PathBuf::from(path.trim()))),
+        None => match input {
+            "save" => Some(PlayerCommand::Save(PathBuf::from(DEFAULT_SAVE_PATH))),
+            "undo" => Some(PlayerCommand::Undo),
+            "redo" => Some(PlayerCommand::Redo),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Split the optional mark to place from the end of the input.
+/// If no mark is given, the player places their own mark.
+///
+/// # Arguments
+///
+/// * input - The input of the player, e.g. `A1O`
+pub(crate) fn split_placed_mark(input: &str) -> (&str, Option<Mark>) {
+    match input.chars().last() {
+        Some('X') | Some('x') => (input[..input.len() - 1].trim(), Some(Mark::Cross)),
+        Some('O') | Some('o') => (input[..input.len() - 1].trim(), Some(Mark::Naught)),
+        _ => (input, None),
+    }
+}
+
+pub(crate) fn column_to_index(column: &str) -> Option<usize> {
+    match column {
+        "A" | "a" => Some(0),
+        "B" | "b" => Some(1),
+        "C" | "c" => Some(2),
+        _ => None,
+    }
+}
+
+pub(crate) fn coord_to_index(coord: &str) -> Option<usize> {
     let chars: Vec<char> = coord.chars().collect();
     if chars.len() != 2 {
         return None;
@@ -71,6 +258,34 @@ fn coord_to_index(coord: &str) -> Option<usize> {
         ('1'..='3', 'A'..='C') => (chars[1] as u8 - b'A', chars[0] as u8 - b'1'),
         _ => return None,
     };
-    print!("{} {} ", row, col);
     Some(row as usize * 3 + col as usize)
 }
+
+/// Get the index of the cell numbered from 1 to 9 row by row, like the keys of a phone keypad
+///
+/// # Arguments
+///
+/// * input - The number of the cell, e.g. `5` for the center
+pub(crate) fn digit_to_index(input: &str) -> Option<usize> {
+    match input.as_bytes() {
+        [digit @ b'1'..=b'9'] => Some((digit - b'1') as usize),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_index() {
+        let player = ConsolePlayer::new(Mark::Cross);
+        assert_eq!(player.cell_index("B2"), Some(4));
+        assert_eq!(player.cell_index("5"), None);
+        let player = player.with_layout(BoardLayout::Numpad);
+        assert_eq!(player.cell_index("1"), Some(0));
+        assert_eq!(player.cell_index("9"), Some(8));
+        assert_eq!(player.cell_index("0"), None);
+        assert_eq!(player.cell_index("C1"), Some(2));
+    }
+}
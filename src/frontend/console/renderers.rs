@@ -1,10 +1,193 @@
-//! The renderer which is used in the cli interface
+//! The renderers which are used in the cli interface
+//! The `ConsoleRenderer` draws the grid as plain text,
+//! the `ColoredConsoleRenderer` also colors the marks
+//! Both highlight the winning line at the end of the game,
+//! between brackets for the former and in inverse video for the latter
+//! They clear the screen before each frame, unless they are set to append the frames,
+//! e.g. to keep the scrollback or to write the game to a file
+//! Both draw the cells with the glyphs of their `RendererStyle`,
+//! and the lines of the grid with box-drawing characters or, for the terminals without Unicode, in plain ASCII
+//! The cells are designated by their coordinates, e.g. `B2`, or by their number in the `BoardLayout::Numpad` layout
+use std::env;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
 use crate::{
-    game::renderers::Renderer,
-    logic::{GameState, Grid},
+    game::{
+        clock::Clocks,
+        players::{display_name, Player},
+        renderers::Renderer,
+    },
+    logic::{errors::MoveError, Cell, GameMove, GameState, Grid, Mark},
 };
 
-pub struct ConsoleRenderer;
+/// How the cells are designated on the board drawn by the console renderers and typed by the console players.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum BoardLayout {
+    /// The columns are letters and the rows are digits, e.g. `B2`.
+    #[default]
+    Coordinates,
+    /// The cells are numbered from 1 to 9 row by row, like the keys of a phone keypad,
+    /// and the empty cells show their number.
+    Numpad,
+}
+
+/// The style of the console renderers: the glyphs drawn for the crosses, the naughts and the empty cells,
+/// the characters of the lines of the grid and the layout of the board.
+/// The grid lines up when the three glyphs take as many columns, e.g. `❌`, `⭕` and two spaces.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RendererStyle {
+    cross: String,
+    naught: String,
+    empty: String,
+    ascii: bool,
+    layout: BoardLayout,
+}
+
+impl RendererStyle {
+    /// Create the style drawing the marks as `X` and `O`, and the empty cells as a space
+    pub fn new() -> Self {
+        RendererStyle {
+            cross: Mark::Cross.to_string(),
+            naught: Mark::Naught.to_string(),
+            empty: String::from(" "),
+            ascii: false,
+            layout: BoardLayout::Coordinates,
+        }
+    }
+
+    /// Create the style drawing the lines of the grid in plain ASCII, e.g. `+---+`
+    pub fn ascii() -> Self {
+        Self::new().with_ascii(true)
+    }
+
+    /// Create the default style, in plain ASCII when the terminal is not expected to draw Unicode:
+    /// when `TERM` is `dumb`, when the locale is not UTF-8, or on Windows outside of Windows Terminal
+    pub fn detect() -> Self {
+        Self::new().with_ascii(!supports_unicode())
+    }
+
+    /// Create the style drawing the marks as `❌` and `⭕`
+    pub fn emoji() -> Self {
+        Self::new().with_glyphs("❌", "⭕", "  ")
+    }
+
+    /// Set the glyphs of the cells
+    ///
+    /// # Arguments
+    ///
+    /// * cross - the glyph of the crosses
+    /// * naught - the glyph of the naughts
+    /// * empty - the glyph of the empty cells
+    pub fn with_glyphs(mut self, cross: &str, naught: &str, empty: &str) -> Self {
+        self.cross = cross.to_string();
+        self.naught = naught.to_string();
+        self.empty = empty.to_string();
+        self
+    }
+
+    /// Set whether the lines of the grid are drawn in plain ASCII or with box-drawing characters
+    ///
+    /// # Arguments
+    ///
+    /// * ascii - whether the lines are drawn in plain ASCII
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Set how the cells are designated on the board
+    ///
+    /// # Arguments
+    ///
+    /// * layout - the layout of the board
+    pub fn with_layout(mut self, layout: BoardLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Return the glyph of the cell
+    ///
+    /// # Arguments
+    ///
+    /// * cell - the `Cell` to be drawn
+    pub fn glyph(&self, cell: Cell) -> &str {
+        match cell.mark() {
+            Some(Mark::Cross) => &self.cross,
+            Some(Mark::Naught) => &self.naught,
+            None => &self.empty,
+        }
+    }
+
+    /// Return the text drawn for the cell at the index: its glyph,
+    /// or its number when it is empty in the `BoardLayout::Numpad` layout
+    ///
+    /// # Arguments
+    ///
+    /// * cell_index - the index of the cell, row by row
+    /// * cell - the `Cell` to be drawn
+    fn cell_text(&self, cell_index: usize, cell: Cell) -> String {
+        match self.layout {
+            BoardLayout::Numpad if cell.is_vacant() => (cell_index + 1).to_string(),
+            _ => self.glyph(cell).to_string(),
+        }
+    }
+}
+
+impl Default for RendererStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A console renderer drawing the grid as plain text, the winning line between brackets.
+pub struct ConsoleRenderer {
+    style: RendererStyle,
+    clear_screen: bool,
+}
+
+impl ConsoleRenderer {
+    /// Create a renderer with the default `RendererStyle`, clearing the screen before each frame
+    pub fn new() -> Self {
+        ConsoleRenderer {
+            style: RendererStyle::default(),
+            clear_screen: true,
+        }
+    }
+
+    /// Set the style of the renderer
+    ///
+    /// # Arguments
+    ///
+    /// * style - the glyphs of the cells
+    pub fn with_style(mut self, style: RendererStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set whether the screen is cleared before each frame, or the frames are appended
+    ///
+    /// # Arguments
+    ///
+    /// * clear_screen - whether the screen is cleared
+    pub fn with_clear_screen(mut self, clear_screen: bool) -> Self {
+        self.clear_screen = clear_screen;
+        self
+    }
+}
+
+impl Default for ConsoleRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A console renderer coloring the crosses and the naughts, and the winning line in inverse video.
+/// The colors are only used when the terminal supports them, see `ColoredConsoleRenderer::new`.
+pub struct ColoredConsoleRenderer {
+    plain: ConsoleRenderer,
+    color: bool,
+}
 
 impl Renderer for ConsoleRenderer {
     /// Render the game with the curent `GameState`
@@ -13,57 +196,427 @@ impl Renderer for ConsoleRenderer {
     ///
     /// * game_state - the curent `GameState` which will be rendered
     fn render(&self, game_state: &GameState) {
-        if game_state.game_not_started() {
-            println!("Nice to see you play");
+        self.render_with_players(game_state, &[]);
+    }
+
+    /// Render the game with the curent `GameState`, naming the players
+    ///
+    /// # Arguments
+    ///
+    /// * game_state - the curent `GameState` which will be rendered
+    /// * players - the players of the game
+    fn render_with_players(&self, game_state: &GameState, players: &[&dyn Player]) {
+        let winning_indexes = game_state.winning_indexes().unwrap_or_default();
+        let grid_text = styled_grid_to_text(game_state.grid(), &self.style, &winning_indexes);
+        render_frame(game_state, players, &grid_text, self.clear_screen);
+    }
+
+    /// Render the last move, e.g. `Last move: X -> B2`
+    ///
+    /// # Arguments
+    ///
+    /// * game_move - the move leading to the rendered `GameState`
+    fn render_move(&self, game_move: &GameMove) {
+        println!("Last move: {}", game_move);
+    }
+
+    /// Render the reason the last move was rejected
+    ///
+    /// # Arguments
+    ///
+    /// * error - the error of the rejected move
+    fn render_invalid(&self, error: &MoveError) {
+        println!("Invalid move: {}", error);
+    }
+
+    /// Render the remaining time of both players, e.g. `X 4:59.2 | O 5:00.0`
+    ///
+    /// # Arguments
+    ///
+    /// * clocks - the clocks of the players
+    fn render_clocks(&self, clocks: &Clocks) {
+        println!(
+            "{} {} | {} {}",
+            Mark::Cross,
+            clock_to_text(clocks.remaining(Mark::Cross)),
+            Mark::Naught,
+            clock_to_text(clocks.remaining(Mark::Naught))
+        );
+    }
+}
+
+impl ColoredConsoleRenderer {
+    /// Create a renderer using the colors when the standard output is a terminal,
+    /// the `NO_COLOR` environment variable is not set and the terminal is not `dumb`
+    pub fn new() -> Self {
+        Self::with_color(supports_color())
+    }
+
+    /// Create a renderer using the colors or not, whatever the terminal
+    ///
+    /// # Arguments
+    ///
+    /// * color - whether the marks are colored
+    pub fn with_color(color: bool) -> Self {
+        ColoredConsoleRenderer {
+            plain: ConsoleRenderer::new(),
+            color,
         }
+    }
+
+    /// Set the style of the renderer
+    ///
+    /// # Arguments
+    ///
+    /// * style - the glyphs of the cells
+    pub fn with_style(mut self, style: RendererStyle) -> Self {
+        self.plain.style = style;
+        self
+    }
+
+    /// Set whether the screen is cleared before each frame, or the frames are appended
+    ///
+    /// # Arguments
+    ///
+    /// * clear_screen - whether the screen is cleared
+    pub fn with_clear_screen(mut self, clear_screen: bool) -> Self {
+        self.plain.clear_screen = clear_screen;
+        self
+    }
+}
+
+impl Default for ColoredConsoleRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer for ColoredConsoleRenderer {
+    /// Render the game with the curent `GameState`
+    ///
+    /// # Arguments
+    ///
+    /// * game_state - the curent `GameState` which will be rendered
+    fn render(&self, game_state: &GameState) {
+        self.render_with_players(game_state, &[]);
+    }
+
+    /// Render the game with the curent `GameState`, naming the players
+    ///
+    /// # Arguments
+    ///
+    /// * game_state - the curent `GameState` which will be rendered
+    /// * players - the players of the game
+    fn render_with_players(&self, game_state: &GameState, players: &[&dyn Player]) {
+        if self.color {
+            let grid_text = colored_grid_to_text(game_state, &self.plain.style);
+            render_frame(game_state, players, &grid_text, self.plain.clear_screen);
+        } else {
+            self.plain.render_with_players(game_state, players);
+        }
+    }
+
+    fn render_move(&self, game_move: &GameMove) {
+        self.plain.render_move(game_move);
+    }
+
+    fn render_invalid(&self, error: &MoveError) {
+        self.plain.render_invalid(error);
+    }
+
+    fn render_clocks(&self, clocks: &Clocks) {
+        self.plain.render_clocks(clocks);
+    }
+}
+
+/// Render a frame: the players, the grid already drawn and the result at the end of the game
+///
+/// # Arguments
+///
+/// * game_state - the curent `GameState` which will be rendered
+/// * players - the players of the game
+/// * grid_text - the grid of the `GameState` drawn as text
+/// * clear - whether the screen is cleared before the frame, or the frame is appended after a blank line
+fn render_frame(game_state: &GameState, players: &[&dyn Player], grid_text: &str, clear: bool) {
+    if game_state.game_not_started() {
+        println!("Nice to see you play");
+    }
+    if clear {
         clear_screen();
-        print_game(game_state.grid());
-
-        if game_state.game_over() {
-            match game_state.winner_mark() {
-                Some(mark) => {
-                    println!("{} wins!", mark);
-                    match game_state.winning_indexes() {
-                        Some(indexes) => println!("The winning indexes are: {:?}", indexes),
-                        None => todo!("No winning indexes"),
-                    }
-                }
-                None => print!("No one wins this time"),
-            }
+    } else {
+        println!();
+    }
+    for player in players {
+        println!("{}: {}", player.get_mark(), display_name(*player));
+    }
+    println!("{}", grid_text);
+
+    if game_state.game_over() {
+        match game_state.winner_mark() {
+            Some(mark) => match players.iter().find(|player| player.get_mark() == mark) {
+                Some(player) => println!("{} ({}) wins!", display_name(*player), mark),
+                None => println!("{} wins!", mark),
+            },
+            None => print!("No one wins this time"),
         }
     }
 }
 
+/// Write the remaining time as minutes, seconds and tenths of a second, e.g. `4:59.2`
+///
+/// # Arguments
+///
+/// * remaining - the remaining time of a player
+pub(crate) fn clock_to_text(remaining: Duration) -> String {
+    let tenths = remaining.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
 /// Clear the terminal screen
 fn clear_screen() {
     print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
 }
 
-/// Print the grid to the standard output
+/// Draw the grid as text, with the letters of the columns and the numbers of the rows
+///
+/// # Arguments
+///
+/// * grid - The `Grid` to be drawn
+pub(crate) fn grid_to_text(grid: &Grid) -> String {
+    styled_grid_to_text(grid, &RendererStyle::default(), &[])
+}
+
+/// Draw the grid as text with the glyphs of the style, the highlighted cells between brackets
+///
+/// # Arguments
+///
+/// * grid - The `Grid` to be drawn
+/// * style - The glyphs of the cells
+/// * highlighted - The indexes of the cells to highlight, e.g. the winning line
+fn styled_grid_to_text(grid: &Grid, style: &RendererStyle, highlighted: &[usize]) -> String {
+    let cells = grid.cells();
+    let cells = std::array::from_fn(|index| {
+        let text = style.cell_text(index, cells[index]);
+        if highlighted.contains(&index) {
+            format!("[{}]", text)
+        } else {
+            format!(" {} ", text)
+        }
+    });
+    draw_styled_grid(cells, style)
+}
+
+/// Draw the grid as text with the colors of the marks, the winning line in inverse video
 ///
 /// # Arguments
 ///
-/// * grid - The `Grid` to be printed on the terminal
-fn print_game(grid: &Grid) {
-    let output = format!(
+/// * game_state - The `GameState` whose grid is drawn
+/// * style - The glyphs of the cells
+fn colored_grid_to_text(game_state: &GameState, style: &RendererStyle) -> String {
+    let winning_indexes = game_state.winning_indexes().unwrap_or_default();
+    let cells = game_state.grid().cells();
+    let cells = std::array::from_fn(|index| {
+        colored_cell(index, cells[index], style, winning_indexes.contains(&index))
+    });
+    draw_styled_grid(cells, style)
+}
+
+/// Write the glyph of the cell with the color of its mark, in inverse video when highlighted
+///
+/// # Arguments
+///
+/// * cell_index - The index of the cell, row by row
+/// * cell - The `Cell` to be written
+/// * style - The glyphs of the cells
+/// * highlighted - whether the cell is part of the winning line
+fn colored_cell(cell_index: usize, cell: Cell, style: &RendererStyle, highlighted: bool) -> String {
+    let glyph = style.cell_text(cell_index, cell);
+    let color = match cell.mark() {
+        Some(Mark::Cross) => "31",
+        Some(Mark::Naught) => "34",
+        None => return format!(" {} ", glyph),
+    };
+    if highlighted {
+        format!("\x1b[1;7;{}m {} \x1b[0m", color, glyph)
+    } else {
+        format!(" \x1b[1;{}m{}\x1b[0m ", color, glyph)
+    }
+}
+
+/// Draw the text of the cells in the grid with the lines and the layout of the style
+///
+/// # Arguments
+///
+/// * cells - The text of each cell with the space around it, as many columns wide once printed
+/// * style - The style of the grid
+fn draw_styled_grid(cells: [String; Grid::SIZE], style: &RendererStyle) -> String {
+    // The cells numbered in the numpad layout need no letters and numbers around the grid.
+    let (columns, rows) = match style.layout {
+        BoardLayout::Coordinates => (["A", "B", "C"], ["1", "2", "3"]),
+        BoardLayout::Numpad => ([" "; 3], [" "; 3]),
+    };
+    if style.ascii {
+        draw_ascii_grid(cells, columns, rows)
+    } else {
+        draw_grid(cells, columns, rows)
+    }
+}
+
+/// Draw the text of the cells in the grid, with the labels of the columns and the rows
+///
+/// # Arguments
+///
+/// * cells - The text of each cell with the space around it, as many columns wide once printed
+/// * columns - The labels of the columns, e.g. `A`
+/// * rows - The labels of the rows, e.g. `1`
+fn draw_grid(cells: [String; Grid::SIZE], columns: [&str; 3], rows: [&str; 3]) -> String {
+    format!(
         r#"
-        A   B   C
+        {9}   {10}   {11}
         ------------
-     1 ┆  {0} │ {1} │ {2}
+     {12} ┆ {0}│{1}│{2}
        ┆ ───┼───┼───
-     2 ┆  {3} │ {4} │ {5}
+     {13} ┆ {3}│{4}│{5}
        ┆ ───┼───┼───
-     3 ┆  {6} │ {7} │ {8}
+     {14} ┆ {6}│{7}│{8}
     "#,
-        grid.cells()[0],
-        grid.cells()[1],
-        grid.cells()[2],
-        grid.cells()[3],
-        grid.cells()[4],
-        grid.cells()[5],
-        grid.cells()[6],
-        grid.cells()[7],
-        grid.cells()[8],
-    );
-    println!("{}", output);
+        cells[0],
+        cells[1],
+        cells[2],
+        cells[3],
+        cells[4],
+        cells[5],
+        cells[6],
+        cells[7],
+        cells[8],
+        columns[0],
+        columns[1],
+        columns[2],
+        rows[0],
+        rows[1],
+        rows[2],
+    )
+}
+
+/// Draw the text of the cells in the grid with plain ASCII lines, with the labels of the columns and the rows
+///
+/// # Arguments
+///
+/// * cells - The text of each cell with the space around it, as many columns wide once printed
+/// * columns - The labels of the columns, e.g. `A`
+/// * rows - The labels of the rows, e.g. `1`
+fn draw_ascii_grid(cells: [String; Grid::SIZE], columns: [&str; 3], rows: [&str; 3]) -> String {
+    format!(
+        r#"
+        {9}   {10}   {11}
+      +---+---+---+
+    {12} |{0}|{1}|{2}|
+      +---+---+---+
+    {13} |{3}|{4}|{5}|
+      +---+---+---+
+    {14} |{6}|{7}|{8}|
+      +---+---+---+
+    "#,
+        cells[0],
+        cells[1],
+        cells[2],
+        cells[3],
+        cells[4],
+        cells[5],
+        cells[6],
+        cells[7],
+        cells[8],
+        columns[0],
+        columns[1],
+        columns[2],
+        rows[0],
+        rows[1],
+        rows[2],
+    )
+}
+
+/// Returns `true` if the terminal is expected to draw the box-drawing characters, `false` otherwise
+fn supports_unicode() -> bool {
+    if env::var("TERM").ok().as_deref() == Some("dumb") {
+        return false;
+    }
+    if cfg!(windows) {
+        return env::var_os("WT_SESSION").is_some();
+    }
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|locale| !locale.is_empty()))
+        .is_some_and(|locale| {
+            let locale = locale.to_uppercase();
+            locale.contains("UTF-8") || locale.contains("UTF8")
+        })
+}
+
+/// Returns `true` if the standard output is a terminal which supports the colors, `false` otherwise
+fn supports_color() -> bool {
+    io::stdout().is_terminal()
+        && env::var_os("NO_COLOR").is_none()
+        && env::var("TERM").ok().as_deref() != Some("dumb")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colored_grid_to_text() {
+        let game_state = GameState::from_notation("XXX/OO./... O").unwrap();
+        let text = colored_grid_to_text(&game_state, &RendererStyle::default());
+        assert!(text.contains("\x1b[1;7;31m X \x1b[0m│\x1b[1;7;31m X "));
+        assert!(text.contains("\x1b[1;34mO\x1b[0m │ \x1b[1;34mO\x1b[0m │  "));
+        assert_eq!(
+            colored_grid_to_text(
+                &GameState::from_notation(".../.../... X").unwrap(),
+                &RendererStyle::default()
+            ),
+            grid_to_text(&Grid::new(None))
+        );
+    }
+
+    #[test]
+    fn test_renderer_style() {
+        let grid: Grid = "X...O....".parse().unwrap();
+        let text = styled_grid_to_text(&grid, &RendererStyle::emoji(), &[]);
+        assert!(text.contains("1 ┆  ❌ │    │   "));
+        assert!(text.contains("2 ┆     │ ⭕ │   "));
+        let style = RendererStyle::new().with_glyphs("■", "□", "·");
+        assert_eq!(style.glyph(Cell::new_empty()), "·");
+        assert_eq!(style.glyph(Cell::new_marked(Mark::Naught)), "□");
+        assert_eq!(
+            styled_grid_to_text(&grid, &RendererStyle::new(), &[]),
+            grid_to_text(&grid)
+        );
+    }
+
+    #[test]
+    fn test_ascii_grid() {
+        let grid: Grid = "XO.XO.X..".parse().unwrap();
+        let text = styled_grid_to_text(&grid, &RendererStyle::ascii(), &[0, 3, 6]);
+        assert!(text.is_ascii());
+        assert!(text.contains("\n      +---+---+---+\n    1 |[X]| O |   |\n"));
+        assert!(text.contains("\n    3 |[X]|   |   |\n      +---+---+---+\n"));
+    }
+
+    #[test]
+    fn test_numpad_layout() {
+        let grid: Grid = "X...O....".parse().unwrap();
+        let style = RendererStyle::ascii().with_layout(BoardLayout::Numpad);
+        let text = styled_grid_to_text(&grid, &style, &[]);
+        assert!(text.contains("\n      | X | 2 | 3 |\n"));
+        assert!(text.contains("\n      | 4 | O | 6 |\n"));
+        assert!(!text.contains('A'));
+    }
+
+    #[test]
+    fn test_winning_line_between_brackets() {
+        let grid: Grid = "XO.XO.X..".parse().unwrap();
+        let text = styled_grid_to_text(&grid, &RendererStyle::new(), &[0, 3, 6]);
+        assert!(text.contains("1 ┆ [X]│ O │   "));
+        assert!(text.contains("3 ┆ [X]│   │   "));
+    }
 }
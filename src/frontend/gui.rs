@@ -0,0 +1,251 @@
+//! The native window frontend, drawn with egui, behind the `gui` feature.
+//! A click on a cell places the mark, with `Variant::Wild` a right click places the naught,
+//! and with `Variant::Gravity` the mark drops in the column of the cell.
+//! The opponent of the crosses is picked in the top bar, where the new game button starts over.
+//!
+//! The window redraws every frame from the `SyncGameFacade`, which plays the clicks one at a time,
+//! and the computer opponent plays its move in the frame after the click.
+
+use eframe::egui::{self, Color32, RichText};
+
+use crate::{
+    frontend::opponent::Opponent,
+    game::{GameStatus, Intent, Player, SyncGameFacade},
+    logic::{Grid, Mark, Variant},
+};
+
+/// The size of a cell of the board, in points.
+const CELL_SIZE: f32 = 96.0;
+
+/// The state of the window, kept from one frame to the next.
+pub struct GuiApp {
+    game: SyncGameFacade,
+    variant: Variant,
+    opponent: Opponent,
+    /// The computer player of the naughts, none when the opponent is human.
+    computer: Option<Box<dyn Player>>,
+    /// Why the last click was not played.
+    message: Option<String>,
+}
+
+impl GuiApp {
+    /// Creates a new `GuiApp` starting a game against the opponent.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The variant of the rules the games are played with.
+    /// * `opponent` - Who plays the naughts.
+    pub fn new(variant: Variant, opponent: Opponent) -> Self {
+        GuiApp {
+            game: SyncGameFacade::new(variant, None),
+            variant,
+            opponent,
+            computer: opponent.player(Mark::Naught),
+            message: None,
+        }
+    }
+
+    /// Starts a new game on an empty grid, against the opponent selected last.
+    fn new_game(&mut self) {
+        *self = GuiApp::new(self.variant, self.opponent);
+    }
+
+    /// Plays the intent of the user, keeping why it was not played.
+    fn click(&mut self, intent: Intent) {
+        self.message = self.game.apply(intent).err().map(|error| error.to_string());
+    }
+
+    /// Plays the move of the computer player when it is its turn.
+    fn play_computer(&mut self) {
+        let Some(computer) = self.computer.as_mut() else {
+            return;
+        };
+        if self.game.status() != GameStatus::InProgress(computer.get_mark()) {
+            return;
+        }
+        let Some(game_move) = computer.get_move(self.game.state()) else {
+            return;
+        };
+        let intent = Intent::PlaceMark(game_move.cell_index(), *game_move.placed_mark());
+        if let Err(error) = self.game.apply(intent) {
+            self.message = Some(format!(
+                "{} made an invalid move: {}",
+                computer.kind(),
+                error
+            ));
+        }
+    }
+
+    /// Returns whose turn it is, or how the game ended.
+    fn status_text(&self) -> String {
+        match self.game.status() {
+            GameStatus::InProgress(mark) if self.computer.is_some() && mark == Mark::Naught => {
+                format!("{} is thinking", self.opponent.name())
+            }
+            GameStatus::InProgress(mark) => format!("Turn of {}", mark),
+            GameStatus::Won(mark) => format!("{} wins", mark),
+            GameStatus::Tie => String::from("Tie"),
+        }
+    }
+
+    /// Draws the top bar, with the selection of the opponent and the new game button.
+    fn top_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let selected = self.opponent;
+            egui::ComboBox::from_label("Opponent")
+                .selected_text(selected.name())
+                .show_ui(ui, |ui| {
+                    for opponent in Opponent::ALL {
+                        ui.selectable_value(&mut self.opponent, opponent, opponent.name());
+                    }
+                });
+            // The opponent takes over the naughts of the game in progress.
+            if self.opponent != selected {
+                self.computer = self.opponent.player(Mark::Naught);
+            }
+            if ui.button("New game").clicked() {
+                self.new_game();
+            }
+        });
+    }
+
+    /// Draws the board, a button per cell, and plays the clicks.
+    fn board(&mut self, ui: &mut egui::Ui) {
+        let game_state = *self.game.state();
+        let winning_cells = game_state.winning_indexes().unwrap_or_default();
+        let users_turn = match self.game.status() {
+            GameStatus::InProgress(mark) => self.computer.is_none() || mark == Mark::Cross,
+            GameStatus::Won(_) | GameStatus::Tie => false,
+        };
+        let cells = game_state.grid().cells();
+
+        let mut intent = None;
+        egui::Grid::new("board")
+            .spacing(egui::vec2(4.0, 4.0))
+            .show(ui, |ui| {
+                for (index, cell) in cells.iter().enumerate() {
+                    let text = cell.mark().map_or(String::new(), |mark| mark.to_string());
+                    let color = match cell.mark() {
+                        Some(Mark::Cross) => Color32::from_rgb(0xdd, 0x33, 0x33),
+                        Some(Mark::Naught) => Color32::from_rgb(0x33, 0x66, 0xcc),
+                        None => Color32::GRAY,
+                    };
+                    let mut button =
+                        egui::Button::new(RichText::new(text).size(CELL_SIZE / 2.0).color(color))
+                            .min_size(egui::vec2(CELL_SIZE, CELL_SIZE));
+                    if winning_cells.contains(&index) {
+                        button = button.fill(Color32::from_rgb(0x22, 0xaa, 0x22));
+                    }
+                    let response = ui.add_enabled(users_turn && cell.is_vacant(), button);
+                    if response.clicked() {
+                        intent = Some(match game_state.variant() {
+                            Variant::Gravity => Intent::Drop(index % Grid::WIDTH),
+                            Variant::Wild => Intent::PlaceMark(index, Mark::Cross),
+                            Variant::Standard | Variant::Notakto => Intent::Place(index),
+                        });
+                    } else if response.secondary_clicked() && game_state.variant() == Variant::Wild
+                    {
+                        intent = Some(Intent::PlaceMark(index, Mark::Naught));
+                    }
+                    if index % Grid::WIDTH == Grid::WIDTH - 1 {
+                        ui.end_row();
+                    }
+                }
+            });
+        if let Some(intent) = intent {
+            self.click(intent);
+        }
+    }
+}
+
+impl eframe::App for GuiApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.play_computer();
+
+        egui::TopBottomPanel::top("top_bar").show(ctx, |ui| self.top_bar(ui));
+        egui::TopBottomPanel::bottom("status").show(ctx, |ui| {
+            ui.label(RichText::new(self.status_text()).strong());
+            if let Some(message) = &self.message {
+                ui.colored_label(Color32::from_rgb(0xdd, 0x33, 0x33), message);
+            }
+        });
+        egui::CentralPanel::default().show(ctx, |ui| self.board(ui));
+
+        // The computer plays in the next frame, after the click is drawn.
+        if let (Some(computer), GameStatus::InProgress(mark)) = (&self.computer, self.game.status())
+        {
+            if computer.get_mark() == mark {
+                ctx.request_repaint();
+            }
+        }
+    }
+}
+
+/// Opens the window and plays until it is closed.
+///
+/// # Arguments
+///
+/// * `variant` - The variant of the rules the games are played with.
+/// * `opponent` - Who plays the naughts of the first game.
+pub fn run(variant: Variant, opponent: Opponent) -> Result<(), eframe::Error> {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([340.0, 420.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Tic Tac Toe",
+        options,
+        Box::new(move |_creation_context| Box::new(GuiApp::new(variant, opponent))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_click() {
+        let mut app = GuiApp::new(Variant::Standard, Opponent::Human);
+        app.click(Intent::Place(4));
+        assert_eq!(app.game.state().to_notation(), ".../.X./... O");
+        assert_eq!(app.message, None);
+
+        app.click(Intent::Place(4));
+        assert!(app.message.is_some());
+        assert_eq!(app.status_text(), "Turn of O");
+
+        // The message of the rejected click is gone once a click is played.
+        app.click(Intent::Place(0));
+        assert_eq!(app.message, None);
+        assert_eq!(app.game.state().to_notation(), "O../.X./... X");
+    }
+
+    #[test]
+    fn test_click_drops_the_mark_with_gravity() {
+        let mut app = GuiApp::new(Variant::Gravity, Opponent::Human);
+        app.click(Intent::Drop(1));
+        assert_eq!(app.game.state().grid().cells()[7].mark(), Some(Mark::Cross));
+    }
+
+    #[test]
+    fn test_play_computer() {
+        let mut app = GuiApp::new(Variant::Standard, Opponent::Perfect);
+        // The computer plays the naughts, it waits for the user.
+        app.play_computer();
+        assert!(app.game.state().game_not_started());
+        assert_eq!(app.status_text(), "Turn of X");
+
+        app.click(Intent::Place(4));
+        assert_eq!(app.status_text(), "Perfect AI is thinking");
+        app.play_computer();
+        assert_eq!(app.game.status(), GameStatus::InProgress(Mark::Cross));
+        assert_eq!(app.game.state().grid().naught_count(), 1);
+        assert_eq!(app.message, None);
+
+        // Against a human, nobody plays the naughts for them.
+        let mut app = GuiApp::new(Variant::Standard, Opponent::Human);
+        app.click(Intent::Place(4));
+        app.play_computer();
+        assert_eq!(app.game.status(), GameStatus::InProgress(Mark::Naught));
+    }
+}
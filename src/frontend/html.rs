@@ -0,0 +1,191 @@
+//! The renderer writing a standalone HTML report of the game when it ends, e.g. to share a game.
+//! The report shows the players, every move with the board after it, drawn as an SVG image, and the result.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use crate::{
+    frontend::svg::board_to_svg,
+    game::{
+        engine::{GameEnding, GameResult},
+        players::{display_name, Player},
+        renderers::Renderer,
+    },
+    logic::{GameState, Mark},
+};
+
+/// A renderer writing the report of the game to an HTML file at the end of the game.
+#[derive(Debug)]
+pub struct HtmlReportRenderer {
+    path: PathBuf,
+    /// The marks and the names of the players, as last rendered.
+    players: Mutex<Vec<(Mark, String)>>,
+    /// The last error met while writing the report.
+    error: Mutex<Option<io::Error>>,
+}
+
+impl HtmlReportRenderer {
+    /// Creates a new `HtmlReportRenderer` writing the report to the file, replacing its content.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the HTML file.
+    pub fn new(path: &Path) -> Self {
+        HtmlReportRenderer {
+            path: path.to_path_buf(),
+            players: Mutex::new(vec![]),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// Returns the last error met while writing the report, if any, and forgets it.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+impl Renderer for HtmlReportRenderer {
+    fn render(&self, _game_state: &GameState) {}
+
+    fn render_with_players(&self, _game_state: &GameState, players: &[&dyn Player]) {
+        *self.players.lock().unwrap_or_else(PoisonError::into_inner) = players
+            .iter()
+            .map(|player| (player.get_mark(), display_name(*player)))
+            .collect();
+    }
+
+    fn render_end(&self, result: &GameResult) {
+        let report = game_report(
+            result,
+            &self.players.lock().unwrap_or_else(PoisonError::into_inner),
+        );
+        if let Err(error) = fs::write(&self.path, report) {
+            *self.error.lock().unwrap_or_else(PoisonError::into_inner) = Some(error);
+        }
+    }
+}
+
+/// Writes the report of the game as a standalone HTML page.
+///
+/// # Arguments
+///
+/// * `result` - The result of the game, with its moves.
+/// * `players` - The marks and the names of the players, none if they are unknown.
+pub fn game_report(result: &GameResult, players: &[(Mark, String)]) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Tic Tac Toe</title>\n\
+         <style>\n\
+         body { font-family: sans-serif; }\n\
+         figure { display: inline-block; margin: 8px; text-align: center; }\n\
+         svg { width: 120px; height: 120px; border: 1px solid #ccc; }\n\
+         </style>\n</head>\n<body>\n<h1>Tic Tac Toe</h1>\n",
+    );
+    if !players.is_empty() {
+        html += "<ul>\n";
+        for (mark, name) in players {
+            html += &format!("<li>{}: {}</li>\n", mark, escape(name));
+        }
+        html += "</ul>\n";
+    }
+
+    let initial_state = result
+        .moves
+        .first()
+        .map_or(result.final_state, |game_move| *game_move.before_state());
+    html += &figure("Start", &initial_state);
+    for (i, game_move) in result.moves.iter().enumerate() {
+        let caption = format!("{}. {}", i + 1, game_move);
+        html += &figure(&escape(&caption), game_move.after_state());
+    }
+
+    html += &format!(
+        "<p><strong>{}</strong></p>\n</body>\n</html>\n",
+        escape(&ending_text(result))
+    );
+    html
+}
+
+/// Returns the figure of the board of the game state with the caption.
+///
+/// # Arguments
+///
+/// * `caption` - The caption of the figure, escaped.
+/// * `game_state` - The game state to draw.
+fn figure(caption: &str, game_state: &GameState) -> String {
+    format!(
+        "<figure>\n{}<figcaption>{}</figcaption>\n</figure>\n",
+        board_to_svg(game_state),
+        caption
+    )
+}
+
+/// Returns how the game ended, e.g. `X wins`.
+///
+/// # Arguments
+///
+/// * `result` - The result of the game.
+pub(crate) fn ending_text(result: &GameResult) -> String {
+    match &result.ending {
+        GameEnding::Finished => match result.winner {
+            Some(mark) => format!("{} wins", mark),
+            None => String::from("Tie"),
+        },
+        GameEnding::Forfeit(mark) => format!("{} forfeits, {} wins", mark, mark.other()),
+        GameEnding::Aborted(error) => format!("Aborted: {}", error),
+        GameEnding::Interrupted => String::from("Interrupted"),
+        GameEnding::FlagFall(mark) => format!("{} loses on time, {} wins", mark, mark.other()),
+    }
+}
+
+/// Escapes the text to be written in HTML.
+///
+/// # Arguments
+///
+/// * `text` - The text to escape.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::game::{ScriptedPlayer, TicTacToe};
+
+    #[test]
+    fn test_html_report() {
+        let path = env::temp_dir().join(format!("game_report_{}.html", process::id()));
+        let renderer = HtmlReportRenderer::new(&path);
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        TicTacToe::new(&mut cross, &mut naught, &renderer, None)
+            .unwrap()
+            .play(None);
+        assert!(renderer.take_error().is_none());
+
+        let report = fs::read_to_string(&path).unwrap();
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert_eq!(report.matches("<figure>").count(), 6);
+        assert!(report.contains("<figcaption>1. X -&gt; A1</figcaption>"));
+        assert!(report.contains("<figcaption>5. X -&gt; C1</figcaption>"));
+        assert!(report.contains("<strong>X wins</strong>"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("<b>Tom & \"Jerry\"</b>"),
+            "&lt;b&gt;Tom &amp; &quot;Jerry&quot;&lt;/b&gt;"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! The renderer writing each position as a line of JSON, for the tools reading the games as they are played.
+//! Each render writes one object with the cells row by row, `null` for the empty ones,
+//! the mark of the player whose turn it is, whether the game is over, and the winner, e.g.
+//!
+//! ```text
+//! {"board":["X",null,null,null,"O",null,null,null,null],"current_mark":"X","game_over":false,"winner":null}
+//! ```
+
+use std::io::{self, Write};
+use std::sync::{Mutex, PoisonError};
+
+use crate::{game::renderers::Renderer, logic::GameState};
+
+/// A renderer writing each position as a line of JSON to the writer.
+#[derive(Debug)]
+pub struct JsonRenderer<W: Write> {
+    writer: Mutex<W>,
+    /// The last error met while writing a position.
+    error: Mutex<Option<io::Error>>,
+}
+
+impl<W: Write> JsonRenderer<W> {
+    /// Creates a new `JsonRenderer` writing to the writer, e.g. the standard output or a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer of the lines of JSON.
+    pub fn new(writer: W) -> Self {
+        JsonRenderer {
+            writer: Mutex::new(writer),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// Returns the writer, e.g. to read the lines written to a buffer.
+    pub fn into_inner(self) -> W {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the last error met while writing a position, if any, and forgets it.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+impl<W: Write + Send> Renderer for JsonRenderer<W> {
+    fn render(&self, game_state: &GameState) {
+        let line = game_state_to_json(game_state);
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(error) = writeln!(writer, "{}", line).and_then(|()| writer.flush()) {
+            *self.error.lock().unwrap_or_else(PoisonError::into_inner) = Some(error);
+        }
+    }
+}
+
+/// Writes the game state as a JSON object, on a single line.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to write.
+pub fn game_state_to_json(game_state: &GameState) -> String {
+    let board: Vec<String> = game_state
+        .grid()
+        .cells()
+        .iter()
+        .map(|cell| match cell.mark() {
+            Some(mark) => format!("\"{}\"", mark),
+            None => String::from("null"),
+        })
+        .collect();
+    let winner = match game_state.winner_mark() {
+        Some(mark) => format!("\"{}\"", mark),
+        None => String::from("null"),
+    };
+    format!(
+        "{{\"board\":[{}],\"current_mark\":\"{}\",\"game_over\":{},\"winner\":{}}}",
+        board.join(","),
+        game_state.current_mark(),
+        game_state.game_over(),
+        winner
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{ScriptedPlayer, TicTacToe};
+    use crate::logic::Mark;
+
+    #[test]
+    fn test_game_state_to_json() {
+        let game_state = GameState::from_notation("X../.O./... X").unwrap();
+        assert_eq!(
+            game_state_to_json(&game_state),
+            "{\"board\":[\"X\",null,null,null,\"O\",null,null,null,null],\"current_mark\":\"X\",\"game_over\":false,\"winner\":null}"
+        );
+        let game_state = GameState::from_notation("XXX/OO./... O").unwrap();
+        assert!(game_state_to_json(&game_state).ends_with("\"game_over\":true,\"winner\":\"X\"}"));
+    }
+
+    #[test]
+    fn test_json_renderer_writes_a_line_per_render() {
+        let renderer = JsonRenderer::new(Vec::new());
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        TicTacToe::new(&mut cross, &mut naught, &renderer, None)
+            .unwrap()
+            .play(None);
+        assert!(renderer.take_error().is_none());
+
+        let output = String::from_utf8(renderer.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("{\"board\":[null,null,null"));
+        assert!(lines[5].ends_with("\"winner\":\"X\"}"));
+    }
+}
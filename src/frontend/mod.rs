@@ -1,3 +1,18 @@
 //! A module to take care of the frontend for the tic tac toe game
+//! And it contains the svg module, drawing the positions as SVG images
+//! And it contains the html module, writing the report of a game as an HTML page
+//! And it contains the json module, writing the positions as lines of JSON for other tools
+//! And it contains the opponent module, the opponents offered by the window and the web page
+//! And it contains the gui module, the native window frontend, behind the `gui` feature
+//! And it contains the tui module, the full-screen frontend of the terminal, behind the `tui` feature
 
 pub mod console;
+#[cfg(feature = "gui")]
+pub mod gui;
+pub mod html;
+pub mod json;
+pub mod network;
+pub mod opponent;
+pub mod svg;
+#[cfg(feature = "tui")]
+pub mod tui;
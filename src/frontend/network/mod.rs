@@ -0,0 +1,8 @@
+//! The frontend to be used when played between two machines
+//! Contain the player asking a remote peer for its moves over TCP
+//! And contain the counterpart answering for a local player
+//! And contain the telnet-like server seating remote players typing their moves
+//! And contain the web server hosting a playable board for the browser
+pub mod players;
+pub mod telnet;
+pub mod web;
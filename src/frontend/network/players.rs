@@ -0,0 +1,154 @@
+//! The players used to play over TCP
+//! The `NetworkPlayer` sends the game state to a remote peer and waits for its move,
+//! the remote peer answers with `answer_moves`, for a player of its own.
+//! Both sides exchange the lines of the `protocol` module.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+use crate::{
+    game::{
+        players::{
+            protocol::{move_line, parse_move_line, parse_position_line, position_line},
+            Player, PlayerCommand,
+        },
+        Renderer,
+    },
+    logic::{GameMove, GameState, Mark},
+};
+
+/// A player whose moves are chosen by a remote peer
+pub struct NetworkPlayer {
+    mark: Mark,
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+    /// The command sent to the engine when the connection is closed.
+    command: Option<PlayerCommand>,
+}
+
+impl NetworkPlayer {
+    /// Connect to a remote peer waiting with `answer_moves`
+    ///
+    /// # Arguments
+    ///
+    /// * mark - The mark of the player
+    /// * address - The address of the remote peer
+    pub fn connect(mark: Mark, address: impl ToSocketAddrs) -> io::Result<Self> {
+        Self::from_stream(mark, TcpStream::connect(address)?)
+    }
+
+    /// Wait for a remote peer to connect on the listener
+    ///
+    /// # Arguments
+    ///
+    /// * mark - The mark of the player
+    /// * listener - The listener the remote peer connects to
+    pub fn accept(mark: Mark, listener: &TcpListener) -> io::Result<Self> {
+        let (stream, _) = listener.accept()?;
+        Self::from_stream(mark, stream)
+    }
+
+    fn from_stream(mark: Mark, stream: TcpStream) -> io::Result<Self> {
+        let reader = BufReader::new(stream.try_clone()?);
+        Ok(NetworkPlayer {
+            mark,
+            writer: stream,
+            reader,
+            command: None,
+        })
+    }
+
+    /// Send the position to the remote peer and read its answer
+    fn exchange(&mut self, game_state: &GameState) -> io::Result<String> {
+        writeln!(self.writer, "{}", position_line(game_state))?;
+
+        let mut answer = String::new();
+        if self.reader.read_line(&mut answer)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "The remote peer closed the connection",
+            ));
+        }
+        Ok(answer)
+    }
+}
+
+impl Player for NetworkPlayer {
+    /// Get the move of the remote peer
+    /// The player resigns when the connection is closed
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        let Ok(answer) = self.exchange(game_state) else {
+            self.command = Some(PlayerCommand::Resign);
+            return None;
+        };
+        parse_move_line(game_state, &answer)
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn kind(&self) -> &'static str {
+        "Remote"
+    }
+
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        self.command.take()
+    }
+}
+
+/// Answer the positions sent by a `NetworkPlayer` with the moves of the local player,
+/// until the connection is closed
+///
+/// # Arguments
+///
+/// * stream - The connection to the `NetworkPlayer`
+/// * player - The local player choosing the moves
+/// * renderer - The renderer showing each position before the local player moves
+pub fn answer_moves(
+    stream: TcpStream,
+    player: &mut dyn Player,
+    renderer: &dyn Renderer,
+) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let answer = parse_position_line(&line).and_then(|game_state| {
+            renderer.render(&game_state);
+            player.get_move(&game_state)
+        });
+        match answer {
+            Some(game_move) => writeln!(writer, "{}", move_line(&game_move))?,
+            None => writeln!(writer, "error")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::{
+        game::{renderers::NoRenderer, HeuristicPlayer},
+        logic::Grid,
+    };
+
+    #[test]
+    fn test_network_player_gets_the_remote_move() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let remote = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            answer_moves(stream, &mut HeuristicPlayer::new(Mark::Cross), &NoRenderer).unwrap();
+        });
+
+        let mut player = NetworkPlayer::connect(Mark::Cross, address).unwrap();
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(player.get_move(&game_state).unwrap().cell_index(), 4);
+
+        drop(player);
+        remote.join().unwrap();
+    }
+}
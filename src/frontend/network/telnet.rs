@@ -0,0 +1,175 @@
+//! The players seated on a telnet-like server
+//! Each remote player connects with any line based client, such as telnet or netcat,
+//! receives the grid as text and answers with the same input as in the cli, e.g. `A1`,
+//! while the host terminal renders the authoritative game
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+
+use crate::{
+    frontend::console::{
+        players::{column_to_index, coord_to_index, split_placed_mark},
+        renderers::grid_to_text,
+    },
+    game::players::{Player, PlayerCommand},
+    logic::{GameMove, GameState, Mark, Variant},
+};
+
+/// A player typing their moves in a remote terminal
+pub struct TelnetPlayer {
+    mark: Mark,
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+    /// The command sent to the engine when the connection is closed.
+    command: Option<PlayerCommand>,
+}
+
+impl TelnetPlayer {
+    /// Wait for a remote player to connect on the listener and greet them
+    ///
+    /// # Arguments
+    ///
+    /// * mark - The mark of the player
+    /// * listener - The listener the remote player connects to
+    pub fn accept(mark: Mark, listener: &TcpListener) -> io::Result<Self> {
+        let (stream, _) = listener.accept()?;
+        let mut player = TelnetPlayer {
+            mark,
+            writer: stream.try_clone()?,
+            reader: BufReader::new(stream),
+            command: None,
+        };
+        writeln!(
+            player.writer,
+            "Welcome, you play {}. Waiting for the game to start...",
+            mark
+        )?;
+        Ok(player)
+    }
+
+    /// Send the grid and the prompt, then read the answer of the remote player
+    fn ask(&mut self, game_state: &GameState, message: Option<&str>) -> io::Result<String> {
+        write!(self.writer, "{}", grid_to_text(game_state.grid()))?;
+        if let Some(message) = message {
+            writeln!(self.writer, "{}", message)?;
+        }
+        let prompt = match game_state.variant() {
+            Variant::Gravity => "move (column A, B or C)",
+            Variant::Wild => "move (add X or O to choose the mark, e.g. A1O)",
+            Variant::Standard | Variant::Notakto => "move (e.g. A1)",
+        };
+        write!(self.writer, "{}'s {}: ", self.mark, prompt)?;
+        self.writer.flush()?;
+
+        let mut answer = String::new();
+        if self.reader.read_line(&mut answer)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "The remote player disconnected",
+            ));
+        }
+        Ok(answer)
+    }
+}
+
+impl Player for TelnetPlayer {
+    /// Get the move from the remote player, asking again until the input is a possible move
+    /// Returns `None` and resigns if the remote player disconnects
+    ///
+    /// # Arguments
+    ///
+    /// * game_state - The curent `GameState` of the game
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        let mut message = None;
+        while !game_state.game_over() {
+            let Ok(answer) = self.ask(game_state, message) else {
+                self.command = Some(PlayerCommand::Resign);
+                return None;
+            };
+            match parse_input(game_state, answer.trim()) {
+                Ok(next_move) => return Some(next_move),
+                Err(error) => message = Some(error),
+            }
+        }
+        None
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn kind(&self) -> &'static str {
+        "Remote human"
+    }
+
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        self.command.take()
+    }
+}
+
+/// Seat a remote player for each mark, in order, on the listener
+///
+/// # Arguments
+///
+/// * listener - The listener the remote players connect to
+/// * marks - The marks of the seats
+pub fn seat_players(listener: &TcpListener, marks: &[Mark]) -> io::Result<Vec<TelnetPlayer>> {
+    marks
+        .iter()
+        .map(|mark| TelnetPlayer::accept(*mark, listener))
+        .collect()
+}
+
+/// Turn the input of the remote player into a move
+fn parse_input(game_state: &GameState, input: &str) -> Result<GameMove, &'static str> {
+    if game_state.variant() == Variant::Gravity {
+        let column = column_to_index(input).ok_or("Invalid input. Try again.")?;
+        return game_state
+            .make_move_to_column(column)
+            .map_err(|_| "That column is full.");
+    }
+
+    let (coord, placed_mark) = match game_state.variant() {
+        Variant::Wild => split_placed_mark(input),
+        _ => (input, None),
+    };
+    let cell_index = coord_to_index(coord)
+        .ok_or("Invalid input. Try again. The input shall be in the format A1 or 1A.")?;
+    match placed_mark {
+        Some(mark) => game_state.make_move_with(cell_index, mark),
+        None => game_state.make_move_to(cell_index),
+    }
+    .map_err(|_| "That cell is already occupied.")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::logic::Grid;
+
+    #[test]
+    fn test_telnet_player_reads_the_remote_move() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let remote = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(b"D4\nB2\n").unwrap();
+            let mut received = String::new();
+            let mut reader = BufReader::new(stream);
+            while reader.read_line(&mut received).unwrap() > 0 {}
+            received
+        });
+
+        let mut players = seat_players(&listener, &[Mark::Cross]).unwrap();
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let next_move = players[0].get_move(&game_state).unwrap();
+        assert_eq!(next_move.cell_index(), 4);
+
+        drop(players);
+        let received = remote.join().unwrap();
+        assert!(received.starts_with("Welcome, you play X."));
+        assert!(received.contains("Invalid input."));
+    }
+}
@@ -0,0 +1,456 @@
+//! The web server hosting a playable board, for a demo in the browser
+//! `GET /` serves the page, `POST /new?ai=perfect` starts a session against the computer player,
+//! and `POST /move?session=1&cell=4` plays the cell then the answer of the computer player.
+//! Each session keeps its game in a `SyncGameFacade` and answers with the game state as JSON
+//! Each connection is answered on its own thread, and a client which stops sending its request
+//! is dropped after a few seconds
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use crate::{
+    frontend::{json::game_state_to_json, opponent::Opponent},
+    game::{
+        facade::{GameStatus, Intent, SyncGameFacade},
+        json::escape_json,
+    },
+    logic::{Grid, Mark, Variant},
+};
+
+/// The most sessions kept at once, the oldest one is dropped to make room for a new one
+const MAX_SESSIONS: usize = 256;
+
+/// The time a client has to send each line of its request
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The mark of the computer player in the sessions, the user plays the crosses
+const COMPUTER_MARK: Mark = Mark::Naught;
+
+/// The page of the board, asking the server for every move
+const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Tic Tac Toe</title>
+<style>
+body { font-family: sans-serif; text-align: center; }
+#board { display: inline-grid; grid-template-columns: repeat(3, 96px); gap: 4px; margin: 16px; }
+#board button { width: 96px; height: 96px; font-size: 48px; }
+#board button.win { background: #2a2; color: white; }
+</style>
+</head>
+<body>
+<h1>Tic Tac Toe</h1>
+<label>Opponent
+<select id="ai">
+<!-- opponents -->
+</select>
+</label>
+<button id="new">New game</button>
+<div id="board"></div>
+<p id="status"></p>
+<script>
+let session = null;
+const board = document.getElementById("board");
+const cells = [];
+for (let i = 0; i < 9; i++) {
+  const cell = document.createElement("button");
+  cell.onclick = () => play(i, "X");
+  cell.oncontextmenu = (event) => { event.preventDefault(); play(i, "O"); };
+  board.appendChild(cell);
+  cells.push(cell);
+}
+async function call(path) {
+  const response = await fetch(path, { method: "POST" });
+  const data = await response.json();
+  if (!response.ok) {
+    document.getElementById("status").textContent = data.error;
+    return;
+  }
+  session = data.session;
+  data.state.board.forEach((mark, i) => {
+    cells[i].textContent = mark || "";
+    cells[i].classList.toggle("win", data.winning_cells.includes(i));
+  });
+  document.getElementById("status").textContent = data.status;
+}
+function play(cell, mark) {
+  if (session !== null) call("/move?session=" + session + "&cell=" + cell + "&mark=" + mark);
+}
+function newGame() {
+  call("/new?ai=" + document.getElementById("ai").value);
+}
+document.getElementById("new").onclick = newGame;
+newGame();
+</script>
+</body>
+</html>
+"#;
+
+/// A game played in the browser
+struct Session {
+    game: SyncGameFacade,
+    /// The opponent of the user, who plays the naughts
+    opponent: Opponent,
+}
+
+/// The answer to a request
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(body: String) -> Self {
+        Response {
+            status: "200 OK",
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        Response {
+            status,
+            content_type: "application/json",
+            body: format!("{{\"error\":\"{}\"}}", escape_json(message)),
+        }
+    }
+}
+
+/// The sessions of the server, shared by the connections
+struct Sessions {
+    variant: Variant,
+    games: BTreeMap<u64, Session>,
+    next_session: u64,
+}
+
+impl Sessions {
+    /// Route the request to the page or to the session
+    fn respond(&mut self, method: &str, target: &str) -> Response {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let parameter = |name: &str| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+        };
+        match (method, path) {
+            ("GET", "/") => Response {
+                status: "200 OK",
+                content_type: "text/html; charset=utf-8",
+                body: page(),
+            },
+            ("POST", "/new") => {
+                let ai = parameter("ai").unwrap_or(Opponent::default().id());
+                match Opponent::from_id(ai) {
+                    Some(opponent) => self.new_session(opponent),
+                    None => {
+                        Response::error("400 Bad Request", &format!("Unknown opponent `{}`", ai))
+                    }
+                }
+            }
+            ("POST", "/move") => {
+                let session = parameter("session").and_then(|id| id.parse().ok());
+                let cell = parameter("cell").and_then(|cell| cell.parse().ok());
+                match (session, cell) {
+                    (Some(session), Some(cell)) => self.play(
+                        session,
+                        cell,
+                        parameter("mark").and_then(|mark| mark.parse().ok()),
+                    ),
+                    _ => Response::error("400 Bad Request", "A move needs a session and a cell"),
+                }
+            }
+            (_, "/" | "/new" | "/move") => {
+                Response::error("405 Method Not Allowed", "Method not allowed")
+            }
+            _ => Response::error("404 Not Found", "Not found"),
+        }
+    }
+
+    /// Start a session against the opponent
+    fn new_session(&mut self, opponent: Opponent) -> Response {
+        if self.games.len() >= MAX_SESSIONS {
+            self.games.pop_first();
+        }
+        let id = self.next_session;
+        self.next_session += 1;
+        self.games.insert(
+            id,
+            Session {
+                game: SyncGameFacade::new(self.variant, None),
+                opponent,
+            },
+        );
+        Response::json(session_json(id, &self.games[&id]))
+    }
+
+    /// Play the cell clicked in the session, then the answer of the computer player
+    /// The mark is only used by `Variant::Wild`, where either mark can be placed
+    fn play(&mut self, id: u64, cell: usize, mark: Option<Mark>) -> Response {
+        let Some(session) = self.games.get_mut(&id) else {
+            return Response::error("404 Not Found", "Unknown session, start a new game");
+        };
+        let mut computer = session.opponent.player(COMPUTER_MARK);
+        let computers_turn = session.game.status() == GameStatus::InProgress(COMPUTER_MARK);
+        if computer.is_some() && computers_turn {
+            return Response::error("409 Conflict", "It is the turn of the computer");
+        }
+        let intent = match session.game.state().variant() {
+            Variant::Gravity => Intent::Drop(cell % Grid::WIDTH),
+            Variant::Wild => Intent::PlaceMark(cell, mark.unwrap_or(Mark::Cross)),
+            Variant::Standard | Variant::Notakto => Intent::Place(cell),
+        };
+        if let Err(error) = session.game.apply(intent) {
+            return Response::error("400 Bad Request", &error.to_string());
+        }
+
+        if let Some(computer) = computer.as_mut() {
+            if session.game.status() == GameStatus::InProgress(COMPUTER_MARK) {
+                let computer_move = computer.get_move(session.game.state()).map(|game_move| {
+                    Intent::PlaceMark(game_move.cell_index(), *game_move.placed_mark())
+                });
+                if let Some(intent) = computer_move {
+                    // The moves of the computer players are possible moves.
+                    let _ = session.game.apply(intent);
+                }
+            }
+        }
+        Response::json(session_json(id, session))
+    }
+}
+
+/// A server hosting the board and the sessions played on it
+pub struct WebServer {
+    listener: TcpListener,
+    sessions: Arc<Mutex<Sessions>>,
+}
+
+impl WebServer {
+    /// Listen on the address, the games are played with the standard rules
+    ///
+    /// # Arguments
+    ///
+    /// * address - The address to listen on, e.g. `127.0.0.1:8080`
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(WebServer {
+            listener: TcpListener::bind(address)?,
+            sessions: Arc::new(Mutex::new(Sessions {
+                variant: Variant::Standard,
+                games: BTreeMap::new(),
+                next_session: 1,
+            })),
+        })
+    }
+
+    /// Play the games of the new sessions with the variant
+    ///
+    /// # Arguments
+    ///
+    /// * variant - The variant of the rules
+    pub fn with_variant(self, variant: Variant) -> Self {
+        lock(&self.sessions).variant = variant;
+        self
+    }
+
+    /// Return the address the server listens on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Answer the requests until the listener fails, each connection on its own thread
+    /// A connection which fails only loses its own request
+    pub fn serve(&self) -> io::Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept()?;
+            let sessions = Arc::clone(&self.sessions);
+            thread::spawn(move || {
+                let _ = answer(stream, &sessions);
+            });
+        }
+    }
+}
+
+/// Lock the sessions, a thread which panicked while playing leaves them usable
+fn lock(sessions: &Mutex<Sessions>) -> MutexGuard<'_, Sessions> {
+    sessions.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Read the request of the connection and write the response
+/// The sessions are only locked once the request is read
+fn answer(stream: TcpStream, sessions: &Mutex<Sessions>) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // The parameters are in the query, the headers and the body are not needed.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+
+    let mut fields = request_line.split_whitespace();
+    let response = match (fields.next(), fields.next()) {
+        (Some(method), Some(target)) => lock(sessions).respond(method, target),
+        _ => Response::error("400 Bad Request", "Malformed request"),
+    };
+    let mut writer = stream;
+    write!(
+        writer,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    writer.flush()
+}
+
+/// Return the page of the board, offering every opponent
+fn page() -> String {
+    let options: String = Opponent::ALL
+        .iter()
+        .map(|opponent| {
+            let selected = if *opponent == Opponent::default() {
+                " selected"
+            } else {
+                ""
+            };
+            format!(
+                "<option value=\"{}\"{}>{}</option>\n",
+                opponent.id(),
+                selected,
+                opponent.name()
+            )
+        })
+        .collect();
+    PAGE.replace("<!-- opponents -->\n", &options)
+}
+
+/// Write the session as a JSON object: its id, the game state, the winning cells and the status
+fn session_json(id: u64, session: &Session) -> String {
+    let game_state = session.game.state();
+    let winning_cells: Vec<String> = game_state
+        .winning_indexes()
+        .unwrap_or_default()
+        .iter()
+        .map(usize::to_string)
+        .collect();
+    let status = match session.game.status() {
+        GameStatus::InProgress(mark) => format!("Turn of {}", mark),
+        GameStatus::Won(mark) => format!("{} wins", mark),
+        GameStatus::Tie => String::from("Tie"),
+    };
+    format!(
+        "{{\"session\":{},\"state\":{},\"winning_cells\":[{}],\"status\":\"{}\"}}",
+        id,
+        game_state_to_json(game_state),
+        winning_cells.join(","),
+        escape_json(&status)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn sessions() -> Sessions {
+        Sessions {
+            variant: Variant::Standard,
+            games: BTreeMap::new(),
+            next_session: 1,
+        }
+    }
+
+    #[test]
+    fn test_session_against_the_perfect_player() {
+        let mut sessions = sessions();
+        let response = sessions.respond("POST", "/new?ai=perfect");
+        assert_eq!(response.status, "200 OK");
+        assert!(response
+            .body
+            .starts_with("{\"session\":1,\"state\":{\"board\":[null,"));
+
+        let response = sessions.respond("POST", "/move?session=1&cell=0");
+        assert_eq!(response.status, "200 OK");
+        // The perfect player answers a corner with the center.
+        assert!(response
+            .body
+            .contains("\"board\":[\"X\",null,null,null,\"O\",null,null,null,null]"));
+        assert!(response.body.ends_with("\"status\":\"Turn of X\"}"));
+
+        let response = sessions.respond("POST", "/move?session=1&cell=4");
+        assert_eq!(response.status, "400 Bad Request");
+        assert!(response.body.starts_with("{\"error\":"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut sessions = sessions();
+        assert_eq!(
+            sessions.respond("POST", "/new?ai=genius").status,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            sessions.respond("POST", "/move?session=7&cell=0").status,
+            "404 Not Found"
+        );
+        assert_eq!(
+            sessions.respond("GET", "/move").status,
+            "405 Method Not Allowed"
+        );
+        assert_eq!(
+            sessions.respond("GET", "/favicon.ico").status,
+            "404 Not Found"
+        );
+    }
+
+    #[test]
+    fn test_two_humans_win_with_the_winning_cells() {
+        let mut sessions = sessions();
+        sessions.respond("POST", "/new?ai=human");
+        for cell in [0, 3, 1, 4] {
+            sessions.respond("POST", &format!("/move?session=1&cell={}", cell));
+        }
+        let response = sessions.respond("POST", "/move?session=1&cell=2");
+        assert!(response.body.contains("\"winning_cells\":[0,1,2]"));
+        assert!(response.body.ends_with("\"status\":\"X wins\"}"));
+    }
+
+    #[test]
+    fn test_page_offers_every_opponent() {
+        let page = page();
+        for opponent in Opponent::ALL {
+            assert!(page.contains(&format!("<option value=\"{}\"", opponent.id())));
+        }
+        assert!(page.contains("<option value=\"perfect\" selected>Perfect AI</option>"));
+    }
+
+    #[test]
+    fn test_idle_connection_does_not_block_the_others() {
+        let server = WebServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.serve());
+
+        // A connection which never sends its request, like a preconnect of a browser.
+        let _idle = TcpStream::connect(address).unwrap();
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        stream.set_read_timeout(Some(READ_TIMEOUT / 2)).unwrap();
+        let mut received = String::new();
+        stream.read_to_string(&mut received).unwrap();
+        assert!(received.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(received.contains("Content-Type: text/html; charset=utf-8\r\n"));
+        assert!(received.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,104 @@
+//! The opponents offered by the frontends where the user picks who they play against,
+//! the window of the `gui` feature and the page of the web server.
+//! Each opponent has a name shown to the user and an id used in the requests and on the command line.
+
+use crate::{
+    game::players::{
+        builder::AiBuilder, heuristic::HeuristicPlayer, perfect::PerfectPlayer, random::DumbPlayer,
+        Player,
+    },
+    logic::Mark,
+};
+
+/// Who plays against the user.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Opponent {
+    /// Another user, clicking on the same board.
+    Human,
+    /// A computer player picking a random cell.
+    Random,
+    /// A computer player with a shallow search, which can be beaten.
+    Casual,
+    /// A computer player following its rules of thumb.
+    Heuristic,
+    /// A computer player which never loses.
+    #[default]
+    Perfect,
+}
+
+impl Opponent {
+    /// Every opponent, in the order they are offered.
+    pub const ALL: [Opponent; 5] = [
+        Opponent::Human,
+        Opponent::Random,
+        Opponent::Casual,
+        Opponent::Heuristic,
+        Opponent::Perfect,
+    ];
+
+    /// Returns the name of the opponent, as shown to the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Opponent::Human => "Human",
+            Opponent::Random => "Random AI",
+            Opponent::Casual => "Casual AI",
+            Opponent::Heuristic => "Heuristic AI",
+            Opponent::Perfect => "Perfect AI",
+        }
+    }
+
+    /// Returns the id of the opponent, as written in the requests, e.g. `perfect`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Opponent::Human => "human",
+            Opponent::Random => "random",
+            Opponent::Casual => "casual",
+            Opponent::Heuristic => "heuristic",
+            Opponent::Perfect => "perfect",
+        }
+    }
+
+    /// Returns the opponent with the id, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the opponent, as returned by `Opponent::id`.
+    pub fn from_id(id: &str) -> Option<Opponent> {
+        Opponent::ALL
+            .into_iter()
+            .find(|opponent| opponent.id() == id)
+    }
+
+    /// Creates the computer player of the opponent, none for a human.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn player(&self, mark: Mark) -> Option<Box<dyn Player>> {
+        match self {
+            Opponent::Human => None,
+            Opponent::Random => Some(Box::new(DumbPlayer::new(mark))),
+            Opponent::Casual => Some(AiBuilder::casual().build(mark)),
+            Opponent::Heuristic => Some(Box::new(HeuristicPlayer::new(mark))),
+            Opponent::Perfect => Some(Box::new(PerfectPlayer::new(mark))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_id() {
+        for opponent in Opponent::ALL {
+            assert_eq!(Opponent::from_id(opponent.id()), Some(opponent));
+        }
+        assert_eq!(Opponent::from_id("genius"), None);
+        assert!(Opponent::Human.player(Mark::Naught).is_none());
+        assert_eq!(
+            Opponent::Perfect.player(Mark::Naught).unwrap().get_mark(),
+            Mark::Naught
+        );
+    }
+}
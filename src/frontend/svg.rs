@@ -0,0 +1,178 @@
+//! The renderer drawing the positions of the game as SVG images, e.g. to embed a game in a web page.
+//! Each position is written to its own file in a directory, named after the number of marks on the grid,
+//! e.g. `position_3.svg` after the third move, and the final board is also written to `final.svg`
+//! with the winning line drawn through.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use crate::{
+    game::{engine::GameResult, renderers::Renderer},
+    logic::{CellCoord, GameState, Grid, Mark},
+};
+
+/// The size of a cell of the grid, in pixels.
+const CELL_SIZE: usize = 100;
+
+/// A renderer writing each position to an SVG file.
+#[derive(Debug)]
+pub struct SvgRenderer {
+    directory: PathBuf,
+    /// The last error met while writing a position.
+    error: Mutex<Option<io::Error>>,
+}
+
+impl SvgRenderer {
+    /// Creates a new `SvgRenderer` writing the files to the directory, which is created with the first file.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory of the SVG files.
+    pub fn new(directory: &Path) -> Self {
+        SvgRenderer {
+            directory: directory.to_path_buf(),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// Returns the last error met while writing a position, if any, and forgets it.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    /// Writes the image of the game state to the file of the directory, keeping the error if it fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file in the directory.
+    /// * `game_state` - The game state to draw.
+    fn write(&self, file_name: &str, game_state: &GameState) {
+        let written = fs::create_dir_all(&self.directory)
+            .and_then(|()| fs::write(self.directory.join(file_name), board_to_svg(game_state)));
+        if let Err(error) = written {
+            *self.error.lock().unwrap_or_else(PoisonError::into_inner) = Some(error);
+        }
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn render(&self, game_state: &GameState) {
+        let marks = Grid::SIZE - game_state.grid().empty_count();
+        self.write(&format!("position_{}.svg", marks), game_state);
+    }
+
+    fn render_end(&self, result: &GameResult) {
+        self.write("final.svg", &result.final_state);
+    }
+}
+
+/// Draws the grid of the game state as an SVG image, with the winning line drawn through at the end of the game.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to draw.
+pub fn board_to_svg(game_state: &GameState) -> String {
+    let size = CELL_SIZE * Grid::WIDTH;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">\n\
+         <rect width=\"{0}\" height=\"{0}\" fill=\"white\"/>\n",
+        size
+    );
+    for i in 1..Grid::WIDTH {
+        let offset = i * CELL_SIZE;
+        svg += &format!(
+            "<line x1=\"{0}\" y1=\"0\" x2=\"{0}\" y2=\"{1}\" stroke=\"#333\" stroke-width=\"4\"/>\n\
+             <line x1=\"0\" y1=\"{0}\" x2=\"{1}\" y2=\"{0}\" stroke=\"#333\" stroke-width=\"4\"/>\n",
+            offset, size
+        );
+    }
+
+    let arm = CELL_SIZE * 3 / 10;
+    for (index, cell) in game_state.grid().cells().into_iter().enumerate() {
+        let (x, y) = cell_center(index);
+        match cell.mark() {
+            Some(Mark::Cross) => {
+                svg += &format!(
+                    "<path d=\"M {} {} L {} {} M {} {} L {} {}\" stroke=\"#d33\" stroke-width=\"8\" stroke-linecap=\"round\"/>\n",
+                    x - arm, y - arm, x + arm, y + arm, x + arm, y - arm, x - arm, y + arm
+                );
+            }
+            Some(Mark::Naught) => {
+                svg += &format!(
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" stroke=\"#36c\" stroke-width=\"8\" fill=\"none\"/>\n",
+                    x, y, arm
+                );
+            }
+            None => {}
+        }
+    }
+
+    // The indexes of a line are in order, the line goes from the first cell to the last one.
+    if let Some(indexes) = game_state.winning_indexes() {
+        let (x1, y1) = cell_center(indexes[0]);
+        let (x2, y2) = cell_center(indexes[indexes.len() - 1]);
+        svg += &format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#2a2\" stroke-width=\"10\" stroke-linecap=\"round\" opacity=\"0.8\"/>\n",
+            x1, y1, x2, y2
+        );
+    }
+    svg + "</svg>\n"
+}
+
+/// Returns the coordinates of the center of the cell in the image.
+///
+/// # Arguments
+///
+/// * `index` - The index of the cell, row by row.
+fn cell_center(index: usize) -> (usize, usize) {
+    let coord = CellCoord::from_index(index).expect("The cell is on the grid");
+    (
+        coord.column() * CELL_SIZE + CELL_SIZE / 2,
+        coord.row() * CELL_SIZE + CELL_SIZE / 2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::game::{ScriptedPlayer, TicTacToe};
+
+    #[test]
+    fn test_board_to_svg() {
+        let svg = board_to_svg(&GameState::from_notation("X../.O./... X").unwrap());
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("<path d=\"M 20 20 L 80 80 M 80 20 L 20 80\""));
+        assert!(svg.contains("<circle cx=\"150\" cy=\"150\""));
+        assert!(!svg.contains("stroke=\"#2a2\""));
+
+        let svg = board_to_svg(&GameState::from_notation("XO./XO./X.. O").unwrap());
+        assert!(svg.contains("<line x1=\"50\" y1=\"50\" x2=\"50\" y2=\"250\" stroke=\"#2a2\""));
+    }
+
+    #[test]
+    fn test_svg_renderer_writes_the_positions() {
+        let directory = env::temp_dir().join(format!("svg_positions_{}", process::id()));
+        let renderer = SvgRenderer::new(&directory);
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        TicTacToe::new(&mut cross, &mut naught, &renderer, None)
+            .unwrap()
+            .play(None);
+        assert!(renderer.take_error().is_none());
+
+        for marks in 0..=5 {
+            assert!(directory.join(format!("position_{}.svg", marks)).exists());
+        }
+        let final_board = fs::read_to_string(directory.join("final.svg")).unwrap();
+        assert!(final_board.contains("stroke=\"#2a2\""));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
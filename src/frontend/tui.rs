@@ -0,0 +1,506 @@
+//! The full-screen frontend of the terminal, drawn with ratatui, behind the `tui` feature.
+//! The arrow keys move the cursor on the board and Enter or Space places the mark,
+//! `x` and `o` place either mark with `Variant::Wild`, and with `Variant::Gravity` the mark drops in the column of the cursor.
+//! `u` and `r` undo and redo the moves, and `q`, Esc or Ctrl-C interrupt the game.
+//! The status pane shows the players, whose turn it is, the clocks, the last move and why a move was rejected.
+//!
+//! The `Tui` owns the terminal, shared by its renderer and its players,
+//! and gives it back to the shell when the last of them is dropped.
+
+use std::io::{self, Stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crossterm::cursor::Show;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::{Frame, Terminal};
+
+use crate::{
+    frontend::{console::renderers::clock_to_text, html::ending_text},
+    game::{
+        clock::Clocks,
+        control::GameControl,
+        engine::GameResult,
+        players::{display_name, Player, PlayerCommand},
+        renderers::Renderer,
+    },
+    logic::{errors::MoveError, Cell, CellCoord, GameMove, GameState, Grid, Mark, Variant},
+};
+
+/// Whether a `Tui` holds the terminal, in raw mode on the alternate screen.
+static TERMINAL_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Gives the terminal back to the shell if a `Tui` holds it,
+/// e.g. before the process exits without dropping the `Tui`.
+pub fn restore_terminal() {
+    if TERMINAL_TAKEN.swap(false, Ordering::SeqCst) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+    }
+}
+
+/// The terminal and what it shows, kept from one frame to the next.
+struct Screen {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// The control of the games, interrupted when a player quits.
+    control: GameControl,
+    game_state: Option<GameState>,
+    /// The cell under the cursor, shown while a player chooses their move.
+    cursor: CellCoord,
+    choosing: bool,
+    /// The marks and the names of the players.
+    players: Vec<String>,
+    clocks: Option<String>,
+    last_move: Option<String>,
+    message: Option<String>,
+}
+
+impl Screen {
+    /// Draws the board and the status pane.
+    /// A frame which cannot be drawn is skipped, the terminal has nowhere else to tell it.
+    fn draw(&mut self) {
+        let Screen {
+            terminal,
+            game_state,
+            cursor,
+            choosing,
+            players,
+            clocks,
+            last_move,
+            message,
+            ..
+        } = self;
+        let cursor = choosing.then_some(*cursor);
+        let mut status: Vec<Line> = players
+            .iter()
+            .map(|player| Line::from(player.as_str()))
+            .collect();
+        if let Some(game_state) = game_state.as_ref() {
+            status.push(Line::from(turn_text(game_state)));
+        }
+        status.extend(clocks.iter().map(|clocks| Line::from(clocks.as_str())));
+        status.extend(
+            last_move
+                .iter()
+                .map(|last_move| Line::from(format!("Last move: {}", last_move))),
+        );
+        status.extend(message.iter().map(|message| Line::from(message.as_str())));
+        if cursor.is_some() {
+            status.push(Line::from(""));
+            status.push(Line::from(help_text(game_state.as_ref())));
+        }
+        let _ = terminal.draw(|frame| draw_frame(frame, game_state.as_ref(), cursor, status));
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        TERMINAL_TAKEN.store(false, Ordering::SeqCst);
+        let _ = disable_raw_mode();
+        let _ = execute!(self.terminal.backend_mut(), LeaveAlternateScreen);
+        let _ = self.terminal.show_cursor();
+    }
+}
+
+/// Locks the screen, a thread which panicked while drawing leaves it usable.
+fn lock(screen: &Mutex<Screen>) -> MutexGuard<'_, Screen> {
+    screen.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The full-screen frontend, whose renderer and players share the terminal.
+#[derive(Clone)]
+pub struct Tui {
+    screen: Arc<Mutex<Screen>>,
+}
+
+impl Tui {
+    /// Takes the terminal over, in raw mode on the alternate screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The control of the games, interrupted when a player quits.
+    pub fn new(control: GameControl) -> io::Result<Self> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        let terminal = execute!(stdout, EnterAlternateScreen)
+            .and_then(|()| Terminal::new(CrosstermBackend::new(stdout)));
+        let terminal = match terminal {
+            Ok(terminal) => terminal,
+            Err(error) => {
+                let _ = disable_raw_mode();
+                return Err(error);
+            }
+        };
+        TERMINAL_TAKEN.store(true, Ordering::SeqCst);
+        Ok(Tui {
+            screen: Arc::new(Mutex::new(Screen {
+                terminal,
+                control,
+                game_state: None,
+                cursor: CellCoord::from_index(Grid::SIZE / 2).expect("The center is on the grid"),
+                choosing: false,
+                players: vec![],
+                clocks: None,
+                last_move: None,
+                message: None,
+            })),
+        })
+    }
+
+    /// Returns the renderer drawing the games on the screen.
+    pub fn renderer(&self) -> TuiRenderer {
+        TuiRenderer {
+            screen: Arc::clone(&self.screen),
+        }
+    }
+
+    /// Returns a player choosing their moves with the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn player(&self, mark: Mark) -> TuiPlayer {
+        TuiPlayer {
+            screen: Arc::clone(&self.screen),
+            mark,
+            name: None,
+            command: None,
+        }
+    }
+}
+
+/// The renderer of the full-screen frontend, see `Tui::renderer`.
+pub struct TuiRenderer {
+    screen: Arc<Mutex<Screen>>,
+}
+
+impl Renderer for TuiRenderer {
+    fn render(&self, game_state: &GameState) {
+        self.render_with_players(game_state, &[]);
+    }
+
+    fn render_with_players(&self, game_state: &GameState, players: &[&dyn Player]) {
+        let mut screen = lock(&self.screen);
+        screen.game_state = Some(*game_state);
+        screen.players = players
+            .iter()
+            .map(|player| format!("{}: {}", player.get_mark(), display_name(*player)))
+            .collect();
+        // The move leading to the game state and the rejected move, if any, are rendered next.
+        screen.last_move = None;
+        screen.message = None;
+        screen.draw();
+    }
+
+    fn render_move(&self, game_move: &GameMove) {
+        let mut screen = lock(&self.screen);
+        screen.last_move = Some(game_move.to_string());
+        screen.draw();
+    }
+
+    fn render_invalid(&self, error: &MoveError) {
+        let mut screen = lock(&self.screen);
+        screen.message = Some(format!("Invalid move: {}", error));
+        screen.draw();
+    }
+
+    fn render_clocks(&self, clocks: &Clocks) {
+        let mut screen = lock(&self.screen);
+        screen.clocks = Some(format!(
+            "{} {} | {} {}",
+            Mark::Cross,
+            clock_to_text(clocks.remaining(Mark::Cross)),
+            Mark::Naught,
+            clock_to_text(clocks.remaining(Mark::Naught))
+        ));
+        screen.draw();
+    }
+
+    /// Shows the result of the game until a key is pressed.
+    fn render_end(&self, result: &GameResult) {
+        let mut screen = lock(&self.screen);
+        screen.game_state = Some(result.final_state);
+        screen.message = Some(format!("{}, press any key", ending_text(result)));
+        screen.draw();
+        while read_key().is_some_and(|key| key.kind != KeyEventKind::Press) {}
+    }
+}
+
+/// A player of the full-screen frontend, see `Tui::player`.
+pub struct TuiPlayer {
+    screen: Arc<Mutex<Screen>>,
+    mark: Mark,
+    name: Option<String>,
+    command: Option<PlayerCommand>,
+}
+
+impl TuiPlayer {
+    /// Sets the name shown for the player.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the player.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+}
+
+impl Player for TuiPlayer {
+    /// Moves the cursor with the keys until the player places their mark, runs a command or quits.
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        let mut screen = lock(&self.screen);
+        screen.game_state = Some(*game_state);
+        screen.choosing = true;
+        let game_move = loop {
+            screen.draw();
+            let Some(key) = read_key() else {
+                break None;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let cursor = screen.cursor;
+            let placed_mark = match key.code {
+                KeyCode::Left => {
+                    screen.cursor = moved(cursor, 0, -1);
+                    continue;
+                }
+                KeyCode::Right => {
+                    screen.cursor = moved(cursor, 0, 1);
+                    continue;
+                }
+                KeyCode::Up => {
+                    screen.cursor = moved(cursor, -1, 0);
+                    continue;
+                }
+                KeyCode::Down => {
+                    screen.cursor = moved(cursor, 1, 0);
+                    continue;
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => self.mark,
+                KeyCode::Char('x') if game_state.variant() == Variant::Wild => Mark::Cross,
+                KeyCode::Char('o') if game_state.variant() == Variant::Wild => Mark::Naught,
+                KeyCode::Char('u') => {
+                    self.command = Some(PlayerCommand::Undo);
+                    break None;
+                }
+                KeyCode::Char('r') => {
+                    self.command = Some(PlayerCommand::Redo);
+                    break None;
+                }
+                KeyCode::Char('c') if !key.modifiers.contains(KeyModifiers::CONTROL) => continue,
+                KeyCode::Char('q') | KeyCode::Char('c') | KeyCode::Esc => {
+                    screen.control.interrupt();
+                    break None;
+                }
+                _ => continue,
+            };
+            let game_move = if game_state.variant() == Variant::Gravity {
+                game_state.make_move_to_column(cursor.column())
+            } else {
+                game_state.make_move_with(cursor.index(), placed_mark)
+            };
+            match game_move {
+                Ok(game_move) => break Some(game_move),
+                Err(error) => screen.message = Some(format!("Invalid move: {}", error)),
+            }
+        };
+        screen.choosing = false;
+        game_move
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.kind().to_string())
+    }
+
+    fn kind(&self) -> &'static str {
+        "Human"
+    }
+
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        self.command.take()
+    }
+}
+
+/// Waits for the next key, or returns `None` if the terminal cannot be read.
+fn read_key() -> Option<KeyEvent> {
+    loop {
+        match event::read() {
+            Ok(Event::Key(key)) => return Some(key),
+            Ok(_) => {}
+            Err(_) => return None,
+        }
+    }
+}
+
+/// Returns the cell next to the cursor in the direction, or the cursor if it is on the edge of the grid.
+///
+/// # Arguments
+///
+/// * `cursor` - The cell under the cursor.
+/// * `rows` - The number of rows to move, down when positive.
+/// * `columns` - The number of columns to move, right when positive.
+fn moved(cursor: CellCoord, rows: isize, columns: isize) -> CellCoord {
+    cursor
+        .row()
+        .checked_add_signed(rows)
+        .zip(cursor.column().checked_add_signed(columns))
+        .and_then(|(row, column)| CellCoord::new(row, column))
+        .unwrap_or(cursor)
+}
+
+/// Returns whose turn it is, or how the game ended, e.g. `X to play`.
+fn turn_text(game_state: &GameState) -> String {
+    if !game_state.game_over() {
+        format!("{} to play", game_state.current_mark())
+    } else {
+        match game_state.winner_mark() {
+            Some(mark) => format!("{} wins", mark),
+            None => String::from("Tie"),
+        }
+    }
+}
+
+/// Returns the keys of the player, with the keys of the marks with `Variant::Wild`.
+fn help_text(game_state: Option<&GameState>) -> &'static str {
+    match game_state.map(GameState::variant) {
+        Some(Variant::Wild) => "Arrows: move  Enter/x/o: place  u: undo  r: redo  q: quit",
+        _ => "Arrows: move  Enter: place  u: undo  r: redo  q: quit",
+    }
+}
+
+/// Draws the board on the left and the status pane on the right.
+///
+/// # Arguments
+///
+/// * `frame` - The frame to draw.
+/// * `game_state` - The game state on the board, none before the first one.
+/// * `cursor` - The cell under the cursor, if a player is choosing their move.
+/// * `status` - The lines of the status pane.
+fn draw_frame(
+    frame: &mut Frame,
+    game_state: Option<&GameState>,
+    cursor: Option<CellCoord>,
+    status: Vec<Line>,
+) {
+    let areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(15), Constraint::Min(24)])
+        .split(frame.size());
+    let board = Paragraph::new(board_lines(game_state, cursor))
+        .block(Block::default().borders(Borders::ALL).title("Board"));
+    frame.render_widget(board, areas[0]);
+    let status =
+        Paragraph::new(status).block(Block::default().borders(Borders::ALL).title("Status"));
+    frame.render_widget(status, areas[1]);
+}
+
+/// Returns the lines of the board, the marks in color, the winning line in bold on green
+/// and the cell under the cursor in inverse video.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state on the board, none for an empty board.
+/// * `cursor` - The cell under the cursor, if any.
+fn board_lines(game_state: Option<&GameState>, cursor: Option<CellCoord>) -> Vec<Line<'static>> {
+    let cells = game_state.map_or([Cell::new_empty(); Grid::SIZE], |game_state| {
+        game_state.grid().cells()
+    });
+    let winning_indexes = game_state
+        .and_then(GameState::winning_indexes)
+        .unwrap_or_default();
+    let mut lines = vec![];
+    for row in 0..Grid::WIDTH {
+        if row > 0 {
+            lines.push(Line::from("───┼───┼───"));
+        }
+        let mut spans = vec![];
+        for column in 0..Grid::WIDTH {
+            if column > 0 {
+                spans.push(Span::raw("│"));
+            }
+            let coord = CellCoord::new(row, column).expect("The cell is on the grid");
+            let cell = cells[coord.index()];
+            let mut style = match cell.mark() {
+                Some(Mark::Cross) => Style::default().fg(Color::Red),
+                Some(Mark::Naught) => Style::default().fg(Color::Blue),
+                None => Style::default(),
+            };
+            if winning_indexes.contains(&coord.index()) {
+                style = style.bg(Color::Green).add_modifier(Modifier::BOLD);
+            }
+            if cursor == Some(coord) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            spans.push(Span::styled(format!(" {} ", cell), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coord(row: usize, column: usize) -> CellCoord {
+        CellCoord::new(row, column).unwrap()
+    }
+
+    #[test]
+    fn test_moved() {
+        assert_eq!(moved(coord(1, 1), -1, 0), coord(0, 1));
+        assert_eq!(moved(coord(1, 1), 1, 1), coord(2, 2));
+        // The cursor stays on the grid.
+        assert_eq!(moved(coord(0, 0), -1, 0), coord(0, 0));
+        assert_eq!(moved(coord(2, 2), 0, 1), coord(2, 2));
+    }
+
+    #[test]
+    fn test_turn_text() {
+        let game_state = GameState::from_notation(".../.X./... O").unwrap();
+        assert_eq!(turn_text(&game_state), "O to play");
+        let game_state = GameState::from_notation("XXX/OO./... O").unwrap();
+        assert_eq!(turn_text(&game_state), "X wins");
+        let game_state = GameState::from_notation("XOX/XOO/OXX O").unwrap();
+        assert_eq!(turn_text(&game_state), "Tie");
+    }
+
+    #[test]
+    fn test_board_lines() {
+        let lines = board_lines(None, Some(coord(1, 1)));
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[2].spans.len(), 5);
+        assert_eq!(lines[2].spans[2].content, "   ");
+        assert!(lines[2].spans[2]
+            .style
+            .add_modifier
+            .contains(Modifier::REVERSED));
+        assert!(!lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::REVERSED));
+
+        let game_state = GameState::from_notation("XXX/OO./... O").unwrap();
+        let lines = board_lines(Some(&game_state), None);
+        assert_eq!(lines[0].spans[0].content, " X ");
+        assert_eq!(lines[0].spans[0].style.bg, Some(Color::Green));
+        assert_eq!(lines[2].spans[0].content, " O ");
+        assert_eq!(lines[2].spans[0].style.fg, Some(Color::Blue));
+        assert_eq!(lines[2].spans[0].style.bg, None);
+    }
+}
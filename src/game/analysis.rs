@@ -0,0 +1,163 @@
+//! The analysis of positions, for the frontends which offer hints without a player.
+//! The hints are looked up in the `PolicyTable` of the variant, which is solved on first use,
+//! and the positions out of the table fall back to the `HeuristicPlayer`.
+//! The analysis of every move solves the position again, to tell how many plies the game lasts
+//! with perfect play: the winner ends the game as soon as possible and the loser as late as possible.
+//! `perft` counts the leaves of the tree of moves, to check the move generation against known totals.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::logic::solver::{move_value, WIN_VALUE};
+use crate::logic::{GameMove, GameState, Grid};
+
+use super::players::heuristic::HeuristicPlayer;
+use super::players::perfect::PolicyTable;
+use super::players::Player;
+
+/// The outcome of a move with perfect play from both players, for the player making it.
+/// The distances count the plies until the end of the game, the move included.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    /// The player wins in the number of plies.
+    Win(usize),
+    /// The game ends without a winner.
+    Draw,
+    /// The player loses in the number of plies.
+    Loss(usize),
+}
+
+impl Outcome {
+    /// Returns the outcome of the value of a move.
+    fn from_value(value: i32) -> Self {
+        match value {
+            0 => Outcome::Draw,
+            value if value > 0 => Outcome::Win((WIN_VALUE - value) as usize),
+            value => Outcome::Loss((WIN_VALUE + value) as usize),
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Win(plies) => write!(f, "Win in {}", plies),
+            Outcome::Draw => write!(f, "Draw"),
+            Outcome::Loss(plies) => write!(f, "Loss in {}", plies),
+        }
+    }
+}
+
+/// Returns a move of perfect play for the player whose turn it is, winning at once when possible,
+/// or `None` if the game is over.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to find the move for.
+pub fn best_move(game_state: &GameState) -> Option<GameMove> {
+    if game_state.game_over() {
+        return None;
+    }
+    let best_move = PolicyTable::for_variant(game_state.variant())
+        .best_moves(game_state)
+        .and_then(|moves| {
+            // Every winning move is perfect play, the one ending the game makes the better hint.
+            let mark = game_state.current_mark();
+            let winning_move = moves
+                .iter()
+                .find(|game_move| game_move.after_state().winner_mark() == Some(mark));
+            winning_move.or(moves.first()).copied()
+        });
+    best_move.or_else(|| HeuristicPlayer::new(game_state.current_mark()).get_move(game_state))
+}
+
+/// Returns the cell index of a move of perfect play for the player whose turn it is,
+/// or `None` if the game is over. With `Variant::Wild`, use `best_move` to also get the mark to place.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to find the move for.
+pub fn suggest_move(game_state: &GameState) -> Option<usize> {
+    best_move(game_state).map(|game_move| game_move.cell_index())
+}
+
+/// Returns the outcome of every cell the player whose turn it is can mark, by cell index,
+/// or no cell if the game is over. With `Variant::Wild`, a cell gets the outcome of its best mark.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to analyze.
+pub fn analyze(game_state: &GameState) -> Vec<(usize, Outcome)> {
+    if game_state.game_over() {
+        return vec![];
+    }
+    let mut values = HashMap::new();
+    let mut best_values: [Option<i32>; Grid::SIZE] = [None; Grid::SIZE];
+    for game_move in game_state.possible_moves() {
+        let value = move_value(game_move.after_state(), &mut values);
+        let best_value = &mut best_values[game_move.cell_index()];
+        *best_value = Some(best_value.map_or(value, |best_value| best_value.max(value)));
+    }
+    best_values
+        .iter()
+        .enumerate()
+        .filter_map(|(cell_index, value)| Some((cell_index, Outcome::from_value((*value)?))))
+        .collect()
+}
+
+/// Returns the number of leaves of the tree of the moves from the game state, to the depth:
+/// the game states after `depth` plies, and the finished game states reached before.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state at the root of the tree.
+/// * `depth` - The number of plies of the tree.
+pub fn perft(game_state: &GameState, depth: usize) -> u64 {
+    if depth == 0 || game_state.game_over() {
+        return 1;
+    }
+    game_state
+        .possible_moves()
+        .iter()
+        .map(|game_move| perft(game_move.after_state(), depth - 1))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_move() {
+        let game_state = GameState::from_notation("XX./OO./... X").unwrap();
+        assert_eq!(suggest_move(&game_state), Some(2));
+        let game_state = GameState::from_notation("XX./OO./... O").unwrap();
+        assert_eq!(suggest_move(&game_state), Some(5));
+        let game_state = GameState::from_notation("XXX/OO./... O").unwrap();
+        assert_eq!(suggest_move(&game_state), None);
+    }
+
+    #[test]
+    fn test_analyze() {
+        let game_state = GameState::from_notation("XX./OO./... X").unwrap();
+        let outcomes = analyze(&game_state);
+        assert_eq!(outcomes.len(), 5);
+        assert_eq!(outcomes[0], (2, Outcome::Win(1)));
+        assert!(outcomes.contains(&(8, Outcome::Loss(2))));
+
+        let game_state = GameState::from_notation("X../.../... O").unwrap();
+        let outcomes = analyze(&game_state);
+        assert!(outcomes.contains(&(4, Outcome::Draw)));
+        assert!(outcomes.contains(&(1, Outcome::Loss(6))));
+        assert!(analyze(&GameState::from_notation("XXX/OO./... O").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_perft() {
+        let game_state = GameState::from_notation(".../.../... X").unwrap();
+        let counts: Vec<u64> = (0..=5).map(|depth| perft(&game_state, depth)).collect();
+        assert_eq!(counts, vec![1, 9, 72, 504, 3024, 15120]);
+        // The number of complete games of tic-tac-toe.
+        assert_eq!(perft(&game_state, 9), 255_168);
+    }
+}
@@ -0,0 +1,155 @@
+//! The asynchronous counterpart of the `Player` trait, behind the `async` feature,
+//! so players waiting for a network peer or a GUI can await their input without blocking.
+//! Only the standard library futures are used, the game can run on any executor.
+//! An `AsyncPlayer` is seated in the `TicTacToe` engine like any player, and returns itself
+//! from `Player::as_async_player`, so `TicTacToe::play_async` awaits its moves.
+
+use std::future::{self, Future};
+use std::pin::Pin;
+
+use crate::logic::errors::MoveError;
+use crate::logic::{GameMove, GameState};
+
+use super::players::Player;
+
+/// The future of the next move of an `AsyncPlayer`.
+pub type MoveFuture<'a> = Pin<Box<dyn Future<Output = Option<GameMove>> + 'a>>;
+
+/// The future of the game state after the move of an `AsyncPlayer`.
+pub type GameStateFuture<'a> = Pin<Box<dyn Future<Output = Result<GameState, MoveError>> + 'a>>;
+
+/// The AsyncPlayer trait defines the behavior of a player whose moves are awaited.
+/// Its mark, name and kind are the ones of the `Player` it also is.
+/// An async player trait has 2 methods:
+/// - get_move_async() returns the future of the next move of the player, ready with `Player::get_move` by default
+/// - make_move_async() returns the future of the game state after the player has made a move
+pub trait AsyncPlayer: Player {
+    fn make_move_async<'a>(&'a mut self, game_state: &'a GameState) -> GameStateFuture<'a> {
+        Box::pin(async move {
+            if self.get_mark() != game_state.current_mark() {
+                return Err(MoveError::NotYourTurn(self.get_mark()));
+            }
+            match self.get_move_async(game_state).await {
+                Some(next_move) => Ok(*next_move.after_state()),
+                None => Err(MoveError::NoPossibleMoves),
+            }
+        })
+    }
+    fn get_move_async<'a>(&'a mut self, game_state: &'a GameState) -> MoveFuture<'a> {
+        Box::pin(future::ready(self.get_move(game_state)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    use super::*;
+    use crate::game::engine::{GameEnding, TicTacToe};
+    use crate::game::renderers::NoRenderer;
+    use crate::game::ScriptedPlayer;
+    use crate::logic::Mark;
+
+    /// A player which is not ready the first time its move is polled.
+    struct SlowPlayer {
+        mark: Mark,
+        cell_indexes: Vec<usize>,
+        pending: usize,
+    }
+
+    struct NotReadyOnce {
+        polled: bool,
+    }
+
+    impl Future for NotReadyOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+            if self.polled {
+                return Poll::Ready(());
+            }
+            self.polled = true;
+            context.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    impl Player for SlowPlayer {
+        fn get_mark(&self) -> Mark {
+            self.mark
+        }
+
+        fn get_move(&mut self, _game_state: &GameState) -> Option<GameMove> {
+            panic!("The moves of the slow player are awaited")
+        }
+
+        fn as_async_player(&mut self) -> Option<&mut dyn AsyncPlayer> {
+            Some(self)
+        }
+    }
+
+    impl AsyncPlayer for SlowPlayer {
+        fn get_move_async<'a>(&'a mut self, game_state: &'a GameState) -> MoveFuture<'a> {
+            Box::pin(async move {
+                NotReadyOnce { polled: false }.await;
+                let cell_index = *self.cell_indexes.get(self.pending)?;
+                self.pending += 1;
+                game_state.make_move_to(cell_index).ok()
+            })
+        }
+    }
+
+    /// A waker which does nothing, the test polls the game until it is ready.
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut context = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_play_async() {
+        let mut cross = SlowPlayer {
+            mark: Mark::Cross,
+            cell_indexes: vec![0, 1, 2],
+            pending: 0,
+        };
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None).unwrap();
+        let result = block_on(game.play_async(None));
+        drop(game);
+
+        assert_eq!(result.ending, GameEnding::Finished);
+        assert_eq!(result.winner, Some(Mark::Cross));
+        assert_eq!(result.moves.len(), 5);
+        assert_eq!(cross.pending, 3);
+    }
+
+    #[test]
+    fn test_play_async_forfeits_a_broken_player() {
+        let mut cross = SlowPlayer {
+            mark: Mark::Cross,
+            cell_indexes: vec![],
+            pending: 0,
+        };
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![4]);
+        let result = block_on(
+            TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+                .unwrap()
+                .with_invalid_move_policy(crate::game::InvalidMovePolicy::Retry(2))
+                .play_async(None),
+        );
+        assert_eq!(result.ending, GameEnding::Forfeit(Mark::Cross));
+    }
+}
@@ -0,0 +1,108 @@
+//! A self-play benchmark: two players play a number of games headlessly, taking turns to start,
+//! and the report gives the speed of the games, the game states searched and the results.
+//! It measures the impact of the optimizations of the searches of the computer players.
+
+use std::time::{Duration, Instant};
+
+use crate::logic::{errors::Error, Mark, Variant};
+
+use super::engine::{InvalidMovePolicy, TicTacToe};
+use super::matches::{Match, MatchResult};
+use super::players::Player;
+use super::renderers::NoRenderer;
+
+/// The report of a benchmark, see `bench`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BenchReport {
+    /// The results of the games.
+    pub result: MatchResult,
+    /// The time the games took.
+    pub elapsed: Duration,
+    /// The number of game states searched by both players.
+    pub nodes: u64,
+}
+
+impl BenchReport {
+    /// Returns the number of games played per second.
+    pub fn games_per_second(&self) -> f64 {
+        self.result.games().len() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Returns the average number of game states searched in a game.
+    pub fn average_nodes(&self) -> f64 {
+        self.nodes as f64 / self.result.games().len().max(1) as f64
+    }
+}
+
+/// Plays the games between the two players without rendering them, and reports on them.
+/// A player which fails to make a valid move forfeits the game.
+/// Returns an error if the players have the wrong marks.
+///
+/// # Arguments
+///
+/// * `player_x` - The player of the crosses.
+/// * `player_o` - The player of the naughts.
+/// * `games` - The number of games.
+/// * `variant` - The variant of the rules the games are played with.
+pub fn bench(
+    player_x: &mut dyn Player,
+    player_o: &mut dyn Player,
+    games: usize,
+    variant: Variant,
+) -> Result<BenchReport, Error> {
+    let nodes_before = player_x.nodes_searched() + player_o.nodes_searched();
+    let start = Instant::now();
+    let mut game = TicTacToe::builder()
+        .player_x(&mut *player_x)
+        .player_o(&mut *player_o)
+        .renderer(NoRenderer)
+        .variant(variant)
+        .invalid_move_policy(InvalidMovePolicy::Forfeit)
+        .build()?;
+    let result = Match::new(games).play(&mut game);
+    let elapsed = start.elapsed();
+    drop(game);
+
+    Ok(BenchReport {
+        result,
+        elapsed,
+        nodes: player_x.nodes_searched() + player_o.nodes_searched() - nodes_before,
+    })
+}
+
+/// Writes the report for the terminal, e.g. `100 games in 1.23s (81.3 games/s)`.
+///
+/// # Arguments
+///
+/// * `report` - The report of the benchmark.
+pub fn report_to_text(report: &BenchReport) -> String {
+    format!(
+        "{} games in {:.2}s ({:.1} games/s)\nNodes searched per game: {:.0}\nX wins: {}, O wins: {}, ties: {}",
+        report.result.games().len(),
+        report.elapsed.as_secs_f64(),
+        report.games_per_second(),
+        report.average_nodes(),
+        report.result.wins(Mark::Cross),
+        report.result.wins(Mark::Naught),
+        report.result.ties()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{DumbPlayer, MinimaxPlayer};
+
+    #[test]
+    fn test_bench() {
+        let mut cross = MinimaxPlayer::new(Mark::Cross);
+        let mut naught = DumbPlayer::new(Mark::Naught);
+        let report = bench(&mut cross, &mut naught, 4, Variant::Standard).unwrap();
+
+        assert_eq!(report.result.games().len(), 4);
+        assert_eq!(report.result.wins(Mark::Naught), 0);
+        assert!(report.nodes > 0);
+        assert_eq!(report.nodes, cross.nodes_searched());
+        assert!(report_to_text(&report).starts_with("4 games in "));
+    }
+}
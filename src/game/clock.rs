@@ -0,0 +1,142 @@
+//! The clocks of the players, when the game is played with a time control.
+//! Each player has a remaining time, the thinking time of each of their moves is deducted from it,
+//! and the increment is added to it after each of their valid moves.
+//! A player whose time runs out loses the game on flag fall, once their move arrives.
+
+use std::time::Duration;
+
+use crate::logic::Mark;
+
+/// The time each player has for the whole game.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TimeControl {
+    initial: Duration,
+    increment: Duration,
+}
+
+impl TimeControl {
+    /// Creates a new `TimeControl` giving each player the time for the whole game, without increment.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The time of each player at the start of the game.
+    pub fn new(initial: Duration) -> Self {
+        TimeControl {
+            initial,
+            increment: Duration::ZERO,
+        }
+    }
+
+    /// Sets the time added to the clock of a player after each of their moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `increment` - The time added after each move.
+    pub fn with_increment(mut self, increment: Duration) -> Self {
+        self.increment = increment;
+        self
+    }
+
+    /// Returns the time of each player at the start of the game.
+    pub fn initial(&self) -> Duration {
+        self.initial
+    }
+
+    /// Returns the time added to the clock of a player after each of their moves.
+    pub fn increment(&self) -> Duration {
+        self.increment
+    }
+}
+
+/// The remaining time of both players.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Clocks {
+    cross: Duration,
+    naught: Duration,
+    increment: Duration,
+}
+
+impl Clocks {
+    /// Creates the clocks at the start of a game played with the time control.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_control` - The time control of the game.
+    pub fn new(time_control: &TimeControl) -> Self {
+        Clocks {
+            cross: time_control.initial,
+            naught: time_control.initial,
+            increment: time_control.increment,
+        }
+    }
+
+    /// Returns the remaining time of the player of the mark.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn remaining(&self, mark: Mark) -> Duration {
+        match mark {
+            Mark::Cross => self.cross,
+            Mark::Naught => self.naught,
+        }
+    }
+
+    /// Deducts the thinking time from the clock of the player,
+    /// and returns `false` if their time ran out.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `elapsed` - The time the player took.
+    pub(crate) fn deduct(&mut self, mark: Mark, elapsed: Duration) -> bool {
+        let clock = self.clock_mut(mark);
+        match clock.checked_sub(elapsed) {
+            Some(remaining) if !remaining.is_zero() => {
+                *clock = remaining;
+                true
+            }
+            _ => {
+                *clock = Duration::ZERO;
+                false
+            }
+        }
+    }
+
+    /// Adds the increment to the clock of the player, after their move.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub(crate) fn add_increment(&mut self, mark: Mark) {
+        let increment = self.increment;
+        *self.clock_mut(mark) += increment;
+    }
+
+    fn clock_mut(&mut self, mark: Mark) -> &mut Duration {
+        match mark {
+            Mark::Cross => &mut self.cross,
+            Mark::Naught => &mut self.naught,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clocks() {
+        let time_control =
+            TimeControl::new(Duration::from_secs(10)).with_increment(Duration::from_secs(1));
+        let mut clocks = Clocks::new(&time_control);
+
+        assert!(clocks.deduct(Mark::Cross, Duration::from_secs(4)));
+        clocks.add_increment(Mark::Cross);
+        assert_eq!(clocks.remaining(Mark::Cross), Duration::from_secs(7));
+        assert_eq!(clocks.remaining(Mark::Naught), Duration::from_secs(10));
+
+        assert!(!clocks.deduct(Mark::Naught, Duration::from_secs(10)));
+        assert_eq!(clocks.remaining(Mark::Naught), Duration::ZERO);
+    }
+}
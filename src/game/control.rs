@@ -0,0 +1,127 @@
+//! The control of a game from outside the game loop, e.g. from a signal handler or another thread.
+//! The engine checks its `GameControl` before each turn: it waits while the game is paused,
+//! and ends the game when it is interrupted. The current player is never interrupted in the middle of their move.
+
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use super::saved_game::SavedGame;
+
+/// A handle pausing, resuming or interrupting a game, see `TicTacToe::control`.
+/// The handles are cheap to clone and can be sent to other threads.
+#[derive(Clone, Default)]
+pub struct GameControl {
+    shared: Arc<Shared>,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<ControlState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct ControlState {
+    paused: bool,
+    interrupted: bool,
+    /// The game as it was at the start of the current turn.
+    snapshot: Option<SavedGame>,
+}
+
+impl GameControl {
+    /// Creates a new `GameControl`, neither paused nor interrupted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Suspends the game before the next turn, until it is resumed or interrupted.
+    pub fn pause(&self) {
+        self.state().paused = true;
+    }
+
+    /// Lets a paused game go on.
+    pub fn resume(&self) {
+        self.state().paused = false;
+        self.shared.changed.notify_all();
+    }
+
+    /// Ends the game before the next turn, even when it is paused.
+    pub fn interrupt(&self) {
+        self.state().interrupted = true;
+        self.shared.changed.notify_all();
+    }
+
+    /// Returns whether the game is paused.
+    pub fn is_paused(&self) -> bool {
+        self.state().paused
+    }
+
+    /// Returns whether the game was interrupted.
+    pub fn is_interrupted(&self) -> bool {
+        self.state().interrupted
+    }
+
+    /// Returns the game as it was at the start of the current turn,
+    /// or `None` if no game was started, so it can be saved when the game is interrupted.
+    pub fn snapshot(&self) -> Option<SavedGame> {
+        self.state().snapshot.clone()
+    }
+
+    /// Records the game at the start of a turn, waits while the game is paused,
+    /// and returns `false` if the game was interrupted.
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_game` - The game being played.
+    pub(crate) fn start_turn(&self, saved_game: &SavedGame) -> bool {
+        let mut state = self.state();
+        state.snapshot = Some(saved_game.clone());
+        while state.paused && !state.interrupted {
+            state = self
+                .shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        !state.interrupted
+    }
+
+    fn state(&self) -> MutexGuard<'_, ControlState> {
+        // The state stays consistent even if a thread panicked while holding the lock.
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::logic::{GameState, Grid};
+
+    #[test]
+    fn test_pause_resume_and_interrupt() {
+        let control = GameControl::new();
+        let saved_game = SavedGame::new(GameState::new(Grid::new(None), None).unwrap());
+        assert!(control.start_turn(&saved_game));
+        assert_eq!(control.snapshot(), Some(saved_game.clone()));
+
+        control.pause();
+        let resumer = control.clone();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            resumer.resume();
+        });
+        assert!(control.start_turn(&saved_game));
+        assert!(!control.is_paused());
+        handle.join().unwrap();
+
+        control.pause();
+        control.interrupt();
+        assert!(!control.start_turn(&saved_game));
+        assert!(control.is_interrupted());
+    }
+}
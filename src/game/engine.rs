@@ -1,36 +1,132 @@
 //!    The TicTacToe struct represents a game of Tic Tac Toe that can be played by two players
 //!    and rendered with a renderer.
+//!    The players and the renderer can be borrowed, e.g. `&mut player`, or owned, e.g. `Box<dyn Player>`,
+//!    and a game owning them can be stored without any lifetime, as a `TicTacToe<'static>`.
+//!    The players, the renderer, the observers and the error handler are `Send`, or `Sync` when they are shared,
+//!    so such a game can be moved to another thread, e.g. to play it in the background of a frontend.
 
-use crate::logic::errors::Error;
-use crate::logic::{GameState, Grid, Mark};
+use std::time::Instant;
 
-use super::players::Player;
+use crate::logic::errors::{Error, MoveError};
+use crate::logic::{Cell, GameMove, GameState, Grid, Mark, Variant};
+
+use super::clock::{Clocks, TimeControl};
+use super::control::GameControl;
+use super::observers::GameObserver;
+use super::players::{display_name, Player, PlayerCommand};
+use super::record::GameRecord;
 use super::renderers::Renderer;
+use super::saved_game::SavedGame;
+
+type ErrorHandler = dyn Fn(Error) + Send;
+
+/// The outcome of a game played to the end.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GameResult {
+    /// The mark of the winner, `None` for a tie.
+    pub winner: Option<Mark>,
+    /// The game state at the end of the game.
+    pub final_state: GameState,
+    /// The moves of the game, in order.
+    pub moves: Vec<GameMove>,
+    /// How the game ended.
+    pub ending: GameEnding,
+}
+
+/// How a game ended.
+#[derive(Clone, PartialEq, Debug)]
+pub enum GameEnding {
+    /// The game was played until a player won or the grid was full.
+    Finished,
+    /// The player of the mark forfeited after their invalid moves, or resigned, the other player won.
+    Forfeit(Mark),
+    /// The game stopped at the invalid move, without a winner.
+    Aborted(MoveError),
+    /// The game was interrupted with its `GameControl`, without a winner.
+    Interrupted,
+    /// The time of the player of the mark ran out, the other player won.
+    FlagFall(Mark),
+}
+
+/// What the engine does when a player fails to make a valid move, e.g. a buggy AI or a broken external bot.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvalidMovePolicy {
+    /// Asks the player again, up to the number of times in a row, then the player forfeits.
+    Retry(usize),
+    /// The player forfeits the game at their first invalid move.
+    Forfeit,
+    /// The game stops at the first invalid move, without a winner.
+    Abort,
+}
+
+impl InvalidMovePolicy {
+    /// The number of invalid moves in a row a player can make by default,
+    /// so a broken bot forfeits instead of being asked forever.
+    pub const DEFAULT_RETRIES: usize = 3;
+}
 
-type ErrorHandler = dyn Fn(Error);
+impl Default for InvalidMovePolicy {
+    /// Asks the player again, up to `InvalidMovePolicy::DEFAULT_RETRIES` times in a row.
+    fn default() -> Self {
+        InvalidMovePolicy::Retry(Self::DEFAULT_RETRIES)
+    }
+}
 
 /// TicTacToe game struct.
 pub struct TicTacToe<'a> {
-    player1: &'a dyn Player,
-    player2: &'a dyn Player,
-    renderer: &'a dyn Renderer,
+    player1: Box<dyn Player + 'a>,
+    player2: Box<dyn Player + 'a>,
+    renderer: Box<dyn Renderer + 'a>,
     error_handler: Option<Box<ErrorHandler>>,
+    variant: Variant,
+    starting_mark: Option<Mark>,
+    observers: Vec<&'a dyn GameObserver>,
+    last_game: Option<SavedGame>,
+    invalid_move_policy: InvalidMovePolicy,
+    /// The number of invalid moves in a row of the current player.
+    invalid_moves: usize,
+    control: GameControl,
+    time_control: Option<TimeControl>,
+    /// The clocks of the current game, when it is played with a time control.
+    clocks: Option<Clocks>,
+    /// The reason the last move was rejected, until it is rendered.
+    rejected_move: Option<MoveError>,
 }
 
 impl<'a> TicTacToe<'a> {
+    /// Returns a builder configuring a new TicTacToe instance option by option.
+    pub fn builder() -> TicTacToeBuilder<'a> {
+        TicTacToeBuilder::default()
+    }
+
     /// Creates a new TicTacToe instance with two players, a renderer, and an optional error handler.
     /// Returns a Result containing the TicTacToe instance or an error message.
     ///
     /// # Arguments
     ///
-    /// * player1 - The first player.
-    /// * player2 - The second player.
-    /// * renderer - The renderer used to display the game.
+    /// * player1 - The first player, borrowed or owned.
+    /// * player2 - The second player, borrowed or owned.
+    /// * renderer - The renderer used to display the game, borrowed or owned.
     /// * error_handler - An optional error handler function.
     pub fn new(
-        player1: &'a dyn Player,
-        player2: &'a dyn Player,
-        renderer: &'a dyn Renderer,
+        player1: impl Player + 'a,
+        player2: impl Player + 'a,
+        renderer: impl Renderer + 'a,
+        error_handler: Option<Box<ErrorHandler>>,
+    ) -> Result<Self, Error> {
+        Self::from_boxes(
+            Box::new(player1),
+            Box::new(player2),
+            Box::new(renderer),
+            error_handler,
+        )
+    }
+
+    /// Creates a new TicTacToe instance from the boxed players and renderer, see `TicTacToe::new`.
+    fn from_boxes(
+        player1: Box<dyn Player + 'a>,
+        player2: Box<dyn Player + 'a>,
+        renderer: Box<dyn Renderer + 'a>,
         error_handler: Option<Box<ErrorHandler>>,
     ) -> Result<Self, Error> {
         if player1.get_mark() == player2.get_mark() {
@@ -45,34 +141,413 @@ impl<'a> TicTacToe<'a> {
             player2,
             renderer,
             error_handler,
+            variant: Variant::Standard,
+            starting_mark: None,
+            observers: vec![],
+            last_game: None,
+            invalid_move_policy: InvalidMovePolicy::default(),
+            invalid_moves: 0,
+            control: GameControl::new(),
+            time_control: None,
+            clocks: None,
+            rejected_move: None,
         })
     }
 
+    /// Sets the variant of the rules the game is played with.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The variant of the rules, `Variant::Standard` by default.
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Sets what the engine does when a player fails to make a valid move.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The policy, asking the player again without limit by default.
+    pub fn with_invalid_move_policy(mut self, policy: InvalidMovePolicy) -> Self {
+        self.invalid_move_policy = policy;
+        self
+    }
+
+    /// Adds an observer notified of the events of the games.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The observer, called after the observers added before it.
+    pub fn with_observer(mut self, observer: &'a dyn GameObserver) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Sets the time each player has for the whole game, a player whose time runs out loses.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_control` - The time control, the games are not timed by default.
+    pub fn with_time_control(mut self, time_control: TimeControl) -> Self {
+        self.time_control = Some(time_control);
+        self
+    }
+
+    /// Returns the clocks of the last game played, or `None` if it was not played with a time control.
+    pub fn clocks(&self) -> Option<&Clocks> {
+        self.clocks.as_ref()
+    }
+
+    /// Sets the control of the games, shared with the frontend.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The control, a new one by default, see `TicTacToe::control`.
+    pub fn with_control(mut self, control: GameControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Returns a handle pausing, resuming or interrupting the games from another thread,
+    /// e.g. to suspend play while a frontend shows a menu, or to stop it on Ctrl-C.
+    pub fn control(&self) -> GameControl {
+        self.control.clone()
+    }
+
+    /// Returns the mark of the player who goes first when no starting mark is given to `TicTacToe::play`.
+    pub(crate) fn starting_mark(&self) -> Mark {
+        self.starting_mark.unwrap_or(Mark::Cross)
+    }
+
     /// Plays a game of Tic Tac Toe using the current `TicTacToe` instance.
     ///
     /// # Arguments
     ///
-    /// * `starting_mark` - An optional starting mark for the game. If `None`, the starting mark is `Mark::Cross`.
-    pub fn play(&self, starting_mark: Option<Mark>) {
-        let mut game_state = GameState::new(Grid::new(None), starting_mark).unwrap();
+    /// * `starting_mark` - An optional starting mark for the game. If `None`, the starting mark is the one
+    ///   given to the builder, or `Mark::Cross`.
+    pub fn play(&mut self, starting_mark: Option<Mark>) -> GameResult {
+        let starting_mark = starting_mark.or(self.starting_mark);
+        let game_state =
+            GameState::new_with_variant(Grid::new(None), starting_mark, self.variant).unwrap();
+        self.resume(SavedGame::new(game_state))
+    }
+
+    /// Resumes a saved game of Tic Tac Toe from its last move, with the variant it was played with.
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_game` - The game to resume, as loaded by `SavedGame::load`.
+    pub fn resume(&mut self, mut saved_game: SavedGame) -> GameResult {
+        let mut game_state = saved_game.current_state();
+        self.begin_game(&game_state);
 
         loop {
-            self.renderer.render(&game_state);
+            self.render_turn(&saved_game);
 
             if game_state.game_over() {
-                break;
+                return self.end_game(saved_game, GameEnding::Finished);
             }
 
-            let current_player = self.get_current_player(&game_state);
+            if let Some(ending) = self.play_turn(&mut saved_game) {
+                return self.end_game(saved_game, ending);
+            }
+            game_state = saved_game.current_state();
+        }
+    }
+
+    /// Plays a game of Tic Tac Toe, awaiting the moves of the players which are `AsyncPlayer`,
+    /// with the same rules, observers, clocks and control as `TicTacToe::play`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_mark` - An optional starting mark for the game. If `None`, the starting mark is the one
+    ///   given to the builder, or `Mark::Cross`.
+    #[cfg(feature = "async")]
+    pub async fn play_async(&mut self, starting_mark: Option<Mark>) -> GameResult {
+        let starting_mark = starting_mark.or(self.starting_mark);
+        let game_state =
+            GameState::new_with_variant(Grid::new(None), starting_mark, self.variant).unwrap();
+        self.resume_async(SavedGame::new(game_state)).await
+    }
+
+    /// Resumes a saved game of Tic Tac Toe from its last move, awaiting the moves of the players
+    /// which are `AsyncPlayer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_game` - The game to resume, as loaded by `SavedGame::load`.
+    #[cfg(feature = "async")]
+    pub async fn resume_async(&mut self, mut saved_game: SavedGame) -> GameResult {
+        let mut game_state = saved_game.current_state();
+        self.begin_game(&game_state);
+
+        loop {
+            self.render_turn(&saved_game);
 
-            match current_player.make_move(&game_state) {
-                Ok(new_game_state) => game_state = new_game_state.to_owned(),
-                Err(err) => {
-                    if let Some(error_handler) = self.error_handler.as_ref() {
-                        error_handler(Error::MoveError(err));
+            if game_state.game_over() {
+                return self.end_game(saved_game, GameEnding::Finished);
+            }
+
+            if let Some(ending) = self.play_turn_async(&mut saved_game).await {
+                return self.end_game(saved_game, ending);
+            }
+            game_state = saved_game.current_state();
+        }
+    }
+
+    /// Starts a new game and notifies the observers and the renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game state the game starts from.
+    fn begin_game(&mut self, game_state: &GameState) {
+        self.start_game();
+        for observer in &self.observers {
+            observer.on_game_start(game_state);
+        }
+        self.renderer.render_start(game_state);
+    }
+
+    /// Resets the count of invalid moves and the clocks for a new game.
+    fn start_game(&mut self) {
+        self.invalid_moves = 0;
+        self.rejected_move = None;
+        self.clocks = self.time_control.as_ref().map(Clocks::new);
+    }
+
+    /// Renders the current game state, the move leading to it,
+    /// the reason the last move was rejected and the clocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_game` - The game being played.
+    fn render_turn(&mut self, saved_game: &SavedGame) {
+        self.renderer.render_with_players(
+            &saved_game.current_state(),
+            &[&*self.player1, &*self.player2],
+        );
+        if let Some(game_move) = saved_game.moves().last() {
+            self.renderer.render_move(game_move);
+        }
+        if let Some(error) = self.rejected_move.take() {
+            self.renderer.render_invalid(&error);
+        }
+        if let Some(clocks) = &self.clocks {
+            self.renderer.render_clocks(clocks);
+        }
+    }
+
+    /// Ends the game, notifies the observers and keeps it as the last game played.
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_game` - The game being played.
+    /// * `ending` - How the game ended.
+    fn end_game(&mut self, saved_game: SavedGame, ending: GameEnding) -> GameResult {
+        let game_state = saved_game.current_state();
+        let winner = match ending {
+            GameEnding::Finished => game_state.winner_mark(),
+            GameEnding::Forfeit(mark) | GameEnding::FlagFall(mark) => Some(mark.other()),
+            GameEnding::Aborted(_) | GameEnding::Interrupted => None,
+        };
+        let result = GameResult {
+            winner,
+            final_state: game_state,
+            moves: saved_game.moves().to_vec(),
+            ending,
+        };
+        // The invalid move ending the game is rendered before its result.
+        if let Some(error) = self.rejected_move.take() {
+            self.renderer.render_invalid(&error);
+        }
+        self.renderer.render_end(&result);
+        for observer in &self.observers {
+            observer.on_game_over(&result);
+        }
+        self.last_game = Some(saved_game);
+        if !self.observers.is_empty() {
+            if let Some(record) = self.record() {
+                for observer in &self.observers {
+                    observer.on_game_recorded(&record);
+                }
+            }
+        }
+        result
+    }
+
+    /// Plays the game turn by turn, without rendering it, as an iterator over the successive game states.
+    /// The first game state is the empty grid, the last one is the game state at the end of the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_mark` - An optional starting mark for the game. If `None`, the starting mark is the one
+    ///   given to the builder, or `Mark::Cross`.
+    pub fn into_turns(mut self, starting_mark: Option<Mark>) -> Turns<'a> {
+        let starting_mark = starting_mark.or(self.starting_mark);
+        self.start_game();
+        let game_state =
+            GameState::new_with_variant(Grid::new(None), starting_mark, self.variant).unwrap();
+        Turns {
+            game: self,
+            saved_game: SavedGame::new(game_state),
+            started: false,
+            ended: false,
+        }
+    }
+
+    /// Asks the current player for their move, or runs the command they made instead,
+    /// and records the outcome in the saved game.
+    /// Returns how the game ended if it was interrupted or the invalid move policy ends it.
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_game` - The game being played.
+    fn play_turn(&mut self, saved_game: &mut SavedGame) -> Option<GameEnding> {
+        if !self.control.start_turn(saved_game) {
+            return Some(GameEnding::Interrupted);
+        }
+        let game_state = saved_game.current_state();
+        let thinking = Instant::now();
+        let outcome = self.get_current_player(&game_state).make_move(&game_state);
+        self.finish_turn(saved_game, game_state, thinking, outcome)
+    }
+
+    /// Plays a turn as `play_turn` does, awaiting the move of the current player
+    /// if it is an `AsyncPlayer`.
+    #[cfg(feature = "async")]
+    async fn play_turn_async(&mut self, saved_game: &mut SavedGame) -> Option<GameEnding> {
+        if !self.control.start_turn(saved_game) {
+            return Some(GameEnding::Interrupted);
+        }
+        let game_state = saved_game.current_state();
+        let thinking = Instant::now();
+        let current_player = self.get_current_player(&game_state);
+        let outcome = match current_player.as_async_player() {
+            Some(async_player) => async_player.make_move_async(&game_state).await,
+            None => current_player.make_move(&game_state),
+        };
+        self.finish_turn(saved_game, game_state, thinking, outcome)
+    }
+
+    /// Plays the outcome of the move of the current player: deducts its time, saves its move,
+    /// or runs its command or the invalid move policy.
+    /// Returns how the game ended if the turn ended it.
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_game` - The game being played.
+    /// * `game_state` - The game state the current player moved from.
+    /// * `thinking` - When the current player was asked for their move.
+    /// * `outcome` - The game state after the move, or why there is no move.
+    fn finish_turn(
+        &mut self,
+        saved_game: &mut SavedGame,
+        game_state: GameState,
+        thinking: Instant,
+        outcome: Result<GameState, MoveError>,
+    ) -> Option<GameEnding> {
+        let mark = self.get_current_player(&game_state).get_mark();
+        if let Some(clocks) = &mut self.clocks {
+            // A move arriving after the time ran out is not played.
+            if !clocks.deduct(mark, thinking.elapsed()) {
+                return Some(GameEnding::FlagFall(mark));
+            }
+        }
+
+        match outcome {
+            Ok(new_game_state) => {
+                self.invalid_moves = 0;
+                if let Some(clocks) = &mut self.clocks {
+                    clocks.add_increment(mark);
+                }
+                if let Some(game_move) = move_between(&game_state, &new_game_state) {
+                    for observer in &self.observers {
+                        observer.on_move_made(&game_move);
                     }
+                    saved_game.push(game_move);
                 }
             }
+            Err(err) => match self.get_current_player(&game_state).take_command() {
+                Some(PlayerCommand::Save(path)) => {
+                    if let Err(error) = saved_game.save(&path) {
+                        self.handle_error(Error::io(
+                            &format!("cannot save the game to `{}`", path.display()),
+                            &error,
+                        ));
+                    }
+                }
+                Some(PlayerCommand::Undo) => {
+                    // Take back the moves played since the last move of the player, if they moved.
+                    if saved_game
+                        .moves()
+                        .iter()
+                        .any(|game_move| *game_move.mark() == mark)
+                    {
+                        while let Some(game_move) = saved_game.undo() {
+                            if *game_move.mark() == mark {
+                                break;
+                            }
+                        }
+                    }
+                }
+                Some(PlayerCommand::Resign) => return Some(GameEnding::Forfeit(mark)),
+                Some(PlayerCommand::Redo) => {
+                    // Play the moves again until it is the turn of the player.
+                    while saved_game.redo().is_some() {
+                        if saved_game.current_state().current_mark() == mark {
+                            break;
+                        }
+                    }
+                }
+                None => {
+                    for observer in &self.observers {
+                        observer.on_invalid_move(mark, &err);
+                    }
+                    self.handle_error(Error::MoveError(err.clone()));
+                    self.rejected_move = Some(err.clone());
+                    self.invalid_moves += 1;
+                    return match self.invalid_move_policy {
+                        InvalidMovePolicy::Retry(times) if self.invalid_moves <= times => None,
+                        InvalidMovePolicy::Retry(_) | InvalidMovePolicy::Forfeit => {
+                            Some(GameEnding::Forfeit(mark))
+                        }
+                        InvalidMovePolicy::Abort => Some(GameEnding::Aborted(err)),
+                    };
+                }
+            },
+        }
+        None
+    }
+
+    /// Returns the moves of the last game played, in order,
+    /// including the moves played before the game was saved when it was resumed.
+    pub fn history(&self) -> &[GameMove] {
+        self.last_game
+            .as_ref()
+            .map_or(&[], |last_game| last_game.moves())
+    }
+
+    /// Returns the record of the last game played, or `None` if no game was played.
+    pub fn record(&self) -> Option<GameRecord> {
+        let last_game = self.last_game.as_ref()?;
+        let (cross, naught) = if self.player1.get_mark() == Mark::Cross {
+            (&*self.player1, &*self.player2)
+        } else {
+            (&*self.player2, &*self.player1)
+        };
+        Some(GameRecord::new(
+            &display_name(cross),
+            &display_name(naught),
+            *last_game.initial_state(),
+            last_game.moves(),
+        ))
+    }
+
+    /// Pass the error to the error handler, if there is one.
+    fn handle_error(&self, error: Error) {
+        if let Some(error_handler) = self.error_handler.as_ref() {
+            error_handler(error);
         }
     }
 
@@ -81,11 +556,513 @@ impl<'a> TicTacToe<'a> {
     /// # Arguments
     ///
     /// * `game_state` - The current game state.
-    fn get_current_player(&self, game_state: &GameState) -> &'a dyn Player {
+    fn get_current_player(&mut self, game_state: &GameState) -> &mut (dyn Player + 'a) {
         if game_state.current_mark() == self.player1.get_mark() {
-            self.player1
+            self.player1.as_mut()
         } else {
-            self.player2
+            self.player2.as_mut()
+        }
+    }
+}
+
+/// A builder of TicTacToe instances, see `TicTacToe::builder`.
+/// The players and the renderer are required, the other options have the defaults of `TicTacToe::new`.
+#[derive(Default)]
+pub struct TicTacToeBuilder<'a> {
+    player_x: Option<Box<dyn Player + 'a>>,
+    player_o: Option<Box<dyn Player + 'a>>,
+    renderer: Option<Box<dyn Renderer + 'a>>,
+    error_handler: Option<Box<ErrorHandler>>,
+    variant: Variant,
+    starting_mark: Option<Mark>,
+    observers: Vec<&'a dyn GameObserver>,
+    invalid_move_policy: InvalidMovePolicy,
+    control: GameControl,
+    time_control: Option<TimeControl>,
+}
+
+impl<'a> TicTacToeBuilder<'a> {
+    /// Sets the player of the crosses.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - The player, with the mark `Mark::Cross`, borrowed or owned.
+    pub fn player_x(mut self, player: impl Player + 'a) -> Self {
+        self.player_x = Some(Box::new(player));
+        self
+    }
+
+    /// Sets the player of the naughts.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - The player, with the mark `Mark::Naught`, borrowed or owned.
+    pub fn player_o(mut self, player: impl Player + 'a) -> Self {
+        self.player_o = Some(Box::new(player));
+        self
+    }
+
+    /// Sets the renderer used to display the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The renderer, borrowed or owned.
+    pub fn renderer(mut self, renderer: impl Renderer + 'a) -> Self {
+        self.renderer = Some(Box::new(renderer));
+        self
+    }
+
+    /// Sets the function called with the errors of the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_handler` - The error handler.
+    pub fn error_handler(mut self, error_handler: Box<ErrorHandler>) -> Self {
+        self.error_handler = Some(error_handler);
+        self
+    }
+
+    /// Sets the variant of the rules the game is played with.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The variant of the rules, `Variant::Standard` by default.
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Sets the mark of the player who goes first.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_mark` - The starting mark, `Mark::Cross` by default.
+    pub fn starting_mark(mut self, starting_mark: Mark) -> Self {
+        self.starting_mark = Some(starting_mark);
+        self
+    }
+
+    /// Sets what the engine does when a player fails to make a valid move.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The policy, asking the player again without limit by default.
+    pub fn invalid_move_policy(mut self, policy: InvalidMovePolicy) -> Self {
+        self.invalid_move_policy = policy;
+        self
+    }
+
+    /// Sets the time each player has for the whole game.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_control` - The time control, the games are not timed by default.
+    pub fn time_control(mut self, time_control: TimeControl) -> Self {
+        self.time_control = Some(time_control);
+        self
+    }
+
+    /// Sets the control pausing, resuming or interrupting the games.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The control, shared with the frontend.
+    pub fn control(mut self, control: GameControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Adds an observer notified of the events of the games.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The observer, called after the observers added before it.
+    pub fn observer(mut self, observer: &'a dyn GameObserver) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Creates the TicTacToe instance.
+    /// Returns an error if a player or the renderer is missing, or if a player has the wrong mark.
+    pub fn build(self) -> Result<TicTacToe<'a>, Error> {
+        let missing = |option: &str| Error::ConfigError(format!("The {} is missing", option));
+        let player_x = self.player_x.ok_or_else(|| missing("player X"))?;
+        let player_o = self.player_o.ok_or_else(|| missing("player O"))?;
+        let renderer = self.renderer.ok_or_else(|| missing("renderer"))?;
+        for (player, mark) in [(&player_x, Mark::Cross), (&player_o, Mark::Naught)] {
+            if player.get_mark() != mark {
+                return Err(Error::ConfigError(format!(
+                    "The player {} has the mark {}",
+                    mark,
+                    player.get_mark()
+                )));
+            }
+        }
+
+        let mut game = TicTacToe::from_boxes(player_x, player_o, renderer, self.error_handler)?
+            .with_variant(self.variant)
+            .with_invalid_move_policy(self.invalid_move_policy)
+            .with_control(self.control);
+        game.time_control = self.time_control;
+        game.starting_mark = self.starting_mark;
+        game.observers = self.observers;
+        Ok(game)
+    }
+}
+
+/// The successive game states of a game, see `TicTacToe::into_turns`.
+pub struct Turns<'a> {
+    game: TicTacToe<'a>,
+    saved_game: SavedGame,
+    started: bool,
+    /// Whether the invalid move policy ended the game.
+    ended: bool,
+}
+
+impl Iterator for Turns<'_> {
+    type Item = GameState;
+
+    fn next(&mut self) -> Option<GameState> {
+        let game_state = self.saved_game.current_state();
+        if !self.started {
+            self.started = true;
+            return Some(game_state);
+        }
+        // The turns where the game state does not change, such as invalid moves, are skipped.
+        while !game_state.game_over() && !self.ended {
+            self.ended = self.game.play_turn(&mut self.saved_game).is_some();
+            let new_game_state = self.saved_game.current_state();
+            if new_game_state != game_state {
+                return Some(new_game_state);
+            }
+        }
+        None
+    }
+}
+
+/// Returns the move leading from one game state to the next, found from the cell which changed.
+///
+/// # Arguments
+///
+/// * `before` - The game state before the move.
+/// * `after` - The game state after the move.
+fn move_between(before: &GameState, after: &GameState) -> Option<GameMove> {
+    let cells = before.grid().cells().into_iter().zip(after.grid().cells());
+    let (cell_index, (_, placed_cell)) = cells
+        .enumerate()
+        .find(|(_, (before_cell, after_cell))| before_cell != after_cell)?;
+    let placed_mark = [Mark::Cross, Mark::Naught]
+        .into_iter()
+        .find(|mark| placed_cell == Cell::new_marked(*mark))?;
+    before.make_move_with(cell_index, placed_mark).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::game::{renderers::NoRenderer, ScriptedPlayer};
+
+    #[test]
+    fn test_builder() {
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4, 5]);
+        let result = TicTacToe::builder()
+            .player_x(&mut cross)
+            .player_o(&mut naught)
+            .renderer(&NoRenderer)
+            .starting_mark(Mark::Naught)
+            .build()
+            .unwrap()
+            .play(None);
+
+        assert_eq!(result.winner, Some(Mark::Naught));
+        assert_eq!(result.final_state.to_notation(), "XX./OOO/... X");
+    }
+
+    #[test]
+    fn test_game_moves_to_another_thread() {
+        let game: TicTacToe<'static> = TicTacToe::builder()
+            .player_x(Box::new(ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2])) as Box<dyn Player>)
+            .player_o(ScriptedPlayer::new(Mark::Naught, vec![3, 4]))
+            .renderer(NoRenderer)
+            .error_handler(Box::new(|_error: Error| {}))
+            .build()
+            .unwrap();
+        let result = thread::spawn(move || {
+            let mut game = game;
+            game.play(None)
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(result.winner, Some(Mark::Cross));
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![]);
+        let mut other_cross = ScriptedPlayer::new(Mark::Cross, vec![]);
+        let missing_renderer = TicTacToe::builder()
+            .player_x(&mut cross)
+            .player_o(&mut other_cross)
+            .build();
+        assert!(matches!(missing_renderer, Err(Error::ConfigError(_))));
+
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![]);
+        let mut other_cross = ScriptedPlayer::new(Mark::Cross, vec![]);
+        let wrong_mark = TicTacToe::builder()
+            .player_x(&mut cross)
+            .player_o(&mut other_cross)
+            .renderer(&NoRenderer)
+            .build();
+        assert!(matches!(wrong_mark, Err(Error::ConfigError(_))));
+    }
+
+    /// A broken bot which never finds a move, counting the times it was asked.
+    struct BrokenPlayer {
+        mark: Mark,
+        asked: usize,
+    }
+
+    impl Player for BrokenPlayer {
+        fn get_move(&mut self, _game_state: &GameState) -> Option<GameMove> {
+            self.asked += 1;
+            None
+        }
+
+        fn get_mark(&self) -> Mark {
+            self.mark
+        }
+    }
+
+    #[test]
+    fn test_invalid_move_policy() {
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![4]);
+        let mut naught = BrokenPlayer {
+            mark: Mark::Naught,
+            asked: 0,
+        };
+        let result = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .with_invalid_move_policy(InvalidMovePolicy::Retry(2))
+            .play(None);
+        assert_eq!(result.ending, GameEnding::Forfeit(Mark::Naught));
+        assert_eq!(result.winner, Some(Mark::Cross));
+        assert_eq!(result.moves.len(), 1);
+        assert_eq!(naught.asked, 3);
+
+        let mut cross = BrokenPlayer {
+            mark: Mark::Cross,
+            asked: 0,
+        };
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![]);
+        let result = TicTacToe::builder()
+            .player_x(&mut cross)
+            .player_o(&mut naught)
+            .renderer(&NoRenderer)
+            .invalid_move_policy(InvalidMovePolicy::Abort)
+            .build()
+            .unwrap()
+            .play(None);
+        assert_eq!(
+            result.ending,
+            GameEnding::Aborted(MoveError::NoPossibleMoves)
+        );
+        assert_eq!(result.winner, None);
+        assert_eq!(cross.asked, 1);
+    }
+
+    /// A renderer writing down what it is asked to render.
+    struct EventRenderer {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Renderer for EventRenderer {
+        fn render(&self, game_state: &GameState) {
+            self.events.lock().unwrap().push(game_state.to_notation());
+        }
+        fn render_start(&self, _game_state: &GameState) {
+            self.events.lock().unwrap().push("start".to_string());
         }
+        fn render_move(&self, game_move: &GameMove) {
+            self.events.lock().unwrap().push(game_move.to_string());
+        }
+        fn render_invalid(&self, error: &MoveError) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("invalid: {}", error));
+        }
+        fn render_end(&self, result: &GameResult) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("end: {:?}", result.ending));
+        }
+    }
+
+    #[test]
+    fn test_renderer_lifecycle() {
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![4]);
+        let mut naught = BrokenPlayer {
+            mark: Mark::Naught,
+            asked: 0,
+        };
+        let renderer = EventRenderer {
+            events: Mutex::new(vec![]),
+        };
+        TicTacToe::new(&mut cross, &mut naught, &renderer, None)
+            .unwrap()
+            .with_invalid_move_policy(InvalidMovePolicy::Retry(1))
+            .play(None);
+        let invalid = format!("invalid: {}", MoveError::NoPossibleMoves);
+        assert_eq!(
+            renderer.events.into_inner().unwrap(),
+            vec![
+                "start",
+                ".../.../... X",
+                ".../.X./... O",
+                "X -> B2",
+                ".../.X./... O",
+                "X -> B2",
+                &invalid,
+                &invalid,
+                "end: Forfeit(Naught)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_into_turns_ends_on_forfeit() {
+        let mut cross = BrokenPlayer {
+            mark: Mark::Cross,
+            asked: 0,
+        };
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![]);
+        let turns = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .with_invalid_move_policy(InvalidMovePolicy::Forfeit)
+            .into_turns(None)
+            .count();
+        assert_eq!(turns, 1);
+    }
+
+    /// A scripted player which thinks for a while before each move.
+    struct SlowPlayer {
+        scripted: ScriptedPlayer,
+    }
+
+    impl Player for SlowPlayer {
+        fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+            thread::sleep(Duration::from_millis(30));
+            self.scripted.get_move(game_state)
+        }
+
+        fn get_mark(&self) -> Mark {
+            self.scripted.get_mark()
+        }
+    }
+
+    #[test]
+    fn test_flag_fall() {
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1]);
+        let mut naught = SlowPlayer {
+            scripted: ScriptedPlayer::new(Mark::Naught, vec![3, 4]),
+        };
+        let time_control =
+            TimeControl::new(Duration::from_millis(50)).with_increment(Duration::from_millis(5));
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .with_time_control(time_control);
+        let result = game.play(None);
+
+        assert_eq!(result.ending, GameEnding::FlagFall(Mark::Naught));
+        assert_eq!(result.winner, Some(Mark::Cross));
+        // The late second move of the naughts is not played.
+        assert_eq!(result.moves.len(), 3);
+        let clocks = game.clocks().unwrap();
+        assert_eq!(clocks.remaining(Mark::Naught), Duration::ZERO);
+        assert!(clocks.remaining(Mark::Cross) > Duration::from_millis(50));
+    }
+
+    /// An observer interrupting the game after the first move.
+    struct Interrupter {
+        control: GameControl,
+    }
+
+    impl GameObserver for Interrupter {
+        fn on_move_made(&self, _game_move: &GameMove) {
+            self.control.interrupt();
+        }
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let control = GameControl::new();
+        let interrupter = Interrupter {
+            control: control.clone(),
+        };
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .with_control(control)
+            .with_observer(&interrupter);
+        let result = game.play(None);
+
+        assert_eq!(result.ending, GameEnding::Interrupted);
+        assert_eq!(result.winner, None);
+        assert_eq!(result.moves.len(), 1);
+        assert_eq!(game.control().snapshot().unwrap().moves(), result.moves);
+    }
+
+    /// A game stored in a struct, owning its players and renderer.
+    struct Table {
+        game: TicTacToe<'static>,
+    }
+
+    #[test]
+    fn test_owned_players() {
+        let cross: Box<dyn Player> = Box::new(ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]));
+        let naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        // The players are sent to the thread, which builds and plays the game.
+        let result = thread::spawn(move || {
+            let mut table = Table {
+                game: TicTacToe::new(cross, naught, Box::new(NoRenderer), None).unwrap(),
+            };
+            table.game.play(None)
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(result.winner, Some(Mark::Cross));
+        assert_eq!(result.moves.len(), 5);
+    }
+
+    #[test]
+    fn test_into_turns() {
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        let notations: Vec<String> = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .into_turns(None)
+            .map(|game_state| game_state.to_notation())
+            .collect();
+
+        assert_eq!(
+            notations,
+            vec![
+                ".../.../... X",
+                "X../.../... O",
+                "X../O../... X",
+                "XX./O../... O",
+                "XX./OO./... X",
+                "XXX/OO./... O",
+            ]
+        );
     }
 }
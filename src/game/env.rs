@@ -0,0 +1,221 @@
+//! An environment with the reset and step interface of reinforcement learning frameworks,
+//! in the style of OpenAI Gym, so external agents can learn to play against a `Player`.
+//! The agent chooses the index of a cell, the opponent answers right away,
+//! and the agent receives the new observation with the reward of its move.
+//! When the variant lets a player place either mark, the agent always places its own.
+
+use crate::logic::errors::{Error, MoveError};
+use crate::logic::{Cell, GameState, Grid, Mark, Variant};
+
+use super::players::Player;
+
+/// The grid seen by the agent: 1 for the cells of the agent's mark,
+/// -1 for the cells of the other mark and 0 for the empty cells.
+pub type Observation = [i8; Grid::SIZE];
+
+/// The outcome of a step of the environment.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Step {
+    /// The grid after the move of the agent and the answer of the opponent.
+    pub observation: Observation,
+    /// 1 if the agent won, -1 if it lost, 0 otherwise.
+    pub reward: f64,
+    /// Whether the game is over and the environment must be reset.
+    pub done: bool,
+}
+
+/// A game against an opponent, played one step at a time.
+pub struct TicTacToeEnv {
+    agent_mark: Mark,
+    opponent: Box<dyn Player>,
+    variant: Variant,
+    starting_mark: Mark,
+    game_state: GameState,
+}
+
+impl TicTacToeEnv {
+    /// Creates a new environment where the agent plays against the opponent.
+    /// The game starts with `Mark::Cross`, so the opponent moves first when the agent plays naughts.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_mark` - The mark of the agent.
+    /// * `opponent` - The player answering the moves of the agent.
+    /// * `variant` - The variant of the rules the games are played with.
+    pub fn new(agent_mark: Mark, opponent: Box<dyn Player>, variant: Variant) -> Self {
+        let game_state = GameState::new_with_variant(Grid::new(None), None, variant).unwrap();
+        TicTacToeEnv {
+            agent_mark,
+            opponent,
+            variant,
+            starting_mark: Mark::Cross,
+            game_state,
+        }
+    }
+
+    /// Sets the mark which starts the games, from the next reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_mark` - The mark of the player who goes first.
+    pub fn with_starting_mark(mut self, starting_mark: Mark) -> Self {
+        self.starting_mark = starting_mark;
+        self
+    }
+
+    /// Starts a new game and returns its first observation,
+    /// after the first move of the opponent if it starts.
+    /// Returns an error if the opponent has no move to play.
+    pub fn reset(&mut self) -> Result<Observation, Error> {
+        self.game_state =
+            GameState::new_with_variant(Grid::new(None), Some(self.starting_mark), self.variant)
+                .unwrap();
+        if self.game_state.current_mark() != self.agent_mark {
+            self.play_opponent()?;
+        }
+        Ok(self.observation())
+    }
+
+    /// Plays the move of the agent on the cell, then the answer of the opponent.
+    /// Returns an error, and leaves the game unchanged, if the move is not possible.
+    /// Returns an error as well if the opponent has no move to answer,
+    /// the game then waits for the opponent and the environment must be reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The index of the cell the agent marks.
+    pub fn step(&mut self, action: usize) -> Result<Step, Error> {
+        let game_move = self.game_state.make_move_to(action)?;
+        self.game_state = *game_move.after_state();
+        if !self.game_state.game_over() {
+            self.play_opponent()?;
+        }
+
+        let done = self.game_state.game_over();
+        let reward = if done {
+            f64::from(self.game_state.score(self.agent_mark).unwrap())
+        } else {
+            0.0
+        };
+        Ok(Step {
+            observation: self.observation(),
+            reward,
+            done,
+        })
+    }
+
+    /// Returns the indexes of the cells the agent can mark.
+    pub fn legal_actions(&self) -> Vec<usize> {
+        let mut actions: Vec<usize> = self
+            .game_state
+            .possible_moves()
+            .iter()
+            .map(|game_move| game_move.cell_index())
+            .collect();
+        actions.dedup();
+        actions
+    }
+
+    /// Returns the current game state.
+    pub fn game_state(&self) -> &GameState {
+        &self.game_state
+    }
+
+    /// Plays the move of the opponent, or returns an error if it has none.
+    fn play_opponent(&mut self) -> Result<(), Error> {
+        let game_move = self
+            .opponent
+            .get_move(&self.game_state)
+            .ok_or(MoveError::NoPossibleMoves)?;
+        self.game_state = *game_move.after_state();
+        Ok(())
+    }
+
+    fn observation(&self) -> Observation {
+        let mut observation = [0; Grid::SIZE];
+        for (value, cell) in observation.iter_mut().zip(self.game_state.grid().cells()) {
+            if cell == Cell::new_marked(self.agent_mark) {
+                *value = 1;
+            } else if cell != Cell::new_empty() {
+                *value = -1;
+            }
+        }
+        observation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{HeuristicPlayer, ScriptedPlayer};
+
+    #[test]
+    fn test_reset_opponent_starts() {
+        let mut env = TicTacToeEnv::new(
+            Mark::Naught,
+            Box::new(HeuristicPlayer::new(Mark::Cross)),
+            Variant::Standard,
+        );
+        assert_eq!(env.reset().unwrap(), [0, 0, 0, 0, -1, 0, 0, 0, 0]);
+        assert_eq!(env.legal_actions(), vec![0, 1, 2, 3, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_step_until_done() {
+        let mut env = TicTacToeEnv::new(
+            Mark::Cross,
+            Box::new(HeuristicPlayer::new(Mark::Naught)),
+            Variant::Standard,
+        );
+        env.reset().unwrap();
+        let step = env.step(0).unwrap();
+        assert_eq!(step.observation, [1, 0, 0, 0, -1, 0, 0, 0, 0]);
+        assert_eq!(step.reward, 0.0);
+        assert!(!step.done);
+
+        let mut step = step;
+        while !step.done {
+            let action = env.legal_actions()[0];
+            step = env.step(action).unwrap();
+        }
+        assert!(env.game_state().game_over());
+        assert!(step.reward <= 0.0);
+    }
+
+    #[test]
+    fn test_step_illegal_action() {
+        let mut env = TicTacToeEnv::new(
+            Mark::Naught,
+            Box::new(HeuristicPlayer::new(Mark::Cross)),
+            Variant::Standard,
+        );
+        let observation = env.reset().unwrap();
+        assert!(env.step(4).is_err());
+        assert!(env.step(9).is_err());
+        assert_eq!(env.step(0).unwrap().observation[0], 1);
+        assert_ne!(observation, env.observation());
+    }
+
+    #[test]
+    fn test_opponent_without_a_move() {
+        let mut env = TicTacToeEnv::new(
+            Mark::Cross,
+            Box::new(ScriptedPlayer::new(Mark::Naught, vec![4])),
+            Variant::Standard,
+        );
+        env.reset().unwrap();
+        assert!(!env.step(0).unwrap().done);
+        assert_eq!(
+            env.step(1),
+            Err(Error::MoveError(MoveError::NoPossibleMoves))
+        );
+        assert_eq!(env.game_state().current_mark(), Mark::Naught);
+
+        let mut env = TicTacToeEnv::new(
+            Mark::Naught,
+            Box::new(ScriptedPlayer::new(Mark::Cross, vec![])),
+            Variant::Standard,
+        );
+        assert!(env.reset().is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! A game driven one intent at a time, for the frame loop of immediate-mode user interfaces.
+//! The `SyncGameFacade` never blocks and never panics: every method returns right away,
+//! and the invalid intents of the user are returned as errors, leaving the game unchanged.
+//!
+//! Latency bounds, on a desktop machine:
+//! - `apply`, `undo`, `status` and `state` check at most the 8 lines of the grid, well under a microsecond.
+//! - `clone` copies at most 10 game states, without allocating.
+//! - `hint` looks the position up in the `PolicyTable` of the variant, in constant time,
+//!   except for the first hint of each variant which solves the table, in a few tens of milliseconds.
+
+use crate::logic::errors::Error;
+use crate::logic::{GameMove, GameState, Grid, Mark, Variant};
+
+use super::analysis;
+
+/// What the user asked the game to do, e.g. with a click on the grid.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Intent {
+    /// Place the mark of the current player on the cell.
+    Place(usize),
+    /// Place the given mark on the cell, as allowed by the `Variant::Wild` rules.
+    PlaceMark(usize, Mark),
+    /// Drop the mark of the current player in the column, as in the `Variant::Gravity` rules.
+    Drop(usize),
+}
+
+/// Where the game stands.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameStatus {
+    /// The game goes on, it is the turn of the mark.
+    InProgress(Mark),
+    /// The game is over, the mark won.
+    Won(Mark),
+    /// The game is over without a winner.
+    Tie,
+}
+
+/// A game and the states it went through, so the moves can be taken back.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncGameFacade {
+    /// The states of the game, from the first one, only the first `len` are played.
+    states: [GameState; Grid::SIZE + 1],
+    len: usize,
+}
+
+impl SyncGameFacade {
+    /// Creates a new game on an empty grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The variant of the rules the game is played with.
+    /// * `starting_mark` - The mark of the player who goes first, `Mark::Cross` if `None`.
+    pub fn new(variant: Variant, starting_mark: Option<Mark>) -> Self {
+        let game_state =
+            GameState::new_with_variant(Grid::new(None), starting_mark, variant).unwrap();
+        Self::from_state(game_state)
+    }
+
+    /// Creates a new game starting from the game state, which cannot be undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game state the game starts from.
+    pub fn from_state(game_state: GameState) -> Self {
+        SyncGameFacade {
+            states: [game_state; Grid::SIZE + 1],
+            len: 1,
+        }
+    }
+
+    /// Returns the current game state.
+    pub fn state(&self) -> &GameState {
+        &self.states[self.len - 1]
+    }
+
+    /// Plays the intent of the current player and returns the move made.
+    /// Returns an error, and leaves the game unchanged, if the move is not possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `intent` - What the current player asked for.
+    pub fn apply(&mut self, intent: Intent) -> Result<GameMove, Error> {
+        let game_state = self.state();
+        let game_move = match intent {
+            Intent::Place(cell_index) => game_state.make_move_to(cell_index),
+            Intent::PlaceMark(cell_index, mark) => game_state.make_move_with(cell_index, mark),
+            Intent::Drop(column) => game_state.make_move_to_column(column),
+        }?;
+        // A move marks an empty cell, so the grid fills up before the states run out.
+        self.states[self.len] = *game_move.after_state();
+        self.len += 1;
+        Ok(game_move)
+    }
+
+    /// Takes back the last move and returns `true`,
+    /// or returns `false` if no move was played since the first game state.
+    pub fn undo(&mut self) -> bool {
+        if self.len == 1 {
+            return false;
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Returns a move of perfect play for the current player,
+    /// or `None` if the game is over.
+    pub fn hint(&self) -> Option<GameMove> {
+        analysis::best_move(self.state())
+    }
+
+    /// Returns where the game stands.
+    pub fn status(&self) -> GameStatus {
+        let game_state = self.state();
+        if let Some(winner) = game_state.winner_mark() {
+            GameStatus::Won(winner)
+        } else if game_state.tie() {
+            GameStatus::Tie
+        } else {
+            GameStatus::InProgress(game_state.current_mark())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A user interface which clicks on the cells in order, asking for a hint and
+    /// taking it back first when `undo_first_hint` is set, then renders the frame.
+    fn run_frames(game: &mut SyncGameFacade, clicks: &[usize], undo_first_hint: bool) {
+        let mut clicks = clicks.iter();
+        let mut asked_hint = false;
+        while let GameStatus::InProgress(_) = game.status() {
+            if undo_first_hint && !asked_hint {
+                asked_hint = true;
+                let hint = game.hint().unwrap();
+                game.apply(Intent::Place(hint.cell_index())).unwrap();
+                assert!(game.undo());
+                continue;
+            }
+            match clicks.next() {
+                // A click on an occupied cell is ignored, as a real interface would.
+                Some(cell_index) => {
+                    let _ = game.apply(Intent::Place(*cell_index));
+                }
+                None => break,
+            }
+            // The frame copies the game, as immediate-mode interfaces do.
+            let frame = *game;
+            assert_eq!(frame.state(), game.state());
+        }
+    }
+
+    #[test]
+    fn test_ui_loop_plays_until_the_win() {
+        let mut game = SyncGameFacade::new(Variant::Standard, None);
+        run_frames(&mut game, &[0, 3, 3, 1, 4, 2], true);
+        assert_eq!(game.status(), GameStatus::Won(Mark::Cross));
+        assert!(game.apply(Intent::Place(8)).is_err());
+        assert_eq!(game.hint(), None);
+    }
+
+    #[test]
+    fn test_invalid_intents_leave_the_game_unchanged() {
+        let mut game = SyncGameFacade::new(Variant::Gravity, Some(Mark::Naught));
+        let before = game;
+        assert!(game.apply(Intent::Place(0)).is_err());
+        assert!(game.apply(Intent::Place(42)).is_err());
+        assert!(game.apply(Intent::Drop(7)).is_err());
+        assert!(game.apply(Intent::PlaceMark(8, Mark::Cross)).is_err());
+        assert_eq!(game, before);
+        assert!(!game.undo());
+
+        let game_move = game.apply(Intent::Drop(1)).unwrap();
+        assert_eq!(game_move.cell_index(), 7);
+        assert_eq!(game.status(), GameStatus::InProgress(Mark::Cross));
+    }
+
+    #[test]
+    fn test_undo_every_move() {
+        let mut game = SyncGameFacade::new(Variant::Standard, None);
+        let first_state = *game.state();
+        while let Some(hint) = game.hint() {
+            game.apply(Intent::Place(hint.cell_index())).unwrap();
+        }
+        assert_eq!(game.status(), GameStatus::Tie);
+        let mut undone = 0;
+        while game.undo() {
+            undone += 1;
+        }
+        assert_eq!(undone, Grid::SIZE);
+        assert_eq!(*game.state(), first_state);
+    }
+}
@@ -0,0 +1,104 @@
+//! An adapter to the `Game` trait of the `minimax` crate, behind the `minimax` feature,
+//! so the search strategies of that crate (negamax, iterative deepening, parallel search...)
+//! can play tic-tac-toe without any glue.
+//! The states are `GameState`s and the moves are `GameMove`s, in every variant of the rules.
+//!
+//! ```ignore
+//! use minimax::{Negamax, Strategy};
+//!
+//! let mut strategy = Negamax::new(NeutralEvaluator, 9);
+//! let next_move = strategy.choose_move(&game_state);
+//! ```
+
+use minimax::{Evaluation, Evaluator, Game, Winner};
+
+use crate::logic::{GameMove, GameState};
+
+/// The rules of tic-tac-toe, seen by the `minimax` crate.
+pub struct TicTacToeGame;
+
+impl Game for TicTacToeGame {
+    type S = GameState;
+    type M = GameMove;
+
+    fn generate_moves(state: &GameState, moves: &mut Vec<GameMove>) {
+        moves.extend(state.possible_moves());
+    }
+
+    /// Returns the winner seen from the player whose turn it is,
+    /// as the winner of the `Variant::Notakto` rules is the player to move.
+    fn get_winner(state: &GameState) -> Option<Winner> {
+        match state.winner_mark() {
+            Some(mark) if mark == state.current_mark() => Some(Winner::PlayerToMove),
+            Some(_) => Some(Winner::PlayerJustMoved),
+            None if state.game_over() => Some(Winner::Draw),
+            None => None,
+        }
+    }
+
+    fn zobrist_hash(state: &GameState) -> u64 {
+        u64::from(state.position_key())
+    }
+
+    fn apply(_state: &mut GameState, game_move: GameMove) -> Option<GameState> {
+        Some(*game_move.after_state())
+    }
+
+    fn notation(_state: &GameState, game_move: GameMove) -> Option<String> {
+        Some(game_move.cell_index().to_string())
+    }
+}
+
+/// An evaluator which scores every unfinished position as even,
+/// the grid is small enough for the strategies to search the games to their end.
+pub struct NeutralEvaluator;
+
+impl Evaluator for NeutralEvaluator {
+    type G = TicTacToeGame;
+
+    fn evaluate(&self, _state: &GameState) -> Evaluation {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use minimax::{Negamax, Strategy};
+
+    use super::*;
+    use crate::logic::{Grid, Mark};
+
+    #[test]
+    fn test_generate_moves() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut moves = vec![];
+        TicTacToeGame::generate_moves(&game_state, &mut moves);
+        assert_eq!(moves, game_state.possible_moves());
+        assert_eq!(TicTacToeGame::get_winner(&game_state), None);
+    }
+
+    #[test]
+    fn test_get_winner() {
+        let game_state = GameState::from_notation("XXX/OO./... O").unwrap();
+        assert_eq!(
+            TicTacToeGame::get_winner(&game_state),
+            Some(Winner::PlayerJustMoved)
+        );
+        let game_state = GameState::from_notation("XOX/XOO/OXX O").unwrap();
+        assert_eq!(TicTacToeGame::get_winner(&game_state), Some(Winner::Draw));
+        let game_state = GameState::from_notation("XXX/.../... O notakto").unwrap();
+        assert_eq!(
+            TicTacToeGame::get_winner(&game_state),
+            Some(Winner::PlayerToMove)
+        );
+    }
+
+    #[test]
+    fn test_negamax_takes_the_win() {
+        let game_state = GameState::from_notation("XX./OO./... X").unwrap();
+        let mut strategy = Negamax::new(NeutralEvaluator, 9);
+        let next_move = strategy.choose_move(&game_state).unwrap();
+        assert_eq!(next_move.cell_index(), 2);
+        assert_eq!(*next_move.mark(), Mark::Cross);
+    }
+}
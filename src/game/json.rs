@@ -0,0 +1,35 @@
+//! The writing of JSON strings, shared by the files and the frontends which write JSON by hand.
+
+/// Escapes the characters which are not allowed in a JSON string, without the quotes.
+///
+/// # Arguments
+///
+/// * `value` - The text to write in the JSON string.
+pub(crate) fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(
+            escape_json("Cell `4` \"x\"\\\n\u{1}"),
+            "Cell `4` \\\"x\\\"\\\\\\n\\u0001"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! The leaderboard of the players of the games stored in the history.
+//! The players are told apart by their name, so the same name always plays for the same entry,
+//! whatever the mark it played with. Each entry gets an Elo rating, updated after each game
+//! in the order the games were stored, like in a tournament.
+
+use std::collections::HashMap;
+use std::io;
+
+use crate::logic::Mark;
+
+use super::storage::{GameStore, StoredGame};
+use super::tournament::{record_game, Standing, INITIAL_RATING};
+
+/// The players of the stored games, ranked by win rate and rating.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Leaderboard {
+    standings: Vec<Standing>,
+}
+
+impl Leaderboard {
+    /// Creates the leaderboard of the decided games, in the order they were played,
+    /// including the games won by forfeit or on time.
+    /// The games aborted or interrupted are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `games` - The stored games.
+    pub fn new(games: &[StoredGame]) -> Self {
+        let mut standings = vec![];
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        for game in games {
+            if !game.decided() {
+                continue;
+            }
+            let [cross, naught] = [Mark::Cross, Mark::Naught].map(|mark| {
+                let name = game.record.player(mark);
+                *indices.entry(name.to_string()).or_insert_with(|| {
+                    standings.push(Standing {
+                        name: name.to_string(),
+                        wins: 0,
+                        ties: 0,
+                        losses: 0,
+                        rating: INITIAL_RATING,
+                    });
+                    standings.len() - 1
+                })
+            });
+            // A player playing against themselves does not change their standing.
+            if cross != naught {
+                record_game(&mut standings, cross, naught, game.winner);
+            }
+        }
+
+        standings.sort_by(|a, b| {
+            b.win_rate()
+                .total_cmp(&a.win_rate())
+                .then(b.rating.total_cmp(&a.rating))
+        });
+        Leaderboard { standings }
+    }
+
+    /// Creates the leaderboard of the games of the history.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The history of the games.
+    pub fn load(store: &GameStore) -> io::Result<Self> {
+        Ok(Self::new(&store.list()?))
+    }
+
+    /// Returns the standings of the players, from the highest win rate to the lowest,
+    /// by rating on equal win rates.
+    pub fn standings(&self) -> &[Standing] {
+        &self.standings
+    }
+
+    /// Returns the standing of the player with the name, if they played a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the player.
+    pub fn get(&self, name: &str) -> Option<&Standing> {
+        self.standings.iter().find(|standing| standing.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::engine::GameEnding;
+    use crate::game::storage::ending_name;
+    use crate::game::GameRecord;
+    use crate::logic::{GameMove, GameState, Grid};
+
+    /// Plays the moves from the empty board and stores the game.
+    fn stored_game(id: usize, cross: &str, naught: &str, cells: &[usize]) -> StoredGame {
+        let initial_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut game_state = initial_state;
+        let mut moves: Vec<GameMove> = vec![];
+        for &cell in cells {
+            let game_move = game_state.make_move_to(cell).unwrap();
+            game_state = *game_move.after_state();
+            moves.push(game_move);
+        }
+        let ending = if game_state.game_over() {
+            GameEnding::Finished
+        } else {
+            GameEnding::Interrupted
+        };
+        StoredGame {
+            id,
+            timestamp: 0,
+            record: GameRecord::new(cross, naught, initial_state, &moves),
+            winner: game_state.winner_mark(),
+            ending: ending_name(&ending).to_string(),
+        }
+    }
+
+    #[test]
+    fn test_leaderboard_ranks_by_win_rate() {
+        let games = [
+            stored_game(1, "Alice", "Bot", &[0, 3, 1, 4, 2]),
+            stored_game(2, "Bot", "Alice", &[0, 3, 1, 4, 2]),
+            stored_game(3, "Carol", "Alice", &[0, 3, 1, 4, 2]),
+            stored_game(4, "Carol", "Bot", &[0, 3, 1]),
+        ];
+        let leaderboard = Leaderboard::new(&games);
+        let names: Vec<&str> = leaderboard
+            .standings()
+            .iter()
+            .map(|standing| standing.name.as_str())
+            .collect();
+
+        assert_eq!(names, vec!["Carol", "Bot", "Alice"]);
+        let alice = leaderboard.get("Alice").unwrap();
+        assert_eq!((alice.wins, alice.ties, alice.losses), (1, 0, 2));
+        assert!(alice.rating < INITIAL_RATING);
+        assert_eq!(leaderboard.get("Carol").unwrap().games(), 1);
+        assert!(leaderboard.get("Dave").is_none());
+    }
+
+    #[test]
+    fn test_leaderboard_counts_the_games_lost_on_time() {
+        let mut flag_fall = stored_game(1, "Alice", "Bot", &[0, 3]);
+        flag_fall.winner = Some(Mark::Naught);
+        flag_fall.ending = ending_name(&GameEnding::FlagFall(Mark::Cross)).to_string();
+        let aborted = stored_game(2, "Alice", "Bot", &[0]);
+        let leaderboard = Leaderboard::new(&[flag_fall, aborted]);
+
+        let bot = leaderboard.get("Bot").unwrap();
+        assert_eq!((bot.wins, bot.ties, bot.losses), (1, 0, 0));
+        assert_eq!(leaderboard.get("Alice").unwrap().losses, 1);
+    }
+}
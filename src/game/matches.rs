@@ -0,0 +1,139 @@
+//! Matches of several games between the same two players.
+//! The starting mark alternates from one game to the next, starting with the starting mark of the engine,
+//! so neither player keeps the advantage of the first move.
+
+use crate::logic::Mark;
+
+use super::engine::{GameEnding, GameResult, TicTacToe};
+
+/// A match of a number of games, see `Match::play`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Match {
+    games: usize,
+}
+
+impl Match {
+    /// Creates a new `Match` of the number of games.
+    ///
+    /// # Arguments
+    ///
+    /// * `games` - The number of games of the match.
+    pub fn new(games: usize) -> Self {
+        Match { games }
+    }
+
+    /// Returns the number of games of the match.
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Plays the games of the match with the players of the engine, alternating the starting mark.
+    /// The match stops early if a game is interrupted.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The engine the games are played with.
+    pub fn play(&self, game: &mut TicTacToe) -> MatchResult {
+        let first_mark = game.starting_mark();
+        let mut results = Vec::with_capacity(self.games);
+        for i in 0..self.games {
+            let starting_mark = if i % 2 == 0 {
+                first_mark
+            } else {
+                first_mark.other()
+            };
+            let result = game.play(Some(starting_mark));
+            let interrupted = result.ending == GameEnding::Interrupted;
+            results.push(result);
+            if interrupted {
+                break;
+            }
+        }
+        MatchResult { games: results }
+    }
+}
+
+/// The results of the games of a match.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MatchResult {
+    games: Vec<GameResult>,
+}
+
+impl MatchResult {
+    /// Returns the results of the games played, in order.
+    pub fn games(&self) -> &[GameResult] {
+        &self.games
+    }
+
+    /// Returns the number of games won by the player of the mark.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn wins(&self, mark: Mark) -> usize {
+        self.games
+            .iter()
+            .filter(|result| result.winner == Some(mark))
+            .count()
+    }
+
+    /// Returns the number of games played to their end without a winner,
+    /// the aborted and interrupted games are not ties.
+    pub fn ties(&self) -> usize {
+        self.games
+            .iter()
+            .filter(|result| result.ending == GameEnding::Finished && result.winner.is_none())
+            .count()
+    }
+
+    /// Returns the mark of the player who won the most games, or `None` if both won as many.
+    pub fn winner(&self) -> Option<Mark> {
+        let (cross, naught) = (self.wins(Mark::Cross), self.wins(Mark::Naught));
+        match cross.cmp(&naught) {
+            std::cmp::Ordering::Greater => Some(Mark::Cross),
+            std::cmp::Ordering::Less => Some(Mark::Naught),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{renderers::NoRenderer, InvalidMovePolicy, ScriptedPlayer};
+
+    #[test]
+    fn test_match_alternates_the_starting_mark() {
+        // Each player wins the game they start, the third game is a tie.
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2, 0, 1, 0, 8, 6, 5, 1]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4, 3, 4, 5, 4, 2, 3, 7]);
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None).unwrap();
+        let result = Match::new(3).play(&mut game);
+        drop(game);
+        assert_eq!(cross.remaining(), 0);
+
+        assert_eq!(result.games().len(), 3);
+        assert_eq!(*result.games()[1].final_state.starting_mark(), Mark::Naught);
+        assert_eq!(*result.games()[2].final_state.starting_mark(), Mark::Cross);
+        assert_eq!(result.wins(Mark::Cross), 1);
+        assert_eq!(result.wins(Mark::Naught), 1);
+        assert_eq!(result.ties(), 1);
+        assert_eq!(result.winner(), None);
+    }
+
+    #[test]
+    fn test_aborted_games_are_not_ties() {
+        // The naughts have no move left in the second game, which they start.
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .with_invalid_move_policy(InvalidMovePolicy::Abort);
+        let result = Match::new(2).play(&mut game);
+
+        assert!(matches!(result.games()[1].ending, GameEnding::Aborted(_)));
+        assert_eq!(result.wins(Mark::Cross), 1);
+        assert_eq!(result.ties(), 0);
+        assert_eq!(result.winner(), Some(Mark::Cross));
+    }
+}
@@ -3,12 +3,58 @@
 //! And it contains the Renderer trait, which is used to define the behavior of a renderer.
 //! And it contains the minimax module, which contains the MinimaxPlayer struct, which is a player that uses the minimax algorithm to make moves.
 
+pub mod analysis;
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod bench;
+pub mod clock;
+pub mod control;
 pub mod engine;
+pub mod env;
+pub mod facade;
+#[cfg(feature = "minimax")]
+pub mod game_tree;
+pub(crate) mod json;
+pub mod leaderboard;
+pub mod matches;
+pub mod observers;
 pub mod players;
+pub mod record;
 pub mod renderers;
+pub mod replay;
+pub mod saved_game;
+pub mod stats;
+pub mod storage;
+pub mod tournament;
 
-pub use engine::TicTacToe;
+pub use analysis::{analyze, best_move, perft, suggest_move, Outcome};
+#[cfg(feature = "async")]
+pub use asynchronous::AsyncPlayer;
+pub use bench::{bench, BenchReport};
+pub use clock::{Clocks, TimeControl};
+pub use control::GameControl;
+pub use engine::{GameEnding, GameResult, InvalidMovePolicy, TicTacToe, TicTacToeBuilder, Turns};
+pub use env::{Step, TicTacToeEnv};
+pub use facade::{GameStatus, Intent, SyncGameFacade};
+#[cfg(feature = "minimax")]
+pub use game_tree::{NeutralEvaluator, TicTacToeGame};
+pub use leaderboard::Leaderboard;
+pub use matches::{Match, MatchResult};
+pub use observers::GameObserver;
+pub use players::builder::AiBuilder;
+pub use players::external::ExternalProcessPlayer;
+pub use players::heuristic::HeuristicPlayer;
+pub use players::learning::{Learner, LearningPlayer};
+pub use players::mcts::MctsPlayer;
 pub use players::minimax::MinimaxPlayer;
+pub use players::perfect::PerfectPlayer;
 pub use players::random::DumbPlayer;
-pub use players::Player;
+pub use players::scripted::{ScriptError, ScriptedPlayer};
+pub use players::timed::{TimedPlayer, TimeoutPolicy};
+pub use players::{Player, PlayerCommand};
+pub use record::GameRecord;
 pub use renderers::Renderer;
+pub use saved_game::SavedGame;
+pub use stats::{PlayerStats, Stats};
+pub use storage::{GameStore, StoredGame};
+pub use tournament::{Standing, Tournament};
@@ -0,0 +1,114 @@
+//! Observers of the game.
+use crate::logic::{errors::MoveError, GameMove, GameState, Mark};
+
+use super::engine::GameResult;
+use super::record::GameRecord;
+
+/// A trait for following the events of a game, such as statistics or logs.
+/// The engine calls the observers in the order they were added,
+/// each method does nothing by default so an observer only implements the events it needs.
+/// Once every observer saw the end of the game, they get its record, with the names of the players.
+/// An observer is `Sync`, as the game borrowing it can be played on another thread.
+pub trait GameObserver: Sync {
+    fn on_game_start(&self, _game_state: &GameState) {}
+    fn on_move_made(&self, _game_move: &GameMove) {}
+    fn on_invalid_move(&self, _mark: Mark, _error: &MoveError) {}
+    fn on_game_over(&self, _result: &GameResult) {}
+    fn on_game_recorded(&self, _record: &GameRecord) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::game::{renderers::NoRenderer, Player, ScriptedPlayer, TicTacToe};
+
+    #[derive(Default)]
+    struct EventLog {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl GameObserver for EventLog {
+        fn on_game_start(&self, _game_state: &GameState) {
+            self.events.lock().unwrap().push(String::from("start"));
+        }
+
+        fn on_move_made(&self, game_move: &GameMove) {
+            let event = format!("{} {}", game_move.mark(), game_move.cell_index());
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn on_invalid_move(&self, mark: Mark, _error: &MoveError) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("{} invalid", mark));
+        }
+
+        fn on_game_over(&self, result: &GameResult) {
+            let event = format!("{:?} wins", result.winner);
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn on_game_recorded(&self, record: &GameRecord) {
+            let event = format!("recorded {}", record.result());
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    /// A scripted player which gives no move the first time it is asked.
+    struct HesitantPlayer {
+        scripted: ScriptedPlayer,
+        hesitated: bool,
+    }
+
+    impl Player for HesitantPlayer {
+        fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+            if !self.hesitated {
+                self.hesitated = true;
+                return None;
+            }
+            self.scripted.get_move(game_state)
+        }
+
+        fn get_mark(&self) -> Mark {
+            self.scripted.get_mark()
+        }
+    }
+
+    #[test]
+    fn test_observers_follow_the_game() {
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = HesitantPlayer {
+            scripted: ScriptedPlayer::new(Mark::Naught, vec![3, 4]),
+            hesitated: false,
+        };
+        let log = EventLog::default();
+        let other_log = EventLog::default();
+        TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .with_observer(&log)
+            .with_observer(&other_log)
+            .play(None);
+
+        assert_eq!(
+            *log.events.lock().unwrap(),
+            vec![
+                "start",
+                "X 0",
+                "O invalid",
+                "O 3",
+                "X 1",
+                "O 4",
+                "X 2",
+                "Some(Cross) wins",
+                "recorded 1-0"
+            ]
+        );
+        assert_eq!(
+            *other_log.events.lock().unwrap(),
+            *log.events.lock().unwrap()
+        );
+    }
+}
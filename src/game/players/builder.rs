@@ -0,0 +1,170 @@
+//! A builder configuring the computer players from presets and fine-grained knobs,
+//! so the cli and the library users create them the same way.
+use std::time::Duration;
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+use crate::{
+    game::players::{
+        mcts::MctsPlayer,
+        minimax::{MinimaxPlayer, SearchSettings},
+        Player,
+    },
+    logic::Mark,
+};
+
+/// The search algorithm of the built player.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Algorithm {
+    Minimax,
+    Mcts { iterations: usize },
+}
+
+/// A builder of computer players.
+///
+/// The depth, randomness and book knobs apply to the minimax search,
+/// the iterations knob applies to the Monte Carlo tree search, and the time limit applies to both.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AiBuilder {
+    algorithm: Algorithm,
+    settings: SearchSettings,
+    seed: Option<u64>,
+}
+
+impl Default for AiBuilder {
+    fn default() -> Self {
+        Self::perfect()
+    }
+}
+
+impl AiBuilder {
+    /// A minimax search to the end of the game, which never loses.
+    pub fn perfect() -> Self {
+        AiBuilder {
+            algorithm: Algorithm::Minimax,
+            settings: SearchSettings::default(),
+            seed: None,
+        }
+    }
+
+    /// A shallow minimax search which plays a random move now and then, and can be beaten.
+    pub fn casual() -> Self {
+        Self::perfect().depth(2).randomness(0.25).book(false)
+    }
+
+    /// A minimax search which stops deepening after a few milliseconds.
+    pub fn blitz() -> Self {
+        Self::perfect().time_limit(Duration::from_millis(10))
+    }
+
+    /// A Monte Carlo tree search, which is experimental and may miss some defenses.
+    pub fn experimental_mcts() -> Self {
+        AiBuilder {
+            algorithm: Algorithm::Mcts { iterations: 2000 },
+            ..Self::perfect()
+        }
+    }
+
+    /// Sets the number of plies searched by the minimax search.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.settings.max_depth = Some(depth);
+        self
+    }
+
+    /// Sets the time after which the search stops deepening, or stops iterating.
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.settings.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Sets the probability, between 0 and 1, of playing a random move instead of searching.
+    pub fn randomness(mut self, randomness: f64) -> Self {
+        self.settings.randomness = randomness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets whether the opening book is used.
+    pub fn book(mut self, use_book: bool) -> Self {
+        self.settings.use_book = use_book;
+        self
+    }
+
+    /// Sets the number of iterations of the Monte Carlo tree search per move.
+    /// Switches the builder to the Monte Carlo tree search.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.algorithm = Algorithm::Mcts { iterations };
+        self
+    }
+
+    /// Sets the seed of the random number generator, so the games are reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Builds a player with the given mark.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn build(&self, mark: Mark) -> Box<dyn Player> {
+        let rng: Box<dyn RngCore + Send> = match self.seed {
+            Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+            None => Box::new(StdRng::from_entropy()),
+        };
+        match self.algorithm {
+            Algorithm::Minimax => {
+                Box::new(MinimaxPlayer::new_with_settings(mark, rng, self.settings))
+            }
+            Algorithm::Mcts { iterations } => Box::new(MctsPlayer::new_with_rng(
+                mark,
+                iterations,
+                self.settings.time_limit,
+                rng,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{GameState, Grid};
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(AiBuilder::default(), AiBuilder::perfect());
+        assert_eq!(AiBuilder::casual().settings.max_depth, Some(2));
+        assert!(!AiBuilder::casual().settings.use_book);
+        assert_eq!(
+            AiBuilder::experimental_mcts().algorithm,
+            Algorithm::Mcts { iterations: 2000 }
+        );
+    }
+
+    #[test]
+    fn test_perfect_players_draw() {
+        let mut cross = AiBuilder::perfect().seed(1).build(Mark::Cross);
+        let mut naught = AiBuilder::perfect().seed(2).build(Mark::Naught);
+        let mut game_state = GameState::new(Grid::new(None), None).unwrap();
+        while !game_state.game_over() {
+            let player = if game_state.current_mark() == Mark::Cross {
+                &mut cross
+            } else {
+                &mut naught
+            };
+            game_state = player.make_move(&game_state).unwrap();
+        }
+        assert!(game_state.tie());
+    }
+
+    #[test]
+    fn test_same_seed_same_moves() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut player1 = AiBuilder::casual().seed(5).build(Mark::Cross);
+        let mut player2 = AiBuilder::casual().seed(5).build(Mark::Cross);
+        for _ in 0..10 {
+            assert_eq!(player1.get_move(&game_state), player2.get_move(&game_state));
+        }
+    }
+}
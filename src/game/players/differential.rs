@@ -0,0 +1,94 @@
+//! A differential check between the two ways the computer players value a position:
+//! the minimax search and the solved `PolicyTable`.
+//! Every position reachable from the empty grid is valued both ways,
+//! so an optimization of either one which changes a value is caught with the offending position.
+use std::collections::HashSet;
+
+use crate::{
+    game::players::{
+        minimax::{negamax, FULL_DEPTH},
+        perfect::PolicyTable,
+        protocol::position_line,
+    },
+    logic::{GameState, Grid, Mark, Variant},
+};
+
+/// A position where the minimax search and the `PolicyTable` disagree.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Divergence {
+    /// The position, as a `position` line of the protocol.
+    pub position: String,
+    /// The value found by the minimax search.
+    pub minimax: i32,
+    /// The value in the `PolicyTable`, `None` if the position is missing.
+    pub table: Option<i32>,
+}
+
+/// Returns every position of the variant where the minimax search and the `PolicyTable` disagree,
+/// whichever mark starts.
+///
+/// # Arguments
+///
+/// * `variant` - The variant of the rules to check.
+pub fn divergences(variant: Variant) -> Vec<Divergence> {
+    let table = PolicyTable::for_variant(variant);
+    let mut visited = HashSet::new();
+    let mut divergences = vec![];
+    for starting_mark in [Mark::Cross, Mark::Naught] {
+        let game_state =
+            GameState::new_with_variant(Grid::new(None), Some(starting_mark), variant).unwrap();
+        check_position(&game_state, table, &mut visited, &mut divergences);
+    }
+    divergences
+}
+
+/// Checks the game state and the positions reachable from it which are not visited yet.
+fn check_position(
+    game_state: &GameState,
+    table: &PolicyTable,
+    visited: &mut HashSet<u32>,
+    divergences: &mut Vec<Divergence>,
+) {
+    if !visited.insert(game_state.position_key()) {
+        return;
+    }
+
+    let minimax = negamax(game_state, -i32::MAX, i32::MAX, FULL_DEPTH);
+    let table_value = table.value(game_state);
+    if table_value != Some(minimax) {
+        divergences.push(Divergence {
+            position: position_line(game_state),
+            minimax,
+            table: table_value,
+        });
+    }
+
+    for move_ in game_state.possible_moves() {
+        check_position(move_.after_state(), table, visited, divergences);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_minimax_agrees_with_the_table() {
+        assert_eq!(divergences(Variant::Standard), vec![]);
+    }
+
+    #[test]
+    fn test_notakto_minimax_agrees_with_the_table() {
+        assert_eq!(divergences(Variant::Notakto), vec![]);
+    }
+
+    #[test]
+    fn test_wild_minimax_agrees_with_the_table() {
+        assert_eq!(divergences(Variant::Wild), vec![]);
+    }
+
+    #[test]
+    fn test_gravity_minimax_agrees_with_the_table() {
+        assert_eq!(divergences(Variant::Gravity), vec![]);
+    }
+}
@@ -0,0 +1,129 @@
+//! A player backed by an external executable, so bots written in any language can play.
+//! The player writes a `position` line on the standard input of the executable
+//! and reads a `move` line from its standard output, see the `protocol` module.
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::{
+    game::players::{
+        protocol::{parse_move_line, position_line},
+        Player, PlayerCommand,
+    },
+    logic::{GameMove, GameState, Mark},
+};
+
+/// A player which asks an external executable for its moves.
+pub struct ExternalProcessPlayer {
+    mark: Mark,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    /// The command sent to the engine when the connection is closed.
+    command: Option<PlayerCommand>,
+}
+
+impl ExternalProcessPlayer {
+    /// Spawns the executable and creates a new ExternalProcessPlayer talking to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `program` - The path of the executable.
+    /// * `args` - The arguments given to the executable.
+    pub fn spawn(mark: Mark, program: &str, args: &[&str]) -> io::Result<Self> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("The standard input is piped");
+        let stdout = child.stdout.take().expect("The standard output is piped");
+
+        Ok(ExternalProcessPlayer {
+            mark,
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            command: None,
+        })
+    }
+
+    /// Sends the position to the executable and reads its answer.
+    fn exchange(&mut self, game_state: &GameState) -> io::Result<String> {
+        writeln!(self.stdin, "{}", position_line(game_state))?;
+        self.stdin.flush()?;
+
+        let mut answer = String::new();
+        if self.stdout.read_line(&mut answer)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "The external player closed its output",
+            ));
+        }
+        Ok(answer)
+    }
+}
+
+impl Player for ExternalProcessPlayer {
+    /// Gets the move of the executable, which resigns when its input or output is closed.
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        let Ok(answer) = self.exchange(game_state) else {
+            self.command = Some(PlayerCommand::Resign);
+            return None;
+        };
+        parse_move_line(game_state, &answer)
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn kind(&self) -> &'static str {
+        "External"
+    }
+
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        self.command.take()
+    }
+}
+
+impl Drop for ExternalProcessPlayer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::Grid;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_move_from_process() {
+        let mut player = ExternalProcessPlayer::spawn(
+            Mark::Cross,
+            "sh",
+            &["-c", "while read line; do echo move 4; done"],
+        )
+        .unwrap();
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(player.get_move(&game_state).unwrap().cell_index(), 4);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_closed_process_forfeits() {
+        use crate::game::{renderers::NoRenderer, GameEnding, ScriptedPlayer, TicTacToe};
+
+        let mut cross = ExternalProcessPlayer::spawn(Mark::Cross, "true", &[]).unwrap();
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![]);
+        // The default policy asks again without limit, a closed pipe still ends the game.
+        let result = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .play(None);
+        assert_eq!(result.ending, GameEnding::Forfeit(Mark::Cross));
+        assert_eq!(result.winner, Some(Mark::Naught));
+    }
+}
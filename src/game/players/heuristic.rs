@@ -0,0 +1,143 @@
+//! A player that follows the classic rules of thumb instead of searching the game tree.
+//! In order of priority, it takes a winning move, blocks the winning move of the other player,
+//! then takes the center, a corner, or an edge.
+use crate::{
+    game::players::Player,
+    logic::{GameMove, GameState, Grid, Mark},
+};
+
+/// A player that follows the classic rules of thumb: win, block, center, corner, edge.
+pub struct HeuristicPlayer {
+    mark: Mark,
+}
+
+impl HeuristicPlayer {
+    /// Creates a new HeuristicPlayer with the given mark.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn new(mark: Mark) -> Self {
+        HeuristicPlayer { mark }
+    }
+}
+
+impl Player for HeuristicPlayer {
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        let mut moves = game_state.possible_moves();
+        moves.sort_by_key(|move_| cell_priority(move_.cell_index()));
+
+        let mover = game_state.current_mark();
+        if let Some(winning_move) = moves
+            .iter()
+            .find(|move_| move_.after_state().winner_mark() == Some(mover))
+        {
+            return Some(*winning_move);
+        }
+
+        // Blocking means not leaving the other player a winning move.
+        if let Some(blocking_move) = moves
+            .iter()
+            .find(|move_| !has_winning_move(move_.after_state()))
+        {
+            return Some(*blocking_move);
+        }
+
+        moves.first().copied()
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn kind(&self) -> &'static str {
+        "Heuristic AI"
+    }
+}
+
+/// Returns `true` if the player whose turn it is can win with their next move, `false` otherwise.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to check.
+fn has_winning_move(game_state: &GameState) -> bool {
+    let mover = game_state.current_mark();
+    game_state
+        .possible_moves()
+        .iter()
+        .any(|move_| move_.after_state().winner_mark() == Some(mover))
+}
+
+/// Returns the priority of a cell, lower is better:
+/// 0 for the center, 1 for the corners and 2 for the edges.
+///
+/// # Arguments
+///
+/// * `cell_index` - The index of the cell.
+pub(super) fn cell_priority(cell_index: usize) -> u8 {
+    let row = cell_index / Grid::WIDTH;
+    let column = cell_index % Grid::WIDTH;
+    let is_border = |i: usize| i == 0 || i == Grid::WIDTH - 1;
+
+    if row == Grid::WIDTH / 2 && column == Grid::WIDTH / 2 {
+        0
+    } else if is_border(row) && is_border(column) {
+        1
+    } else {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::Cell;
+
+    #[test]
+    fn test_get_move_wins() {
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+        ]));
+        let game_state = GameState::new(grid, None).unwrap();
+        let mut player = HeuristicPlayer::new(Mark::Cross);
+        assert_eq!(player.get_move(&game_state).unwrap().cell_index(), 2);
+    }
+
+    #[test]
+    fn test_get_move_blocks() {
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+        ]));
+        let game_state = GameState::new(grid, None).unwrap();
+        let mut player = HeuristicPlayer::new(Mark::Naught);
+        assert_eq!(player.get_move(&game_state).unwrap().cell_index(), 2);
+    }
+
+    #[test]
+    fn test_get_move_center_then_corner() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut player = HeuristicPlayer::new(Mark::Cross);
+        let first_move = player.get_move(&game_state).unwrap();
+        assert_eq!(first_move.cell_index(), 4);
+
+        let mut player = HeuristicPlayer::new(Mark::Naught);
+        let second_move = player.get_move(first_move.after_state()).unwrap();
+        assert_eq!(second_move.cell_index(), 0);
+    }
+}
@@ -0,0 +1,454 @@
+//! A player that learns to play from the games it finishes, in the spirit of MENACE.
+//! It keeps a table with the estimated value of each game state it can move to,
+//! plays the move reaching the best valued state, and after each game moves the value
+//! of every state it went through towards the value of the state which followed, then towards the result.
+//! The table can be trained by self-play and saved to disk.
+//! When the engine owns the player, its `Learner` learns from the games as a `GameObserver`.
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore, SeedableRng};
+
+use crate::{
+    game::{
+        engine::{GameEnding, GameResult},
+        observers::GameObserver,
+        players::Player,
+    },
+    logic::{GameMove, GameState, Grid, Mark, Variant},
+};
+
+/// The value of a game state never seen before.
+const DEFAULT_VALUE: f64 = 0.5;
+/// How far a value moves towards the value of the next state after each game.
+const LEARNING_RATE: f64 = 0.2;
+/// The probability of playing a random move while training, to explore new states.
+const TRAINING_EXPLORATION: f64 = 0.1;
+
+/// The key of a game state in the value table: its variant, then its position within the variant,
+/// so one table can hold what the player learnt in every variant.
+type StateKey = (Variant, u32);
+
+/// Returns the key of the game state in the value table.
+fn state_key(game_state: &GameState) -> StateKey {
+    (game_state.variant(), game_state.position_key())
+}
+
+/// What a learning player learnt, shared with its `Learner`.
+#[derive(Default)]
+struct Memory {
+    /// The value of each game state for the player who moved to it, from 0 for a loss to 1 for a win.
+    values: HashMap<StateKey, f64>,
+    /// The game states the player moved to in the current game.
+    history: Vec<StateKey>,
+}
+
+impl Memory {
+    /// Updates the values with the winner of the game the player took part in,
+    /// then forgets the game so a new one can start.
+    fn learn(&mut self, mark: Mark, winner: Option<Mark>) {
+        let history = std::mem::take(&mut self.history);
+        self.learn_from(&history, reward(winner, mark));
+    }
+
+    /// Moves the value of the last state towards the reward,
+    /// and the value of every other state towards the value of the state which followed.
+    fn learn_from(&mut self, history: &[StateKey], reward: f64) {
+        let mut target = reward;
+        for key in history.iter().rev() {
+            let value = self.values.entry(*key).or_insert(DEFAULT_VALUE);
+            *value += LEARNING_RATE * (target - *value);
+            target = *value;
+        }
+    }
+
+    /// Saves the values to the given file, one `variant key value` triple per line.
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let mut entries: Vec<(StateKey, f64)> = self
+            .values
+            .iter()
+            .map(|(key, value)| (*key, *value))
+            .collect();
+        entries.sort_by_key(|((variant, key), _)| (variant.name(), *key));
+        let content: String = entries
+            .iter()
+            .map(|((variant, key), value)| format!("{} {} {}\n", variant.name(), key, value))
+            .collect();
+        fs::write(path, content)
+    }
+}
+
+/// Parses a `variant key value` line of a value table.
+fn parse_entry(line: &str) -> Option<(StateKey, f64)> {
+    let mut fields = line.split(' ');
+    let variant = Variant::from_name(fields.next()?)?;
+    let key = fields.next()?.parse().ok()?;
+    let value = fields.next()?.parse().ok()?;
+    fields.next().is_none().then_some(((variant, key), value))
+}
+
+/// Locks the memory, a thread which panicked while learning leaves it usable.
+fn lock(memory: &Mutex<Memory>) -> MutexGuard<'_, Memory> {
+    memory.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A player that learns the value of game states from the games it finishes.
+pub struct LearningPlayer {
+    mark: Mark,
+    memory: Arc<Mutex<Memory>>,
+    rng: Box<dyn RngCore + Send>,
+}
+
+impl LearningPlayer {
+    /// Creates a new LearningPlayer with the given mark and an empty value table.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn new(mark: Mark) -> Self {
+        Self::new_with_rng(mark, Box::new(StdRng::from_entropy()))
+    }
+
+    /// Creates a new LearningPlayer whose random choices are drawn from a generator seeded with the given seed,
+    /// so the training is reproducible.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `seed` - The seed of the random number generator.
+    pub fn new_with_seed(mark: Mark, seed: u64) -> Self {
+        Self::new_with_rng(mark, Box::new(StdRng::seed_from_u64(seed)))
+    }
+
+    /// Creates a new LearningPlayer whose random choices are drawn from the given random number generator,
+    /// a seeded generator makes the training reproducible.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `rng` - The random number generator of the exploration and of the ties.
+    pub fn new_with_rng(mark: Mark, rng: Box<dyn RngCore + Send>) -> Self {
+        LearningPlayer {
+            mark,
+            memory: Arc::default(),
+            rng,
+        }
+    }
+
+    /// Creates a new LearningPlayer with the value table saved in the given file.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `path` - The file written by `LearningPlayer::save`.
+    pub fn load(mark: Mark, path: &Path) -> io::Result<Self> {
+        let mut values = HashMap::new();
+        for line in fs::read_to_string(path)?.lines() {
+            match parse_entry(line) {
+                Some((key, value)) => values.insert(key, value),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid line in the value table: `{}`", line),
+                    ))
+                }
+            };
+        }
+        let player = Self::new(mark);
+        lock(&player.memory).values = values;
+        Ok(player)
+    }
+
+    /// Creates a new LearningPlayer with the value table saved in the given file,
+    /// or with an empty value table if the file does not exist yet.
+    /// Returns an error if the file cannot be read or is not a value table.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `path` - The file written by `LearningPlayer::save`.
+    pub fn load_or_new(mark: Mark, path: &Path) -> io::Result<Self> {
+        match Self::load(mark, path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::new(mark)),
+            loaded => loaded,
+        }
+    }
+
+    /// Saves the value table to the given file, one `variant key value` triple per line.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to write.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        lock(&self.memory).save(path)
+    }
+
+    /// Returns the number of game states in the value table.
+    pub fn known_states(&self) -> usize {
+        lock(&self.memory).values.len()
+    }
+
+    /// Returns the learner of the player, which learns from the games it plays as an observer,
+    /// for a player owned by the engine.
+    pub fn learner(&self) -> Learner {
+        Learner {
+            mark: self.mark,
+            memory: Arc::clone(&self.memory),
+        }
+    }
+
+    /// Updates the value table with the result of the game the player took part in,
+    /// then forgets the game so a new one can start.
+    ///
+    /// # Arguments
+    ///
+    /// * `final_state` - The state of the game once over.
+    pub fn learn(&mut self, final_state: &GameState) {
+        lock(&self.memory).learn(self.mark, final_state.winner_mark());
+    }
+
+    /// Plays the given number of games against itself, alternating the starting mark,
+    /// and learns from both sides of every game.
+    ///
+    /// # Arguments
+    ///
+    /// * `games` - The number of games to play.
+    /// * `variant` - The variant of the rules the games are played with.
+    pub fn train(&mut self, games: usize, variant: Variant) {
+        for game in 0..games {
+            let starting_mark = if game % 2 == 0 {
+                Mark::Cross
+            } else {
+                Mark::Naught
+            };
+            let mut game_state =
+                GameState::new_with_variant(Grid::new(None), Some(starting_mark), variant).unwrap();
+            let mut cross_history = Vec::new();
+            let mut naught_history = Vec::new();
+
+            while let Some(next_move) = self.choose_move(&game_state, TRAINING_EXPLORATION) {
+                let history = match next_move.mark() {
+                    Mark::Cross => &mut cross_history,
+                    Mark::Naught => &mut naught_history,
+                };
+                history.push(state_key(next_move.after_state()));
+                game_state = *next_move.after_state();
+            }
+
+            let mut memory = lock(&self.memory);
+            let winner = game_state.winner_mark();
+            memory.learn_from(&cross_history, reward(winner, Mark::Cross));
+            memory.learn_from(&naught_history, reward(winner, Mark::Naught));
+        }
+    }
+
+    /// Returns the move reaching the best valued game state,
+    /// or a random move with the given probability.
+    fn choose_move(&mut self, game_state: &GameState, exploration: f64) -> Option<GameMove> {
+        let moves = game_state.possible_moves();
+        if self.rng.gen_bool(exploration) {
+            return moves.choose(&mut self.rng).copied();
+        }
+
+        let memory = lock(&self.memory);
+        let values = &memory.values;
+        let value_of = |move_: &GameMove| {
+            *values
+                .get(&state_key(move_.after_state()))
+                .unwrap_or(&DEFAULT_VALUE)
+        };
+        let best_value = moves.iter().map(value_of).fold(f64::MIN, f64::max);
+        let best_moves: Vec<GameMove> = moves
+            .iter()
+            .filter(|move_| value_of(move_) == best_value)
+            .copied()
+            .collect();
+        best_moves.choose(&mut self.rng).copied()
+    }
+}
+
+impl Player for LearningPlayer {
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        let next_move = self.choose_move(game_state, 0.0)?;
+        lock(&self.memory)
+            .history
+            .push(state_key(next_move.after_state()));
+        Some(next_move)
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn kind(&self) -> &'static str {
+        "Learning AI"
+    }
+}
+
+/// The learner of a `LearningPlayer`, see `LearningPlayer::learner`.
+/// It learns from the games won, lost or tied, and forgets the games aborted or interrupted.
+pub struct Learner {
+    mark: Mark,
+    memory: Arc<Mutex<Memory>>,
+}
+
+impl Learner {
+    /// Saves the value table of the player to the given file, see `LearningPlayer::save`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to write.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        lock(&self.memory).save(path)
+    }
+}
+
+impl GameObserver for Learner {
+    fn on_game_over(&self, result: &GameResult) {
+        let mut memory = lock(&self.memory);
+        match result.ending {
+            GameEnding::Aborted(_) | GameEnding::Interrupted => memory.history.clear(),
+            _ => memory.learn(self.mark, result.winner),
+        }
+    }
+}
+
+/// Returns the reward of the finished game for the given mark: 1 for a win, 0.5 for a tie and 0 for a loss.
+fn reward(winner: Option<Mark>, mark: Mark) -> f64 {
+    match winner {
+        Some(winner) if winner == mark => 1.0,
+        Some(_) => 0.0,
+        None => 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+    use crate::game::{renderers::NoRenderer, DumbPlayer, TicTacToe};
+
+    fn play(cells: &[usize]) -> Vec<GameMove> {
+        let mut game_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut moves = Vec::new();
+        for cell_index in cells {
+            let next_move = game_state.make_move_to(*cell_index).unwrap();
+            game_state = *next_move.after_state();
+            moves.push(next_move);
+        }
+        moves
+    }
+
+    #[test]
+    fn test_learn_rewards_the_winner() {
+        let moves = play(&[0, 3, 1, 4, 2]);
+        let mut winner = LearningPlayer::new_with_seed(Mark::Cross, 1);
+        let mut loser = LearningPlayer::new_with_seed(Mark::Naught, 1);
+        for move_ in moves.iter() {
+            let player = if *move_.mark() == Mark::Cross {
+                &mut winner
+            } else {
+                &mut loser
+            };
+            lock(&player.memory)
+                .history
+                .push(state_key(move_.after_state()));
+        }
+        let final_state = moves.last().unwrap().after_state();
+        winner.learn(final_state);
+        loser.learn(final_state);
+
+        let winning_key = state_key(final_state);
+        assert!(lock(&winner.memory).values[&winning_key] > DEFAULT_VALUE);
+        let losing_key = state_key(moves[3].after_state());
+        assert!(lock(&loser.memory).values[&losing_key] < DEFAULT_VALUE);
+        assert!(lock(&winner.memory).history.is_empty());
+    }
+
+    #[test]
+    fn test_get_move_plays_the_best_valued_state() {
+        let mut player = LearningPlayer::new_with_seed(Mark::Cross, 1);
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let favourite = game_state.make_move_to(5).unwrap();
+        lock(&player.memory)
+            .values
+            .insert(state_key(favourite.after_state()), 0.9);
+        assert_eq!(player.get_move(&game_state), Some(favourite));
+    }
+
+    #[test]
+    fn test_train_fills_the_table() {
+        let mut player = LearningPlayer::new_with_seed(Mark::Cross, 1);
+        player.train(50, Variant::Standard);
+        assert!(player.known_states() > 50);
+    }
+
+    #[test]
+    fn test_table_keeps_the_variants_apart() {
+        let mut player = LearningPlayer::new_with_seed(Mark::Cross, 1);
+        player.train(20, Variant::Standard);
+        player.train(20, Variant::Notakto);
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let first_move = game_state.make_move_to(4).unwrap();
+        lock(&player.memory)
+            .values
+            .insert(state_key(first_move.after_state()), 0.0);
+        let notakto_state =
+            GameState::new_with_variant(Grid::new(None), None, Variant::Notakto).unwrap();
+        let notakto_move = notakto_state.make_move_to(4).unwrap();
+
+        let values = &lock(&player.memory).values;
+        assert!(values
+            .keys()
+            .any(|(variant, _)| *variant == Variant::Standard));
+        assert!(values
+            .keys()
+            .any(|(variant, _)| *variant == Variant::Notakto));
+        assert_ne!(
+            values.get(&state_key(notakto_move.after_state())),
+            Some(&0.0)
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut player = LearningPlayer::new_with_seed(Mark::Cross, 1);
+        player.train(20, Variant::Standard);
+        player.train(20, Variant::Wild);
+        let path = env::temp_dir().join(format!("learning_table_{}.txt", process::id()));
+        player.save(&path).unwrap();
+        let loaded = LearningPlayer::load(Mark::Naught, &path);
+        fs::write(&path, "not a table").unwrap();
+        let invalid = LearningPlayer::load_or_new(Mark::Naught, &path);
+        fs::remove_file(&path).unwrap();
+        let missing = LearningPlayer::load_or_new(Mark::Naught, &path).unwrap();
+
+        assert_eq!(
+            lock(&loaded.unwrap().memory).values,
+            lock(&player.memory).values
+        );
+        assert_eq!(invalid.err().unwrap().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(missing.known_states(), 0);
+    }
+
+    #[test]
+    fn test_learner_learns_from_the_games_of_the_engine() {
+        let player = LearningPlayer::new_with_seed(Mark::Cross, 1);
+        let learner = player.learner();
+        let naught = DumbPlayer::new_with_seed(Mark::Naught, 1);
+        let mut game = TicTacToe::new(player, naught, NoRenderer, None)
+            .unwrap()
+            .with_observer(&learner);
+        game.play(None);
+        let known_states = lock(&learner.memory).values.len();
+        drop(game);
+
+        assert!(known_states > 0);
+        assert!(lock(&learner.memory).history.is_empty());
+    }
+}
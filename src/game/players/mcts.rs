@@ -0,0 +1,204 @@
+//! An experimental player that uses Monte Carlo tree search to find its moves.
+//! Each iteration walks down the tree with the UCT formula, expands one new state,
+//! plays the rest of the game at random and adds the result to the states along the path.
+//! The most visited move is played once the iterations, or the time, run out.
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore, SeedableRng};
+
+use crate::{
+    game::players::Player,
+    logic::{GameMove, GameState, Mark},
+};
+
+/// The weight of the exploration term of the UCT formula.
+const EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// A player which plays the most visited move of a Monte Carlo tree search.
+pub struct MctsPlayer {
+    mark: Mark,
+    iterations: usize,
+    time_limit: Option<Duration>,
+    rng: Box<dyn RngCore + Send>,
+}
+
+/// A state of the search tree.
+struct Node {
+    state: GameState,
+    move_: Option<GameMove>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried_moves: Vec<GameMove>,
+    visits: u32,
+    /// The sum of the rewards of the player who moved into this state.
+    reward: f64,
+}
+
+impl Node {
+    fn new(state: GameState, move_: Option<GameMove>, parent: Option<usize>) -> Self {
+        Node {
+            state,
+            move_,
+            parent,
+            children: vec![],
+            untried_moves: state.possible_moves(),
+            visits: 0,
+            reward: 0.0,
+        }
+    }
+}
+
+impl MctsPlayer {
+    /// Creates a new MctsPlayer with the given mark and number of iterations per move.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `iterations` - The number of iterations of the search per move.
+    pub fn new(mark: Mark, iterations: usize) -> Self {
+        Self::new_with_rng(mark, iterations, None, Box::new(StdRng::from_entropy()))
+    }
+
+    /// Creates a new MctsPlayer with the given limits and random number generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `iterations` - The maximum number of iterations of the search per move.
+    /// * `time_limit` - The time after which the search stops, `None` for no limit.
+    /// * `rng` - The random number generator used for the playouts.
+    pub fn new_with_rng(
+        mark: Mark,
+        iterations: usize,
+        time_limit: Option<Duration>,
+        rng: Box<dyn RngCore + Send>,
+    ) -> Self {
+        MctsPlayer {
+            mark,
+            iterations,
+            time_limit,
+            rng,
+        }
+    }
+}
+
+impl Player for MctsPlayer {
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        if game_state.game_over() {
+            return None;
+        }
+
+        let start = Instant::now();
+        let mut tree = vec![Node::new(*game_state, None, None)];
+        for _ in 0..self.iterations.max(1) {
+            iterate(&mut tree, self.rng.as_mut());
+            if self
+                .time_limit
+                .is_some_and(|limit| start.elapsed() >= limit)
+            {
+                break;
+            }
+        }
+
+        tree[0]
+            .children
+            .iter()
+            .max_by_key(|&&child| tree[child].visits)
+            .and_then(|&child| tree[child].move_)
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn kind(&self) -> &'static str {
+        "MCTS AI"
+    }
+}
+
+/// Runs one iteration of the search: selection, expansion, playout and backpropagation.
+fn iterate(tree: &mut Vec<Node>, rng: &mut dyn RngCore) {
+    let mut index = 0;
+    while tree[index].untried_moves.is_empty() && !tree[index].children.is_empty() {
+        index = select_child(tree, index);
+    }
+
+    if !tree[index].untried_moves.is_empty() {
+        let position = rng.gen_range(0..tree[index].untried_moves.len());
+        let move_ = tree[index].untried_moves.swap_remove(position);
+        tree.push(Node::new(*move_.after_state(), Some(move_), Some(index)));
+        let child = tree.len() - 1;
+        tree[index].children.push(child);
+        index = child;
+    }
+
+    let final_state = playout(&tree[index].state, rng);
+    let mut node = Some(index);
+    while let Some(current) = node {
+        tree[current].visits += 1;
+        if let Some(move_) = tree[current].move_ {
+            let score = final_state.score(*move_.mark()).unwrap();
+            tree[current].reward += f64::from(score + 1) / 2.0;
+        }
+        node = tree[current].parent;
+    }
+}
+
+/// Returns the child of the node with the highest UCT value.
+fn select_child(tree: &[Node], index: usize) -> usize {
+    let parent_visits = f64::from(tree[index].visits).ln();
+    let uct = |child: usize| {
+        let visits = f64::from(tree[child].visits);
+        tree[child].reward / visits + EXPLORATION * (parent_visits / visits).sqrt()
+    };
+    tree[index]
+        .children
+        .iter()
+        .copied()
+        .max_by(|&a, &b| uct(a).total_cmp(&uct(b)))
+        .unwrap()
+}
+
+/// Plays random moves until the game is over and returns the final state.
+fn playout(game_state: &GameState, rng: &mut dyn RngCore) -> GameState {
+    let mut state = *game_state;
+    while let Some(move_) = state.possible_moves().choose(rng) {
+        state = *move_.after_state();
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{Cell, Grid};
+
+    #[test]
+    fn test_mcts_takes_the_win() {
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+        ]));
+        let game_state = GameState::new(grid, None).unwrap();
+        let mut player =
+            MctsPlayer::new_with_rng(Mark::Cross, 500, None, Box::new(StdRng::seed_from_u64(1)));
+        assert_eq!(player.get_move(&game_state).unwrap().cell_index(), 2);
+    }
+
+    #[test]
+    fn test_mcts_game_over() {
+        let game_state = GameState::new(Grid::new(None), None)
+            .unwrap()
+            .apply_moves(&[0, 3, 1, 4, 2])
+            .unwrap();
+        let mut player = MctsPlayer::new(Mark::Naught, 10);
+        assert_eq!(player.get_move(&game_state), None);
+    }
+}
@@ -1,16 +1,53 @@
 //! A player that uses the minimax algorithm to find the best move.
 //! The minimax algorithm is a recursive algorithm that finds the best move for a player.
-//! It works by recursively finding the best move for the maximized player and the best move for the minimized player.
-//! The maximized player is the player whose turn it is.
-//! The minimized player is the other player.
+//! It is implemented in its negamax form: the score of a state for a player is the opposite of its score for the other player,
+//! so a single function scores the states for both players.
+//! Alpha-beta pruning and move ordering skip the parts of the tree which cannot change the result.
+//! When several moves have the best score, one of them is picked at random.
+//! In the first two plies, the moves are taken from the opening book instead of searched.
+//! The `SearchSettings` can weaken the player: a depth or time limit, random moves, or no book.
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore, SeedableRng};
+
 use crate::{
-    game::players::Player,
-    logic::{GameMove, GameState, Mark},
+    game::players::{heuristic::cell_priority, opening_book, Player},
+    logic::{GameMove, GameState, Grid, Mark},
 };
 
+/// The depth of a search to the end of the game.
+pub(super) const FULL_DEPTH: usize = Grid::SIZE;
+
+/// The settings of the search of a `MinimaxPlayer`, the default settings play perfectly.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) struct SearchSettings {
+    /// The number of plies searched, the states beyond are scored as a tie.
+    pub(super) max_depth: Option<usize>,
+    /// The time after which no deeper search is started.
+    pub(super) time_limit: Option<Duration>,
+    /// The probability of playing a random move instead of searching.
+    pub(super) randomness: f64,
+    /// Whether the opening book is used.
+    pub(super) use_book: bool,
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        SearchSettings {
+            max_depth: None,
+            time_limit: None,
+            randomness: 0.0,
+            use_book: true,
+        }
+    }
+}
+
 /// A player that uses the minimax algorithm to find the best move.
 pub struct MinimaxPlayer {
     mark: Mark,
+    rng: Box<dyn RngCore + Send>,
+    settings: SearchSettings,
+    nodes: u64,
 }
 
 impl MinimaxPlayer {
@@ -20,119 +57,351 @@ impl MinimaxPlayer {
     ///
     /// * `mark` - The mark of the player.
     pub fn new(mark: Mark) -> Self {
-        MinimaxPlayer { mark }
+        Self::new_with_rng(mark, Box::new(StdRng::from_entropy()))
+    }
+
+    /// Creates a new MinimaxPlayer with the given mark and random number generator.
+    /// The generator is used to pick among the moves with the best score,
+    /// a seeded generator makes the games reproducible.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `rng` - The random number generator used to break ties.
+    pub fn new_with_rng(mark: Mark, rng: Box<dyn RngCore + Send>) -> Self {
+        Self::new_with_settings(mark, rng, SearchSettings::default())
+    }
+
+    /// Creates a new MinimaxPlayer searching with the given settings.
+    pub(super) fn new_with_settings(
+        mark: Mark,
+        rng: Box<dyn RngCore + Send>,
+        settings: SearchSettings,
+    ) -> Self {
+        MinimaxPlayer {
+            mark,
+            rng,
+            settings,
+            nodes: 0,
+        }
     }
 }
 
 impl Player for MinimaxPlayer {
-    fn get_move(&self, game_state: &GameState) -> Option<GameMove> {
-        find_best_move(game_state)
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        let rng = &mut self.rng;
+        if self.settings.randomness > 0.0 && rng.gen_bool(self.settings.randomness.min(1.0)) {
+            return game_state.possible_moves().choose(rng.as_mut()).copied();
+        }
+        if self.settings.use_book {
+            if let Some(book_move) = opening_book::book_move(game_state, rng.as_mut()) {
+                return Some(book_move);
+            }
+        }
+        if self.settings.max_depth.is_none() && self.settings.time_limit.is_none() {
+            return find_best_move(game_state, FULL_DEPTH, rng.as_mut(), &mut self.nodes);
+        }
+        deepen(
+            game_state,
+            self.settings.max_depth,
+            self.settings.time_limit,
+            rng.as_mut(),
+            &mut self.nodes,
+        )
     }
 
     fn get_mark(&self) -> Mark {
         self.mark
     }
+
+    fn kind(&self) -> &'static str {
+        "Minimax AI"
+    }
+
+    fn nodes_searched(&self) -> u64 {
+        self.nodes
+    }
 }
 
-/// Finds the best move for the maximized player.
+/// Searches one ply deeper at a time, until the depth limit is reached
+/// or the time limit is spent, and returns the best move of the deepest search.
+/// The time limit is checked between the searches, a started search always completes.
 ///
 /// # Arguments
 ///
 /// * `game_state` - The game state to find the best move for.
-fn find_best_move(game_state: &GameState) -> Option<GameMove> {
-    let maximized_player = game_state.current_mark();
-    let alpha = i32::MIN;
-    let beta = i32::MAX;
-
-    game_state
-        .possible_moves()
-        .into_iter()
-        .max_by_key(|move_| minimax_with_pruning(move_, maximized_player, false, alpha, beta))
+/// * `max_depth` - The maximum number of plies searched, `None` for no limit.
+/// * `time_limit` - The time after which no deeper search is started, `None` for no limit.
+/// * `rng` - The random number generator used to break ties.
+/// * `nodes` - The count of the game states searched, increased by the search.
+fn deepen(
+    game_state: &GameState,
+    max_depth: Option<usize>,
+    time_limit: Option<Duration>,
+    rng: &mut dyn RngCore,
+    nodes: &mut u64,
+) -> Option<GameMove> {
+    let start = Instant::now();
+    let max_depth = max_depth.unwrap_or(FULL_DEPTH).clamp(1, FULL_DEPTH);
+    let mut best_move = None;
+    for depth in 1..=max_depth {
+        best_move = find_best_move(game_state, depth, rng, nodes).or(best_move);
+        if time_limit.is_some_and(|limit| start.elapsed() >= limit) {
+            break;
+        }
+    }
+    best_move
 }
 
-/// Finds the score of the given move.
-/// The score is the score of the after_state of the move.
-/// If the after_state is not a game over state, the score is the score of the best move for the other player.
-/// The best move for the other player is the move with the highest score if the maximized player is the other player.
-/// The best move for the other player is the move with the lowest score if the maximized player is the maximized player.
+/// Finds the best move for the player whose turn it is.
+/// If several moves have the best score, one of them is chosen at random.
 ///
 /// # Arguments
 ///
-/// * `move_` - The move to find the score of.
-/// * `maximized_player` - The maximized player.
-/// * `choose_highest_score` - Whether to choose the highest score or the lowest score.
-#[allow(dead_code)]
-fn minimax(move_: &GameMove, maximized_player: Mark, choose_highest_score: bool) -> i32 {
-    if move_.after_state().game_over() {
-        return move_.after_state().score(maximized_player).unwrap();
-    };
-    let scores = move_
-        .after_state()
-        .possible_moves()
-        .into_iter()
-        .map(|move_| minimax(&move_, maximized_player, !choose_highest_score));
-    if choose_highest_score {
-        scores.max().unwrap()
-    } else {
-        scores.min().unwrap()
+/// * `game_state` - The game state to find the best move for.
+/// * `depth` - The number of plies searched.
+/// * `rng` - The random number generator used to break ties.
+/// * `nodes` - The count of the game states searched, increased by the search.
+fn find_best_move(
+    game_state: &GameState,
+    depth: usize,
+    rng: &mut dyn RngCore,
+    nodes: &mut u64,
+) -> Option<GameMove> {
+    let mut best_score = -i32::MAX;
+    let mut best_moves: Vec<GameMove> = Vec::new();
+
+    for move_ in ordered_moves(game_state) {
+        // The window stays open just below the best score, so moves tied with it get an exact score.
+        let alpha = best_score.saturating_sub(1).max(-i32::MAX);
+        let score = -search(move_.after_state(), -i32::MAX, -alpha, depth - 1, nodes);
+        if score > best_score {
+            best_score = score;
+            best_moves.clear();
+        }
+        if score == best_score {
+            best_moves.push(move_);
+        }
     }
+
+    best_moves.choose(rng).copied()
 }
 
-/// Finds the score of the given move.
-/// The score is the score of the after_state of the move.
-/// If the after_state is not a game over state, the score is the score of the best move for the other player.
-/// The best move for the other player is the move with the highest score if the maximized player is the other player.
-/// The best move for the other player is the move with the lowest score if the maximized player is the maximized player.
-/// Use alpha-beta pruning to speed up the algorithm.
+/// Finds the score of the given game state for the player whose turn it is.
+/// The score of the state is the opposite of the best score among the states reachable by the other player.
+/// Use alpha-beta pruning to speed up the algorithm: once a move reaches `beta`,
+/// the other player will never let the game reach this state, so the remaining moves are skipped.
 ///
 /// # Arguments
 ///
-/// * `move_` - The move to find the score of.
-/// * `maximized_player` - The maximized player.
-/// * `choose_highest_score` - Whether to choose the highest score or the lowest score.
-/// * `alpha` - The alpha value.
-/// * `beta` - The beta value.
-fn minimax_with_pruning(
-    move_: &GameMove,
-    maximized_player: Mark,
-    choose_highest_score: bool,
-    alpha: i32,
-    beta: i32,
-) -> i32 {
-    if move_.after_state().game_over() {
-        return move_.after_state().score(maximized_player).unwrap();
-    }
-
-    let mut best_score = if choose_highest_score {
-        i32::MIN
-    } else {
-        i32::MAX
-    };
-
-    let mut new_alpha = alpha;
-    let mut new_beta = beta;
-
-    for child_move in move_.after_state().possible_moves() {
-        let score = minimax_with_pruning(
-            &child_move,
-            maximized_player,
-            !choose_highest_score,
-            new_alpha,
-            new_beta,
-        );
+/// * `game_state` - The game state to find the score of.
+/// * `alpha` - The score the player whose turn it is is already guaranteed.
+/// * `beta` - The score the other player is already guaranteed, negated.
+/// * `depth` - The number of plies left to search, a state which is not over at depth 0 is scored as a tie.
+pub(super) fn negamax(game_state: &GameState, alpha: i32, beta: i32, depth: usize) -> i32 {
+    search(game_state, alpha, beta, depth, &mut 0)
+}
 
-        if choose_highest_score {
-            best_score = best_score.max(score);
-            new_alpha = new_alpha.max(score);
-        } else {
-            best_score = best_score.min(score);
-            new_beta = new_beta.min(score);
-        }
+/// The negamax search of `negamax`, counting the game states it searches.
+///
+/// # Arguments
+///
+/// * `nodes` - The count of the game states searched, increased by the search.
+fn search(game_state: &GameState, mut alpha: i32, beta: i32, depth: usize, nodes: &mut u64) -> i32 {
+    *nodes += 1;
+    if game_state.game_over() {
+        return game_state.score(game_state.current_mark()).unwrap();
+    }
+    if depth == 0 {
+        return 0;
+    }
 
-        if beta <= alpha {
+    let mut best_score = -i32::MAX;
+    for move_ in ordered_moves(game_state) {
+        let score = -search(move_.after_state(), -beta, -alpha, depth - 1, nodes);
+        best_score = best_score.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
             break; // alpha-beta pruning
         }
     }
 
     best_score
 }
+
+/// Returns the possible moves of the game state, the most promising first:
+/// the center, then the corners, then the edges.
+/// Searching the strongest moves first lets alpha-beta pruning cut more of the tree.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to get the moves of.
+fn ordered_moves(game_state: &GameState) -> Vec<GameMove> {
+    let mut moves = game_state.possible_moves();
+    moves.sort_by_key(|move_| cell_priority(move_.cell_index()));
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{Cell, Grid, Variant};
+
+    #[test]
+    fn test_find_best_move_takes_the_win() {
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+        ]));
+        let game_state = GameState::new(grid, None).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let best_move = find_best_move(&game_state, FULL_DEPTH, &mut rng, &mut 0).unwrap();
+        assert_eq!(best_move.cell_index(), 2);
+    }
+
+    #[test]
+    fn test_find_best_move_breaks_ties_at_random() {
+        // Against a cross in the center, only the corners draw.
+        let game_state = GameState::new(Grid::new(None), None)
+            .unwrap()
+            .make_move_to(4)
+            .unwrap()
+            .after_state()
+            .to_owned();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut chosen_cells: Vec<usize> = (0..20)
+            .map(|_| {
+                find_best_move(&game_state, FULL_DEPTH, &mut rng, &mut 0)
+                    .unwrap()
+                    .cell_index()
+            })
+            .collect();
+        chosen_cells.sort();
+        chosen_cells.dedup();
+        assert!(chosen_cells.len() > 1);
+        assert!(chosen_cells.iter().all(|cell| [0, 2, 6, 8].contains(cell)));
+    }
+
+    #[test]
+    fn test_same_seed_same_moves() {
+        let game_state = GameState::new(Grid::new(None), None)
+            .unwrap()
+            .make_move_to(4)
+            .unwrap()
+            .after_state()
+            .to_owned();
+        let mut player1 =
+            MinimaxPlayer::new_with_rng(Mark::Cross, Box::new(StdRng::seed_from_u64(7)));
+        let mut player2 =
+            MinimaxPlayer::new_with_rng(Mark::Cross, Box::new(StdRng::seed_from_u64(7)));
+        for _ in 0..5 {
+            assert_eq!(player1.get_move(&game_state), player2.get_move(&game_state));
+        }
+    }
+
+    #[test]
+    fn test_depth_limited_search_blocks() {
+        // Naught must block the first row, which a search of two plies sees.
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+        ]));
+        let game_state = GameState::new(grid, None).unwrap();
+        let settings = SearchSettings {
+            max_depth: Some(2),
+            ..SearchSettings::default()
+        };
+        let mut player = MinimaxPlayer::new_with_settings(
+            Mark::Naught,
+            Box::new(StdRng::seed_from_u64(0)),
+            settings,
+        );
+        assert_eq!(player.get_move(&game_state).unwrap().cell_index(), 2);
+    }
+
+    #[test]
+    fn test_negamax_depth_zero_is_a_tie() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(negamax(&game_state, -i32::MAX, i32::MAX, 0), 0);
+    }
+
+    #[test]
+    fn test_negamax_empty_grid_is_a_draw() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(negamax(&game_state, -i32::MAX, i32::MAX, FULL_DEPTH), 0);
+    }
+
+    #[test]
+    fn test_negamax_lost_position() {
+        // Cross threatens both the first row and the first column.
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+        ]));
+        let game_state = GameState::new(grid, None).unwrap();
+        assert_eq!(negamax(&game_state, -i32::MAX, i32::MAX, FULL_DEPTH), -1);
+    }
+
+    #[test]
+    fn test_negamax_notakto_first_player_wins() {
+        let game_state =
+            GameState::new_with_variant(Grid::new(None), None, Variant::Notakto).unwrap();
+        assert_eq!(negamax(&game_state, -i32::MAX, i32::MAX, FULL_DEPTH), 1);
+    }
+
+    #[test]
+    fn test_ordered_moves_center_then_corners() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let indexes: Vec<usize> = ordered_moves(&game_state)
+            .iter()
+            .map(|move_| move_.cell_index())
+            .collect();
+        assert_eq!(indexes, vec![4, 0, 2, 6, 8, 1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn test_opening_book_moves_are_best_moves() {
+        let empty = GameState::new(Grid::new(None), None).unwrap();
+        let mut positions = vec![empty];
+        positions.extend(
+            empty
+                .possible_moves()
+                .iter()
+                .map(|move_| *move_.after_state()),
+        );
+
+        for game_state in positions {
+            let best_score = ordered_moves(&game_state)
+                .iter()
+                .map(|move_| -negamax(move_.after_state(), -i32::MAX, i32::MAX, FULL_DEPTH))
+                .max()
+                .unwrap();
+            for cell_index in opening_book::book_cells(&game_state).unwrap() {
+                let move_ = game_state.make_move_to(cell_index).unwrap();
+                let score = -negamax(move_.after_state(), -i32::MAX, i32::MAX, FULL_DEPTH);
+                assert_eq!(score, best_score, "cell {} in {:?}", cell_index, game_state);
+            }
+        }
+    }
+}
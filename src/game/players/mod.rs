@@ -1,16 +1,52 @@
 //! This module contains the Player trait and the implementations of the players.
 
+use std::path::PathBuf;
+
+#[cfg(feature = "async")]
+use crate::game::asynchronous::AsyncPlayer;
 use crate::logic::{errors::MoveError, GameMove, GameState, Mark};
+pub mod builder;
+pub mod differential;
+pub mod external;
+pub mod heuristic;
+pub mod learning;
+pub mod mcts;
 pub mod minimax;
+pub mod opening_book;
+pub mod perfect;
+pub(crate) mod protocol;
 pub mod random;
+pub mod scripted;
+pub mod timed;
+
+/// A request of a player to the engine, made instead of a move.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PlayerCommand {
+    /// Save the game to the file, to resume it later.
+    Save(PathBuf),
+    /// Take back the last move of the player, and the moves played since.
+    Undo,
+    /// Play again the moves taken back by the last `Undo`.
+    Redo,
+    /// Leave the game, e.g. when the connection to the player is closed, the player forfeits.
+    Resign,
+}
 
 /// The Player trait defines the behavior of a player.
-/// A player trait has 3 methods:
+/// A player is `Send`, so a game owning its players can be played on another thread.
+/// A player trait has 7 methods, and an 8th with the `async` feature:
 /// - get_mark() returns the mark of the player
 /// - get_move() returns the next move of the player
 /// - make_move() returns the game state after the player has made a move
-pub trait Player {
-    fn make_move(&self, game_state: &GameState) -> Result<GameState, MoveError> {
+/// - name() returns the name of the player, its kind by default
+/// - kind() returns the kind of the player, such as "Human" or "Minimax AI"
+/// - take_command() returns the command the player made instead of their last move, if any
+/// - nodes_searched() returns the number of game states the player searched since it was created,
+///   0 for the players which do not search
+/// - as_async_player() returns the player as an `AsyncPlayer` whose moves `TicTacToe::play_async` awaits,
+///   `None` for the players which answer right away
+pub trait Player: Send {
+    fn make_move(&mut self, game_state: &GameState) -> Result<GameState, MoveError> {
         if self.get_mark() != game_state.current_mark() {
             return Err(MoveError::NotYourTurn(self.get_mark()));
         }
@@ -20,5 +56,112 @@ pub trait Player {
         Err(MoveError::NoPossibleMoves)
     }
     fn get_mark(&self) -> Mark;
-    fn get_move(&self, game_state: &GameState) -> Option<GameMove>;
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove>;
+    fn name(&self) -> String {
+        self.kind().to_string()
+    }
+    fn kind(&self) -> &'static str {
+        "Player"
+    }
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        None
+    }
+    fn nodes_searched(&self) -> u64 {
+        0
+    }
+    #[cfg(feature = "async")]
+    fn as_async_player(&mut self) -> Option<&mut dyn AsyncPlayer> {
+        None
+    }
+}
+
+/// A borrowed player plays as the player it borrows, so the engine can use players it does not own.
+impl<P: Player + ?Sized> Player for &mut P {
+    fn make_move(&mut self, game_state: &GameState) -> Result<GameState, MoveError> {
+        (**self).make_move(game_state)
+    }
+    fn get_mark(&self) -> Mark {
+        (**self).get_mark()
+    }
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        (**self).get_move(game_state)
+    }
+    fn name(&self) -> String {
+        (**self).name()
+    }
+    fn kind(&self) -> &'static str {
+        (**self).kind()
+    }
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        (**self).take_command()
+    }
+    fn nodes_searched(&self) -> u64 {
+        (**self).nodes_searched()
+    }
+    #[cfg(feature = "async")]
+    fn as_async_player(&mut self) -> Option<&mut dyn AsyncPlayer> {
+        (**self).as_async_player()
+    }
+}
+
+/// A boxed player plays as the player it owns, e.g. a `Box<dyn Player>` chosen at runtime.
+impl<P: Player + ?Sized> Player for Box<P> {
+    fn make_move(&mut self, game_state: &GameState) -> Result<GameState, MoveError> {
+        (**self).make_move(game_state)
+    }
+    fn get_mark(&self) -> Mark {
+        (**self).get_mark()
+    }
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        (**self).get_move(game_state)
+    }
+    fn name(&self) -> String {
+        (**self).name()
+    }
+    fn kind(&self) -> &'static str {
+        (**self).kind()
+    }
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        (**self).take_command()
+    }
+    fn nodes_searched(&self) -> u64 {
+        (**self).nodes_searched()
+    }
+    #[cfg(feature = "async")]
+    fn as_async_player(&mut self) -> Option<&mut dyn AsyncPlayer> {
+        (**self).as_async_player()
+    }
+}
+
+/// Returns the name of the player followed by its kind, e.g. "Alice (Human)",
+/// or only the name when it is the kind, e.g. "Minimax AI".
+///
+/// # Arguments
+///
+/// * `player` - The player to describe.
+pub fn display_name(player: &dyn Player) -> String {
+    let name = player.name();
+    if name == player.kind() {
+        name
+    } else {
+        format!("{} ({})", name, player.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::console::players::ConsolePlayer;
+    use crate::game::MinimaxPlayer;
+
+    #[test]
+    fn test_display_name() {
+        let human = ConsolePlayer::new(Mark::Cross).with_name("Alice");
+        assert_eq!(display_name(&human), "Alice (Human)");
+        assert_eq!(display_name(&ConsolePlayer::new(Mark::Cross)), "Human");
+        assert_eq!(
+            display_name(&MinimaxPlayer::new(Mark::Naught)),
+            "Minimax AI"
+        );
+    }
 }
@@ -0,0 +1,117 @@
+//! A small opening book for the first two plies of the game.
+//! It lists the moves of perfect play for the empty grid and for every reply to a first move,
+//! so the computer players can skip the longest searches of the game and vary their openings.
+//! The book only applies to the `Variant::Standard` rules, in the `GamePhase::Opening`.
+use rand::{seq::SliceRandom, RngCore};
+
+use crate::logic::{Cell, GameMove, GamePhase, GameState, Grid, Variant};
+
+/// The center cell of the grid.
+const CENTER: usize = Grid::SIZE / 2;
+/// The corner cells of the grid.
+const CORNERS: [usize; 4] = [0, Grid::WIDTH - 1, Grid::SIZE - Grid::WIDTH, Grid::SIZE - 1];
+
+/// Returns the cells the book recommends for the game state,
+/// or `None` if the game state is out of the book.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to look up.
+pub fn book_cells(game_state: &GameState) -> Option<Vec<usize>> {
+    if game_state.variant() != Variant::Standard || game_state.phase() != GamePhase::Opening {
+        return None;
+    }
+
+    let occupied: Vec<usize> = game_state
+        .grid()
+        .cells()
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| **cell != Cell::new_empty())
+        .map(|(index, _)| index)
+        .collect();
+
+    match occupied[..] {
+        [] => Some([vec![CENTER], CORNERS.to_vec()].concat()),
+        [first] if first == CENTER => Some(CORNERS.to_vec()),
+        [first] if CORNERS.contains(&first) => Some(vec![CENTER]),
+        [first] => {
+            let adjacent_corners = CORNERS.iter().copied().filter(|&corner| {
+                corner / Grid::WIDTH == first / Grid::WIDTH
+                    || corner % Grid::WIDTH == first % Grid::WIDTH
+            });
+            let opposite_edge = Grid::SIZE - 1 - first;
+            Some(
+                [
+                    vec![CENTER],
+                    adjacent_corners.collect(),
+                    vec![opposite_edge],
+                ]
+                .concat(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Returns one of the moves the book recommends for the game state, chosen at random,
+/// or `None` if the game state is out of the book.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to look up.
+/// * `rng` - The random number generator used to pick among the book moves.
+pub fn book_move(game_state: &GameState, rng: &mut dyn RngCore) -> Option<GameMove> {
+    let cells = book_cells(game_state)?;
+    let cell_index = cells.choose(rng)?;
+    game_state.make_move_to(*cell_index).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::Mark;
+
+    fn after_first_move(cell_index: usize) -> GameState {
+        *GameState::new(Grid::new(None), None)
+            .unwrap()
+            .make_move_to(cell_index)
+            .unwrap()
+            .after_state()
+    }
+
+    #[test]
+    fn test_book_cells_empty_grid() {
+        let game_state = GameState::new(Grid::new(None), Some(Mark::Naught)).unwrap();
+        assert_eq!(book_cells(&game_state), Some(vec![4, 0, 2, 6, 8]));
+    }
+
+    #[test]
+    fn test_book_cells_after_corner() {
+        assert_eq!(book_cells(&after_first_move(6)), Some(vec![4]));
+    }
+
+    #[test]
+    fn test_book_cells_after_center() {
+        assert_eq!(book_cells(&after_first_move(4)), Some(vec![0, 2, 6, 8]));
+    }
+
+    #[test]
+    fn test_book_cells_after_edge() {
+        assert_eq!(book_cells(&after_first_move(1)), Some(vec![4, 0, 2, 7]));
+        assert_eq!(book_cells(&after_first_move(5)), Some(vec![4, 2, 8, 3]));
+    }
+
+    #[test]
+    fn test_book_cells_out_of_book() {
+        let game_state = after_first_move(4).make_move_to(0).unwrap();
+        assert_eq!(book_cells(game_state.after_state()), None);
+    }
+
+    #[test]
+    fn test_book_cells_other_variant() {
+        let game_state =
+            GameState::new_with_variant(Grid::new(None), None, Variant::Notakto).unwrap();
+        assert_eq!(book_cells(&game_state), None);
+    }
+}
@@ -0,0 +1,223 @@
+//! A player that looks its moves up in a table of perfect play.
+//! The table is computed once per variant by valuing every reachable position with the solver,
+//! then each move is a single lookup instead of a new search.
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::{
+    game::players::Player,
+    logic::{
+        solver::{move_value, solve},
+        GameMove, GameState, Grid, Mark, Variant,
+    },
+};
+
+/// The solved table of every reachable position of a variant.
+pub struct PolicyTable {
+    entries: HashMap<u32, PolicyEntry>,
+}
+
+/// The value of a position and the moves reaching it.
+struct PolicyEntry {
+    /// The score of the position for the player whose turn it is: 1 for a win, 0 for a tie, -1 for a loss.
+    value: i32,
+    /// The cell index and the placed mark of every move of perfect play,
+    /// winning the soonest or losing the latest.
+    best_moves: Vec<(usize, Mark)>,
+}
+
+impl PolicyTable {
+    /// Solves every position reachable from the empty grid with the given variant,
+    /// whichever mark starts.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The variant of the rules to solve.
+    pub fn solve(variant: Variant) -> Self {
+        let mut table = PolicyTable {
+            entries: HashMap::new(),
+        };
+        let mut values = HashMap::new();
+        for starting_mark in [Mark::Cross, Mark::Naught] {
+            let game_state =
+                GameState::new_with_variant(Grid::new(None), Some(starting_mark), variant).unwrap();
+            table.add_position(&game_state, &mut values);
+        }
+        table
+    }
+
+    /// Returns the table of the given variant, solving it on first use.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The variant of the rules.
+    pub fn for_variant(variant: Variant) -> &'static PolicyTable {
+        static TABLES: [OnceLock<PolicyTable>; 4] = [const { OnceLock::new() }; 4];
+        let index = match variant {
+            Variant::Standard => 0,
+            Variant::Wild => 1,
+            Variant::Notakto => 2,
+            Variant::Gravity => 3,
+        };
+        TABLES[index].get_or_init(|| PolicyTable::solve(variant))
+    }
+
+    /// Returns the score of the game state for the player whose turn it is,
+    /// 1 for a win, 0 for a tie and -1 for a loss with perfect play from both players.
+    /// Returns `None` if the game state is not in the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game state to look up.
+    pub fn value(&self, game_state: &GameState) -> Option<i32> {
+        self.entries
+            .get(&game_state.position_key())
+            .map(|entry| entry.value)
+    }
+
+    /// Returns every move of perfect play from the game state,
+    /// or `None` if the game state is not in the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game state to look up.
+    pub fn best_moves(&self, game_state: &GameState) -> Option<Vec<GameMove>> {
+        let entry = self.entries.get(&game_state.position_key())?;
+        entry
+            .best_moves
+            .iter()
+            .map(|(cell_index, mark)| game_state.make_move_with(*cell_index, *mark).ok())
+            .collect()
+    }
+
+    /// Returns the number of positions in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the table has no position, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds the game state and the positions reachable from it which are not in the table yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game state to add.
+    /// * `values` - The values of the game states already solved, by position key.
+    fn add_position(&mut self, game_state: &GameState, values: &mut HashMap<u32, i32>) {
+        let key = game_state.position_key();
+        if self.entries.contains_key(&key) {
+            return;
+        }
+
+        let value = solve(game_state, values).signum();
+        let possible_moves = if game_state.game_over() {
+            vec![]
+        } else {
+            game_state.possible_moves()
+        };
+        let move_values: Vec<i32> = possible_moves
+            .iter()
+            .map(|game_move| move_value(game_move.after_state(), values))
+            .collect();
+        let best_value = move_values.iter().max().copied();
+        let best_moves = possible_moves
+            .iter()
+            .zip(&move_values)
+            .filter(|(_, value)| Some(**value) == best_value)
+            .map(|(game_move, _)| (game_move.cell_index(), *game_move.placed_mark()))
+            .collect();
+        self.entries.insert(key, PolicyEntry { value, best_moves });
+
+        for game_move in &possible_moves {
+            self.add_position(game_move.after_state(), values);
+        }
+    }
+}
+
+/// A player which plays the first move of perfect play found in the `PolicyTable`.
+pub struct PerfectPlayer {
+    mark: Mark,
+}
+
+impl PerfectPlayer {
+    /// Creates a new PerfectPlayer with the given mark.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn new(mark: Mark) -> Self {
+        PerfectPlayer { mark }
+    }
+}
+
+impl Player for PerfectPlayer {
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        PolicyTable::for_variant(game_state.variant())
+            .best_moves(game_state)?
+            .into_iter()
+            .next()
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn kind(&self) -> &'static str {
+        "Perfect AI"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_empty_grid_is_a_draw() {
+        let table = PolicyTable::for_variant(Variant::Standard);
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(table.value(&game_state), Some(0));
+        // 5478 positions are reachable for each starting mark.
+        assert_eq!(table.len(), 2 * 5478);
+    }
+
+    #[test]
+    fn test_perfect_player_takes_the_win() {
+        let grid = Grid::builder()
+            .mark(0, Mark::Cross)
+            .mark(1, Mark::Cross)
+            .mark(3, Mark::Naught)
+            .mark(4, Mark::Naught)
+            .build()
+            .unwrap();
+        let game_state = GameState::new(grid, None).unwrap();
+        let mut player = PerfectPlayer::new(Mark::Cross);
+        assert_eq!(player.get_move(&game_state).unwrap().cell_index(), 2);
+    }
+
+    #[test]
+    fn test_perfect_players_draw() {
+        let mut cross = PerfectPlayer::new(Mark::Cross);
+        let mut naught = PerfectPlayer::new(Mark::Naught);
+        let mut game_state = GameState::new(Grid::new(None), None).unwrap();
+        while !game_state.game_over() {
+            let player = if game_state.current_mark() == Mark::Cross {
+                &mut cross
+            } else {
+                &mut naught
+            };
+            game_state = player.make_move(&game_state).unwrap();
+        }
+        assert!(game_state.tie());
+    }
+
+    #[test]
+    fn test_notakto_first_player_wins() {
+        let table = PolicyTable::for_variant(Variant::Notakto);
+        let game_state =
+            GameState::new_with_variant(Grid::new(None), None, Variant::Notakto).unwrap();
+        assert_eq!(table.value(&game_state), Some(1));
+    }
+}
@@ -0,0 +1,151 @@
+//! The line protocol used to ask another program for a move.
+//! The two sides exchange one line at a time:
+//!
+//! - The asking side writes `position <cells> <mark> <variant>`, where `<cells>` lists the 9 cells row by row
+//!   as `X`, `O` or `.` for an empty cell, `<mark>` is the mark to play and `<variant>` is the variant of the rules,
+//!   e.g. `position X...O.... X standard`.
+//! - The answering side writes `move <index>` with the index of the chosen cell, from 0 to 8, e.g. `move 2`.
+//!   When the variant allows to place either mark, the mark can follow the index, e.g. `move 2 O`.
+use crate::logic::{Cell, GameMove, GameState, Grid, Mark, Variant};
+
+/// Returns the `position` line describing the game state.
+pub(crate) fn position_line(game_state: &GameState) -> String {
+    let cells: String = game_state
+        .grid()
+        .cells()
+        .iter()
+        .map(|cell| {
+            if *cell == Cell::new_empty() {
+                String::from(".")
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect();
+    format!(
+        "position {} {} {}",
+        cells,
+        game_state.current_mark(),
+        game_state.variant().name()
+    )
+}
+
+/// Parses a `position` line into the game state it describes.
+/// Returns `None` if the line is not a valid `position` line.
+pub(crate) fn parse_position_line(line: &str) -> Option<GameState> {
+    let mut words = line.split_whitespace();
+    if words.next()? != "position" {
+        return None;
+    }
+    let cells: Vec<Cell> = words
+        .next()?
+        .chars()
+        .map(|c| match c {
+            '.' => Some(Cell::new_empty()),
+            c => parse_mark(&c.to_string()).map(Cell::new_marked),
+        })
+        .collect::<Option<_>>()?;
+    let cells: [Cell; Grid::SIZE] = cells.try_into().ok()?;
+    let current_mark = parse_mark(words.next()?)?;
+    let variant = Variant::from_name(words.next()?)?;
+    if words.next().is_some() {
+        return None;
+    }
+
+    // The players take turns, so the starting mark follows from the number of marks placed.
+    let grid = Grid::new(Some(cells));
+    let starting_mark = if (Grid::SIZE - grid.empty_count()).is_multiple_of(2) {
+        current_mark
+    } else {
+        current_mark.other()
+    };
+    GameState::new_with_variant(grid, Some(starting_mark), variant).ok()
+}
+
+/// Returns the `move` line describing the move.
+pub(crate) fn move_line(game_move: &GameMove) -> String {
+    if game_move.placed_mark() == game_move.mark() {
+        format!("move {}", game_move.cell_index())
+    } else {
+        format!(
+            "move {} {}",
+            game_move.cell_index(),
+            game_move.placed_mark()
+        )
+    }
+}
+
+/// Parses a `move` line into the move it describes from the game state.
+/// Returns `None` if the line is not a valid `move` line or the move is not possible.
+pub(crate) fn parse_move_line(game_state: &GameState, line: &str) -> Option<GameMove> {
+    let mut words = line.split_whitespace();
+    if words.next()? != "move" {
+        return None;
+    }
+    let cell_index: usize = words.next()?.parse().ok()?;
+    if cell_index >= Grid::SIZE {
+        return None;
+    }
+    let placed_mark = match words.next() {
+        None => None,
+        Some(word) => Some(parse_mark(word)?),
+    };
+    if words.next().is_some() {
+        return None;
+    }
+    match placed_mark {
+        Some(mark) => game_state.make_move_with(cell_index, mark).ok(),
+        None => game_state.make_move_to(cell_index).ok(),
+    }
+}
+
+fn parse_mark(word: &str) -> Option<Mark> {
+    match word {
+        "X" => Some(Mark::Cross),
+        "O" => Some(Mark::Naught),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_line() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let game_state = *game_state.make_move_to(4).unwrap().after_state();
+        assert_eq!(position_line(&game_state), "position ....X.... O standard");
+        assert_eq!(
+            parse_position_line(&position_line(&game_state)),
+            Some(game_state)
+        );
+    }
+
+    #[test]
+    fn test_parse_position_line_starting_mark() {
+        let game_state = GameState::new(Grid::new(None), Some(Mark::Naught)).unwrap();
+        let game_state = *game_state.make_move_to(0).unwrap().after_state();
+        assert_eq!(
+            parse_position_line("position O........ X standard"),
+            Some(game_state)
+        );
+        assert_eq!(parse_position_line("position O........ X"), None);
+        assert_eq!(parse_position_line("position O... X standard"), None);
+    }
+
+    #[test]
+    fn test_parse_move_line() {
+        let game_state = GameState::new_with_variant(Grid::new(None), None, Variant::Wild).unwrap();
+        assert_eq!(
+            parse_move_line(&game_state, "move 4\n"),
+            game_state.make_move_to(4).ok()
+        );
+        let naught_move = game_state.make_move_with(2, Mark::Naught).unwrap();
+        assert_eq!(parse_move_line(&game_state, "move 2 O"), Some(naught_move));
+        assert_eq!(move_line(&naught_move), "move 2 O");
+        assert_eq!(parse_move_line(&game_state, "move 9"), None);
+        assert_eq!(parse_move_line(&game_state, "play 4"), None);
+        assert_eq!(parse_move_line(&game_state, "move 4 X extra"), None);
+    }
+}
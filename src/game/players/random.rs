@@ -1,29 +1,92 @@
+use rand::{rngs::StdRng, seq::SliceRandom, RngCore, SeedableRng};
+
 use crate::logic::{GameMove, GameState, Mark};
 
 use super::Player;
 
-/// A dumb player which take the first possible move to play
-/// Need to be changed to random
+/// A dumb player which plays a random possible move
 pub struct DumbPlayer {
     mark: Mark,
+    rng: Box<dyn RngCore + Send>,
 }
 
 impl DumbPlayer {
     pub fn new(mark: Mark) -> Self {
-        DumbPlayer { mark }
+        Self::new_with_rng(mark, Box::new(StdRng::from_entropy()))
+    }
+
+    /// Creates a new DumbPlayer whose moves are drawn from a generator seeded with the given seed,
+    /// so two players created with the same seed play the same moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `seed` - The seed of the random number generator.
+    pub fn new_with_seed(mark: Mark, seed: u64) -> Self {
+        Self::new_with_rng(mark, Box::new(StdRng::seed_from_u64(seed)))
+    }
+
+    /// Creates a new DumbPlayer whose moves are drawn from the given random number generator,
+    /// so two players created with generators seeded alike play the same moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `rng` - The random number generator the moves are drawn from.
+    pub fn new_with_rng(mark: Mark, rng: Box<dyn RngCore + Send>) -> Self {
+        DumbPlayer { mark, rng }
     }
 }
 
 impl Player for DumbPlayer {
-    fn get_move(&self, game_state: &GameState) -> Option<GameMove> {
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
         let moves = game_state.possible_moves();
-        if moves.is_empty() {
-            return None;
-        }
-        Some(moves[0])
+        moves.choose(&mut self.rng).copied()
     }
 
     fn get_mark(&self) -> Mark {
         self.mark
     }
+
+    fn kind(&self) -> &'static str {
+        "Random AI"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::Grid;
+
+    #[test]
+    fn test_get_move_game_over() {
+        let game_state = GameState::new(Grid::new(None), None)
+            .unwrap()
+            .apply_moves(&[0, 3, 1, 4, 2])
+            .unwrap();
+        let mut player = DumbPlayer::new(Mark::Naught);
+        assert_eq!(player.get_move(&game_state), None);
+    }
+
+    #[test]
+    fn test_same_seed_same_moves() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut player1 = DumbPlayer::new_with_seed(Mark::Cross, 3);
+        let mut player2 = DumbPlayer::new_with_seed(Mark::Cross, 3);
+        for _ in 0..10 {
+            assert_eq!(player1.get_move(&game_state), player2.get_move(&game_state));
+        }
+    }
+
+    #[test]
+    fn test_get_move_is_random() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut player = DumbPlayer::new_with_seed(Mark::Cross, 11);
+        let mut chosen_cells: Vec<usize> = (0..30)
+            .map(|_| player.get_move(&game_state).unwrap().cell_index())
+            .collect();
+        chosen_cells.sort();
+        chosen_cells.dedup();
+        assert!(chosen_cells.len() > 1);
+    }
 }
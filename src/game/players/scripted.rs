@@ -0,0 +1,153 @@
+//! A player that plays a predefined list of cells, in order.
+//! It makes the games deterministic, for the tests of the game loop and for the replays.
+use thiserror::Error;
+
+use crate::{
+    game::players::Player,
+    logic::{errors::MoveError, GameMove, GameState, Mark},
+};
+
+/// Why a script could not be followed.
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub enum ScriptError {
+    #[error("The script of `{0}` has no move left")]
+    NoMoveLeft(Mark),
+    #[error("Move {0} of the script is not possible")]
+    ImpossibleMove(usize, #[source] MoveError),
+}
+
+/// A player which plays the cells of its script, in order.
+pub struct ScriptedPlayer {
+    mark: Mark,
+    cell_indexes: Vec<usize>,
+    next: usize,
+    error: Option<ScriptError>,
+}
+
+impl ScriptedPlayer {
+    /// Creates a new ScriptedPlayer with the given mark and script.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    /// * `cell_indexes` - The indexes of the cells the player marks, in order.
+    pub fn new(mark: Mark, cell_indexes: Vec<usize>) -> Self {
+        ScriptedPlayer {
+            mark,
+            cell_indexes,
+            next: 0,
+            error: None,
+        }
+    }
+
+    /// Returns the number of moves of the script which are not played yet.
+    pub fn remaining(&self) -> usize {
+        self.cell_indexes.len() - self.next
+    }
+
+    /// Returns the error met when the script could not be followed, if any, and forgets it.
+    pub fn take_error(&mut self) -> Option<ScriptError> {
+        self.error.take()
+    }
+}
+
+impl Player for ScriptedPlayer {
+    /// Returns the next move of the script.
+    /// Returns None if the script has no move left or its next move is not possible,
+    /// as the game would not follow the script anymore; `take_error` tells why.
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        if game_state.game_over() {
+            return None;
+        }
+        let Some(&cell_index) = self.cell_indexes.get(self.next) else {
+            self.error = Some(ScriptError::NoMoveLeft(self.mark));
+            return None;
+        };
+        match game_state.make_move_to(cell_index) {
+            Ok(game_move) => {
+                self.next += 1;
+                Some(game_move)
+            }
+            Err(error) => {
+                self.error = Some(ScriptError::ImpossibleMove(self.next + 1, error));
+                None
+            }
+        }
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn kind(&self) -> &'static str {
+        "Scripted"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        game::{Renderer, TicTacToe},
+        logic::Grid,
+    };
+
+    struct RecordingRenderer {
+        game_states: Mutex<Vec<GameState>>,
+    }
+
+    impl Renderer for RecordingRenderer {
+        fn render(&self, game_state: &GameState) {
+            self.game_states.lock().unwrap().push(*game_state);
+        }
+    }
+
+    #[test]
+    fn test_scripted_game() {
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        let renderer = RecordingRenderer {
+            game_states: Mutex::new(vec![]),
+        };
+        TicTacToe::new(&mut cross, &mut naught, &renderer, None)
+            .unwrap()
+            .play(None);
+
+        let game_states = renderer.game_states.lock().unwrap();
+        assert_eq!(game_states.len(), 6);
+        assert_eq!(game_states.last().unwrap().winner_mark(), Some(Mark::Cross));
+        assert_eq!(cross.remaining(), 0);
+        assert_eq!(naught.remaining(), 0);
+    }
+
+    #[test]
+    fn test_illegal_scripted_move() {
+        let mut player = ScriptedPlayer::new(Mark::Cross, vec![0, 0]);
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let game_state = player.make_move(&game_state).unwrap();
+        let game_state = *game_state.make_move_to(4).unwrap().after_state();
+        assert!(player.get_move(&game_state).is_none());
+        assert_eq!(
+            player.take_error(),
+            Some(ScriptError::ImpossibleMove(
+                2,
+                MoveError::CellAlreadyMarked(0)
+            ))
+        );
+        assert!(player.take_error().is_none());
+        assert_eq!(player.remaining(), 1);
+    }
+
+    #[test]
+    fn test_exhausted_script() {
+        let mut player = ScriptedPlayer::new(Mark::Cross, vec![]);
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert!(player.get_move(&game_state).is_none());
+        assert_eq!(
+            player.take_error(),
+            Some(ScriptError::NoMoveLeft(Mark::Cross))
+        );
+    }
+}
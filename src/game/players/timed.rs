@@ -0,0 +1,235 @@
+//! A player that gives another player a limited time for each move.
+//! The inner player chooses its move on a separate thread, if the time runs out
+//! the timed player plays a random possible move instead, or forfeits.
+//! A late move is discarded, and the inner player is only asked again once it has answered.
+//! An inner player which panicked is never asked again, its moves all follow the policy.
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{
+    game::players::{Player, PlayerCommand},
+    logic::{GameMove, GameState, Mark},
+};
+
+/// What a `TimedPlayer` does when its inner player runs out of time.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub enum TimeoutPolicy {
+    /// Play a random possible move instead.
+    #[default]
+    RandomMove,
+    /// Play no move, the player forfeits the game.
+    Forfeit,
+}
+
+type InnerPlayer = Box<dyn Player + Send>;
+
+/// A player which enforces a time limit on the moves of another player.
+pub struct TimedPlayer {
+    mark: Mark,
+    /// The name and the kind of the inner player, known even while it is thinking.
+    name: String,
+    kind: &'static str,
+    /// The inner player, `None` while it is still thinking about a move which timed out.
+    inner: Option<InnerPlayer>,
+    /// The answer of the inner player to the move which timed out.
+    late_answer: Option<Receiver<(InnerPlayer, Option<GameMove>)>>,
+    time_limit: Duration,
+    policy: TimeoutPolicy,
+    timeouts: usize,
+    rng: StdRng,
+}
+
+impl TimedPlayer {
+    /// Creates a new TimedPlayer giving the inner player the time limit for each move.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The player choosing the moves.
+    /// * `time_limit` - The time the inner player has for each move.
+    /// * `policy` - What to do when the inner player runs out of time.
+    pub fn new(inner: InnerPlayer, time_limit: Duration, policy: TimeoutPolicy) -> Self {
+        TimedPlayer {
+            mark: inner.get_mark(),
+            name: inner.name(),
+            kind: inner.kind(),
+            inner: Some(inner),
+            late_answer: None,
+            time_limit,
+            policy,
+            timeouts: 0,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Returns the number of moves for which the inner player ran out of time.
+    pub fn timeouts(&self) -> usize {
+        self.timeouts
+    }
+
+    /// Gets the inner player back if it answered the move which timed out.
+    fn recover_inner(&mut self) {
+        if let Some(late_answer) = &self.late_answer {
+            if let Ok((inner, _)) = late_answer.try_recv() {
+                self.inner = Some(inner);
+                self.late_answer = None;
+            }
+        }
+    }
+
+    /// Plays the move chosen by the `TimeoutPolicy`.
+    fn time_out(&mut self, game_state: &GameState) -> Option<GameMove> {
+        self.timeouts += 1;
+        match self.policy {
+            TimeoutPolicy::RandomMove => game_state.possible_moves().choose(&mut self.rng).copied(),
+            TimeoutPolicy::Forfeit => None,
+        }
+    }
+}
+
+impl Player for TimedPlayer {
+    fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+        self.recover_inner();
+        let Some(mut inner) = self.inner.take() else {
+            return self.time_out(game_state);
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let game_state_copy = *game_state;
+        thread::spawn(move || {
+            let next_move = inner.get_move(&game_state_copy);
+            // The timed player may be gone, the answer is then dropped with the player.
+            let _ = sender.send((inner, next_move));
+        });
+
+        match receiver.recv_timeout(self.time_limit) {
+            Ok((inner, next_move)) => {
+                self.inner = Some(inner);
+                next_move
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                self.late_answer = Some(receiver);
+                self.time_out(game_state)
+            }
+            // The inner player panicked and is gone with its thread.
+            Err(RecvTimeoutError::Disconnected) => self.time_out(game_state),
+        }
+    }
+
+    fn get_mark(&self) -> Mark {
+        self.mark
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    fn take_command(&mut self) -> Option<PlayerCommand> {
+        self.inner.as_mut()?.take_command()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::HeuristicPlayer, logic::Grid};
+
+    struct SlowPlayer {
+        mark: Mark,
+        delay: Duration,
+    }
+
+    impl Player for SlowPlayer {
+        fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+            thread::sleep(self.delay);
+            game_state.make_move_to(0).ok()
+        }
+
+        fn get_mark(&self) -> Mark {
+            self.mark
+        }
+    }
+
+    #[test]
+    fn test_move_in_time() {
+        let mut player = TimedPlayer::new(
+            Box::new(HeuristicPlayer::new(Mark::Cross)),
+            Duration::from_secs(5),
+            TimeoutPolicy::Forfeit,
+        );
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(player.get_move(&game_state).unwrap().cell_index(), 4);
+        assert_eq!(player.timeouts(), 0);
+        assert_eq!(player.kind(), "Heuristic AI");
+    }
+
+    #[test]
+    fn test_timeout_forfeits() {
+        let slow = SlowPlayer {
+            mark: Mark::Cross,
+            delay: Duration::from_millis(200),
+        };
+        let mut player = TimedPlayer::new(
+            Box::new(slow),
+            Duration::from_millis(10),
+            TimeoutPolicy::Forfeit,
+        );
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(player.get_move(&game_state), None);
+        assert_eq!(player.timeouts(), 1);
+    }
+
+    #[test]
+    fn test_timeout_plays_a_random_move() {
+        let slow = SlowPlayer {
+            mark: Mark::Cross,
+            delay: Duration::from_millis(200),
+        };
+        let mut player = TimedPlayer::new(
+            Box::new(slow),
+            Duration::from_millis(10),
+            TimeoutPolicy::RandomMove,
+        );
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert!(player.get_move(&game_state).is_some());
+        // The inner player is still thinking, so the next move times out right away.
+        assert!(player.get_move(&game_state).is_some());
+        assert_eq!(player.timeouts(), 2);
+        assert_eq!(player.kind(), "Player");
+
+        thread::sleep(Duration::from_millis(300));
+        player.recover_inner();
+        assert!(player.inner.is_some());
+    }
+
+    struct PanickingPlayer;
+
+    impl Player for PanickingPlayer {
+        fn get_move(&mut self, _game_state: &GameState) -> Option<GameMove> {
+            panic!("The panicking player cannot move")
+        }
+
+        fn get_mark(&self) -> Mark {
+            Mark::Cross
+        }
+    }
+
+    #[test]
+    fn test_panicking_inner_player() {
+        let mut player = TimedPlayer::new(
+            Box::new(PanickingPlayer),
+            Duration::from_secs(5),
+            TimeoutPolicy::RandomMove,
+        );
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert!(player.get_move(&game_state).is_some());
+        assert!(player.get_move(&game_state).is_some());
+        assert_eq!(player.timeouts(), 2);
+    }
+}
@@ -0,0 +1,303 @@
+//! The record of a played game, written in a text format similar to the PGN of chess games.
+//! The record starts with tags in brackets, one per line, followed by an empty line and the moves,
+//! numbered by pairs and written with the coordinates typed in the cli, e.g.
+//!
+//! ```text
+//! [X "Alice (Human)"]
+//! [O "Minimax AI"]
+//! [Variant "standard"]
+//! [StartingMark "X"]
+//! [Result "1-0"]
+//!
+//! 1. A1 A2 2. B1 B2 3. C1 1-0
+//! ```
+//!
+//! The result is `1-0` when the cross wins, `0-1` when the naught wins, `1/2-1/2` for a tie
+//! and `*` for a game which is not over.
+//! When the variant allows to place either mark, the placed mark follows the coordinates of the move, e.g. `B2O`.
+//! A game which did not start from an empty grid has a `Position` tag in the compact text notation.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::logic::{GameMove, GameState, Grid, Mark, Variant};
+
+/// A played game, with the players, the moves and the result.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GameRecord {
+    cross: String,
+    naught: String,
+    initial_state: GameState,
+    moves: Vec<GameMove>,
+}
+
+impl GameRecord {
+    /// Creates a new `GameRecord` of the moves played from the initial game state.
+    ///
+    /// # Arguments
+    ///
+    /// * `cross` - The name of the player of the crosses.
+    /// * `naught` - The name of the player of the naughts.
+    /// * `initial_state` - The game state the game started from.
+    /// * `moves` - The moves played, in order.
+    pub fn new(cross: &str, naught: &str, initial_state: GameState, moves: &[GameMove]) -> Self {
+        GameRecord {
+            cross: cross.to_string(),
+            naught: naught.to_string(),
+            initial_state,
+            moves: moves.to_vec(),
+        }
+    }
+
+    /// Returns the name of the player of the mark.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn player(&self, mark: Mark) -> &str {
+        match mark {
+            Mark::Cross => &self.cross,
+            Mark::Naught => &self.naught,
+        }
+    }
+
+    /// Returns the game state the game started from.
+    pub fn initial_state(&self) -> &GameState {
+        &self.initial_state
+    }
+
+    /// Returns the moves played, in order.
+    pub fn moves(&self) -> &[GameMove] {
+        &self.moves
+    }
+
+    /// Returns the game state the game started from, followed by the game state after each move.
+    pub fn states(&self) -> Vec<GameState> {
+        let after_states = self.moves.iter().map(|game_move| *game_move.after_state());
+        std::iter::once(self.initial_state)
+            .chain(after_states)
+            .collect()
+    }
+
+    /// Returns the game state after the last move.
+    pub fn final_state(&self) -> GameState {
+        self.moves
+            .last()
+            .map_or(self.initial_state, |game_move| *game_move.after_state())
+    }
+
+    /// Returns the result of the game: `1-0`, `0-1`, `1/2-1/2` or `*` if the game is not over.
+    pub fn result(&self) -> &'static str {
+        let final_state = self.final_state();
+        match final_state.winner_mark() {
+            Some(Mark::Cross) => "1-0",
+            Some(Mark::Naught) => "0-1",
+            None if final_state.tie() => "1/2-1/2",
+            None => "*",
+        }
+    }
+
+    /// Writes the record to the file, replacing its content.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Reads a record written by `GameRecord::save` from the file.
+    /// Returns an error if the file cannot be read, or if a tag is invalid or a move is not possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        GameRecord::parse(&content).map_err(|message| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid game record: {}", message),
+            )
+        })
+    }
+
+    /// Parses the text of a record, or returns the reason why it is invalid.
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        let mut cross = String::new();
+        let mut naught = String::new();
+        let mut variant = Variant::Standard;
+        let mut starting_mark = Mark::Cross;
+        let mut position = None;
+        let mut lines = text.lines();
+
+        for line in lines.by_ref() {
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line
+                .strip_prefix('[')
+                .and_then(|tag| tag.strip_suffix("\"]"))
+                .and_then(|tag| tag.split_once(" \""))
+                .ok_or_else(|| format!("`{}` is not a tag", line))?;
+            match name {
+                "X" => cross = value.to_string(),
+                "O" => naught = value.to_string(),
+                "Variant" => {
+                    variant = Variant::from_name(value)
+                        .ok_or_else(|| format!("`{}` is not a variant", value))?
+                }
+                "StartingMark" => {
+                    starting_mark = match value {
+                        "X" => Mark::Cross,
+                        "O" => Mark::Naught,
+                        _ => return Err(format!("`{}` is not a mark", value)),
+                    }
+                }
+                "Position" => {
+                    position = Some(GameState::from_notation(value).map_err(|e| e.to_string())?)
+                }
+                // The result follows from the moves, the unknown tags are ignored.
+                _ => {}
+            }
+        }
+
+        let initial_state = match position {
+            Some(position) => position,
+            None => GameState::new_with_variant(Grid::new(None), Some(starting_mark), variant)
+                .map_err(|error| error.to_string())?,
+        };
+        let mut record = GameRecord::new(&cross, &naught, initial_state, &[]);
+        for word in lines.flat_map(|line| line.split_whitespace()) {
+            if word.ends_with('.') || ["1-0", "0-1", "1/2-1/2", "*"].contains(&word) {
+                continue;
+            }
+            let game_move = parse_move_text(&record.final_state(), word)
+                .ok_or_else(|| format!("`{}` is not a possible move", word))?;
+            record.moves.push(game_move);
+        }
+        Ok(record)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "[X \"{}\"]", self.cross)?;
+        writeln!(f, "[O \"{}\"]", self.naught)?;
+        writeln!(f, "[Variant \"{}\"]", self.initial_state.variant().name())?;
+        writeln!(
+            f,
+            "[StartingMark \"{}\"]",
+            self.initial_state.starting_mark()
+        )?;
+        if !self.initial_state.game_not_started() {
+            writeln!(f, "[Position \"{}\"]", self.initial_state.to_notation())?;
+        }
+        writeln!(f, "[Result \"{}\"]", self.result())?;
+        writeln!(f)?;
+
+        for (i, game_move) in self.moves.iter().enumerate() {
+            if i % 2 == 0 {
+                write!(f, "{}. ", i / 2 + 1)?;
+            }
+            write!(f, "{} ", move_text(game_move))?;
+        }
+        writeln!(f, "{}", self.result())
+    }
+}
+
+/// Returns the coordinates of the move, e.g. `B2`, followed by the placed mark
+/// when it is not the mark of the player, e.g. `B2O`.
+fn move_text(game_move: &GameMove) -> String {
+    if game_move.placed_mark() == game_move.mark() {
+        game_move.coordinate()
+    } else {
+        format!("{}{}", game_move.coordinate(), game_move.placed_mark())
+    }
+}
+
+/// Parses the coordinates of a move written by `move_text`, e.g. `B2` or `B2O`, into the move from the game state.
+/// Returns `None` if the text is not a move or the move is not possible.
+fn parse_move_text(game_state: &GameState, text: &str) -> Option<GameMove> {
+    let chars: Vec<char> = text.chars().collect();
+    let column = match chars.first()? {
+        c @ 'A'..='C' => *c as usize - 'A' as usize,
+        _ => return None,
+    };
+    let row = match chars.get(1)? {
+        c @ '1'..='3' => *c as usize - '1' as usize,
+        _ => return None,
+    };
+    let cell_index = row * Grid::WIDTH + column;
+    match chars.get(2..)? {
+        [] => game_state.make_move_to(cell_index).ok(),
+        ['X'] => game_state.make_move_with(cell_index, Mark::Cross).ok(),
+        ['O'] => game_state.make_move_with(cell_index, Mark::Naught).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::Variant;
+
+    #[test]
+    fn test_record_text() {
+        let initial_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut moves = vec![];
+        let mut game_state = initial_state;
+        for cell_index in [0, 3, 1, 4, 2] {
+            let game_move = game_state.make_move_to(cell_index).unwrap();
+            game_state = *game_move.after_state();
+            moves.push(game_move);
+        }
+        let record = GameRecord::new("Alice (Human)", "Minimax AI", initial_state, &moves);
+
+        assert_eq!(record.result(), "1-0");
+        assert_eq!(
+            record.to_string(),
+            "[X \"Alice (Human)\"]\n\
+             [O \"Minimax AI\"]\n\
+             [Variant \"standard\"]\n\
+             [StartingMark \"X\"]\n\
+             [Result \"1-0\"]\n\
+             \n\
+             1. A1 A2 2. B1 B2 3. C1 1-0\n"
+        );
+    }
+
+    #[test]
+    fn test_record_text_from_a_position() {
+        let initial_state = GameState::from_notation(".../.X./... O wild").unwrap();
+        let game_move = initial_state.make_move_with(0, Mark::Cross).unwrap();
+        let record = GameRecord::new("X", "O", initial_state, &[game_move]);
+
+        assert_eq!(record.initial_state().variant(), Variant::Wild);
+        assert_eq!(record.result(), "*");
+        assert!(record
+            .to_string()
+            .ends_with("[Position \".../.X./... O wild\"]\n[Result \"*\"]\n\n1. A1X *\n"));
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let initial_state = GameState::from_notation(".../.X./... O wild").unwrap();
+        let first_move = initial_state.make_move_with(0, Mark::Cross).unwrap();
+        let second_move = first_move.after_state().make_move_to(8).unwrap();
+        let record = GameRecord::new(
+            "Alice (Human)",
+            "Bob",
+            initial_state,
+            &[first_move, second_move],
+        );
+
+        assert_eq!(GameRecord::parse(&record.to_string()), Ok(record.clone()));
+        assert_eq!(record.states().len(), 3);
+        assert!(GameRecord::parse("[X \"Alice\"]\n\n1. A1 A1 *\n").is_err());
+        assert!(GameRecord::parse("X Alice\n").is_err());
+    }
+}
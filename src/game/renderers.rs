@@ -1,8 +1,111 @@
 //! Renderers for the game.
-use crate::logic::GameState;
+use std::sync::Arc;
+
+use crate::{
+    game::{clock::Clocks, engine::GameResult, players::Player},
+    logic::{errors::MoveError, GameMove, GameState},
+};
 
 /// A trait for rendering the game.
-/// A renderer has a single method, render, which takes a game state and renders it.
-pub trait Renderer {
+/// A renderer has a method, render, which takes a game state and renders it.
+/// The engine calls render_with_players, which also gives the players of the game,
+/// so the renderer can show their names. By default, it only renders the game state.
+/// After each game state, the engine calls render_move with the move leading to it, if any,
+/// and render_invalid with the reason the last move was rejected, if it was.
+/// When the game is played with a time control, the engine then calls render_clocks.
+/// The engine also calls render_start before the first game state and render_end with the result of the game.
+/// These methods show nothing by default.
+/// A renderer is `Send` and `Sync`, so a game owning or borrowing it can be played on another thread.
+pub trait Renderer: Send + Sync {
     fn render(&self, game_state: &GameState);
+    fn render_with_players(&self, game_state: &GameState, _players: &[&dyn Player]) {
+        self.render(game_state);
+    }
+    fn render_clocks(&self, _clocks: &Clocks) {}
+    fn render_start(&self, _game_state: &GameState) {}
+    fn render_move(&self, _game_move: &GameMove) {}
+    fn render_invalid(&self, _error: &MoveError) {}
+    fn render_end(&self, _result: &GameResult) {}
+}
+
+/// A renderer which shows nothing, for the games played headlessly such as tournaments.
+pub(crate) struct NoRenderer;
+
+impl Renderer for NoRenderer {
+    fn render(&self, _game_state: &GameState) {}
+}
+
+/// A borrowed renderer renders as the renderer it borrows.
+impl<R: Renderer + ?Sized> Renderer for &R {
+    fn render(&self, game_state: &GameState) {
+        (**self).render(game_state);
+    }
+    fn render_with_players(&self, game_state: &GameState, players: &[&dyn Player]) {
+        (**self).render_with_players(game_state, players);
+    }
+    fn render_clocks(&self, clocks: &Clocks) {
+        (**self).render_clocks(clocks);
+    }
+    fn render_start(&self, game_state: &GameState) {
+        (**self).render_start(game_state);
+    }
+    fn render_move(&self, game_move: &GameMove) {
+        (**self).render_move(game_move);
+    }
+    fn render_invalid(&self, error: &MoveError) {
+        (**self).render_invalid(error);
+    }
+    fn render_end(&self, result: &GameResult) {
+        (**self).render_end(result);
+    }
+}
+
+/// A boxed renderer renders as the renderer it owns.
+impl<R: Renderer + ?Sized> Renderer for Box<R> {
+    fn render(&self, game_state: &GameState) {
+        (**self).render(game_state);
+    }
+    fn render_with_players(&self, game_state: &GameState, players: &[&dyn Player]) {
+        (**self).render_with_players(game_state, players);
+    }
+    fn render_clocks(&self, clocks: &Clocks) {
+        (**self).render_clocks(clocks);
+    }
+    fn render_start(&self, game_state: &GameState) {
+        (**self).render_start(game_state);
+    }
+    fn render_move(&self, game_move: &GameMove) {
+        (**self).render_move(game_move);
+    }
+    fn render_invalid(&self, error: &MoveError) {
+        (**self).render_invalid(error);
+    }
+    fn render_end(&self, result: &GameResult) {
+        (**self).render_end(result);
+    }
+}
+
+/// A shared renderer renders as the renderer it points to, e.g. one renderer for several games.
+impl<R: Renderer + ?Sized> Renderer for Arc<R> {
+    fn render(&self, game_state: &GameState) {
+        (**self).render(game_state);
+    }
+    fn render_with_players(&self, game_state: &GameState, players: &[&dyn Player]) {
+        (**self).render_with_players(game_state, players);
+    }
+    fn render_clocks(&self, clocks: &Clocks) {
+        (**self).render_clocks(clocks);
+    }
+    fn render_start(&self, game_state: &GameState) {
+        (**self).render_start(game_state);
+    }
+    fn render_move(&self, game_move: &GameMove) {
+        (**self).render_move(game_move);
+    }
+    fn render_invalid(&self, error: &MoveError) {
+        (**self).render_invalid(error);
+    }
+    fn render_end(&self, result: &GameResult) {
+        (**self).render_end(result);
+    }
 }
@@ -0,0 +1,58 @@
+//! The replay of a recorded game, rendering the game states one after the other.
+//! The pace of the replay is left to the caller, which waits between two game states,
+//! e.g. for a delay or until the user presses Enter.
+
+use super::record::GameRecord;
+use super::renderers::Renderer;
+
+/// Renders the game states of the record in order, waiting between two game states.
+///
+/// # Arguments
+///
+/// * `record` - The record of the game to replay.
+/// * `renderer` - The renderer used to display the game states.
+/// * `wait` - Called after each game state but the last one, returns once the next one can be rendered.
+pub fn replay(record: &GameRecord, renderer: &dyn Renderer, wait: &mut dyn FnMut()) {
+    let states = record.states();
+    for (i, game_state) in states.iter().enumerate() {
+        renderer.render(game_state);
+        if i + 1 < states.len() {
+            wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::logic::{GameState, Grid};
+
+    struct RecordingRenderer {
+        game_states: Mutex<Vec<GameState>>,
+    }
+
+    impl Renderer for RecordingRenderer {
+        fn render(&self, game_state: &GameState) {
+            self.game_states.lock().unwrap().push(*game_state);
+        }
+    }
+
+    #[test]
+    fn test_replay_renders_every_state() {
+        let initial_state = GameState::new(Grid::new(None), None).unwrap();
+        let first_move = initial_state.make_move_to(4).unwrap();
+        let second_move = first_move.after_state().make_move_to(0).unwrap();
+        let record = GameRecord::new("X", "O", initial_state, &[first_move, second_move]);
+        let renderer = RecordingRenderer {
+            game_states: Mutex::new(vec![]),
+        };
+
+        let mut waits = 0;
+        replay(&record, &renderer, &mut || waits += 1);
+
+        assert_eq!(*renderer.game_states.lock().unwrap(), record.states());
+        assert_eq!(waits, 2);
+    }
+}
@@ -0,0 +1,278 @@
+//! A game saved to a file, so an interrupted game can be resumed later.
+//! The file starts with the initial game state in the compact text notation, e.g. `.../.../... X`,
+//! followed by one line for each move played since, in the `move <index>` format of the line protocol.
+//! The moves can be taken back and played again, the moves taken back are not saved.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::logic::{GameMove, GameState};
+
+use super::players::protocol::{move_line, parse_move_line};
+
+/// A game as its initial state and the moves played since.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedGame {
+    initial_state: GameState,
+    moves: Vec<GameMove>,
+    /// The moves taken back, the last one first.
+    undone: Vec<GameMove>,
+}
+
+impl SavedGame {
+    /// Creates a new `SavedGame` with no move played yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_state` - The game state the game started from.
+    pub fn new(initial_state: GameState) -> Self {
+        SavedGame {
+            initial_state,
+            moves: vec![],
+            undone: vec![],
+        }
+    }
+
+    /// Adds a move played from the current game state.
+    /// The moves taken back cannot be played again afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_move` - The move played, its before state shall be the current game state.
+    pub(crate) fn push(&mut self, game_move: GameMove) {
+        self.moves.push(game_move);
+        self.undone.clear();
+    }
+
+    /// Takes back the last move and returns it, or returns `None` if no move was played.
+    pub fn undo(&mut self) -> Option<GameMove> {
+        let game_move = self.moves.pop()?;
+        self.undone.push(game_move);
+        Some(game_move)
+    }
+
+    /// Plays again the last move taken back and returns it,
+    /// or returns `None` if no move was taken back since the last move played.
+    pub fn redo(&mut self) -> Option<GameMove> {
+        let game_move = self.undone.pop()?;
+        self.moves.push(game_move);
+        Some(game_move)
+    }
+
+    /// Returns the game state the game started from.
+    pub fn initial_state(&self) -> &GameState {
+        &self.initial_state
+    }
+
+    /// Returns the moves played since the initial game state, in order.
+    pub fn moves(&self) -> &[GameMove] {
+        &self.moves
+    }
+
+    /// Returns the game state after the last move, or the initial game state if no move was played.
+    pub fn current_state(&self) -> GameState {
+        self.moves
+            .last()
+            .map_or(self.initial_state, |game_move| *game_move.after_state())
+    }
+
+    /// Writes the game to the file, replacing its content.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut content = format!("{}\n", self.initial_state.to_notation());
+        for game_move in &self.moves {
+            content.push_str(&move_line(game_move));
+            content.push('\n');
+        }
+        fs::write(path, content)
+    }
+
+    /// Reads a game written by `SavedGame::save` from the file.
+    /// Returns an error if the file cannot be read, or if a line is invalid or a move is not possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let mut lines = content.lines();
+        let initial_state = GameState::from_notation(lines.next().unwrap_or_default())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+
+        let mut saved_game = SavedGame::new(initial_state);
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            match parse_move_line(&saved_game.current_state(), line) {
+                Some(game_move) => saved_game.push(game_move),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid move in the saved game: `{}`", line),
+                    ))
+                }
+            }
+        }
+        Ok(saved_game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+    use crate::game::{renderers::NoRenderer, Player, PlayerCommand, ScriptedPlayer, TicTacToe};
+    use crate::logic::{Grid, Mark, Variant};
+
+    /// A scripted player which sends a command before the move of its script
+    /// when `before_remaining` moves are left.
+    struct CommandingPlayer {
+        scripted: ScriptedPlayer,
+        before_remaining: usize,
+        pending: Option<PlayerCommand>,
+        command: Option<PlayerCommand>,
+    }
+
+    impl CommandingPlayer {
+        fn new(scripted: ScriptedPlayer, before_remaining: usize, command: PlayerCommand) -> Self {
+            CommandingPlayer {
+                scripted,
+                before_remaining,
+                pending: Some(command),
+                command: None,
+            }
+        }
+    }
+
+    impl Player for CommandingPlayer {
+        fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+            if self.scripted.remaining() == self.before_remaining && self.pending.is_some() {
+                self.command = self.pending.take();
+                return None;
+            }
+            self.scripted.get_move(game_state)
+        }
+
+        fn get_mark(&self) -> Mark {
+            self.scripted.get_mark()
+        }
+
+        fn take_command(&mut self) -> Option<PlayerCommand> {
+            self.command.take()
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let initial_state =
+            GameState::new_with_variant(Grid::new(None), Some(Mark::Naught), Variant::Wild)
+                .unwrap();
+        let mut saved_game = SavedGame::new(initial_state);
+        let first_move = initial_state.make_move_to(4).unwrap();
+        saved_game.push(first_move);
+        let second_move = first_move
+            .after_state()
+            .make_move_with(0, Mark::Naught)
+            .unwrap();
+        saved_game.push(second_move);
+
+        let path = env::temp_dir().join(format!("saved_game_{}.txt", process::id()));
+        saved_game.save(&path).unwrap();
+        let loaded = SavedGame::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded, saved_game);
+        assert_eq!(loaded.current_state(), *second_move.after_state());
+    }
+
+    #[test]
+    fn test_load_invalid_move() {
+        let path = env::temp_dir().join(format!("invalid_saved_game_{}.txt", process::id()));
+        fs::write(&path, ".../.../... X\nmove 4\nmove 4\n").unwrap();
+        let loaded = SavedGame::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_engine_saves_and_resumes() {
+        let path = env::temp_dir().join(format!("engine_saved_game_{}.txt", process::id()));
+        let mut cross = CommandingPlayer::new(
+            ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2]),
+            2,
+            PlayerCommand::Save(path.clone()),
+        );
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4]);
+        TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .play(None);
+        let loaded = SavedGame::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.moves().len(), 2);
+        assert_eq!(loaded.current_state().to_notation(), "X../O../... X");
+
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![1, 2]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![4]);
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None).unwrap();
+        game.resume(loaded);
+        let cell_indexes: Vec<usize> = game
+            .history()
+            .iter()
+            .map(|game_move| game_move.cell_index())
+            .collect();
+        assert_eq!(cell_indexes, vec![0, 3, 1, 4, 2]);
+        drop(game);
+        assert_eq!(cross.remaining(), 0);
+        assert_eq!(naught.remaining(), 0);
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let initial_state = GameState::new(Grid::new(None), None).unwrap();
+        let mut saved_game = SavedGame::new(initial_state);
+        let first_move = initial_state.make_move_to(4).unwrap();
+        saved_game.push(first_move);
+        saved_game.push(first_move.after_state().make_move_to(0).unwrap());
+
+        assert_eq!(saved_game.undo().unwrap().cell_index(), 0);
+        assert_eq!(saved_game.undo(), Some(first_move));
+        assert_eq!(saved_game.undo(), None);
+        assert_eq!(saved_game.current_state(), initial_state);
+
+        assert_eq!(saved_game.redo(), Some(first_move));
+        saved_game.push(first_move.after_state().make_move_to(8).unwrap());
+        assert_eq!(saved_game.redo(), None);
+        assert_eq!(saved_game.current_state().to_notation(), ".../.X./..O X");
+    }
+
+    #[test]
+    fn test_engine_undoes_the_last_move_of_the_player() {
+        let mut cross = CommandingPlayer::new(
+            ScriptedPlayer::new(Mark::Cross, vec![0, 0, 1, 2]),
+            3,
+            PlayerCommand::Undo,
+        );
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4, 5]);
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None).unwrap();
+        let result = game.play(None);
+        assert_eq!(result.winner, Some(Mark::Cross));
+        assert_eq!(result.moves, game.history());
+        let cell_indexes: Vec<usize> = game
+            .history()
+            .iter()
+            .map(|game_move| game_move.cell_index())
+            .collect();
+        assert_eq!(cell_indexes, vec![0, 4, 1, 5, 2]);
+        let record = game.record().unwrap();
+        assert_eq!(record.player(Mark::Naught), "Scripted");
+        assert_eq!(record.result(), "1-0");
+    }
+}
@@ -0,0 +1,139 @@
+//! The statistics of a session: the wins, losses and ties of each player across the games.
+//! The `Stats` follow the games as a `GameObserver`, and are written between the games with `Display`.
+//! The players keep their mark for the whole session, so they are told apart by their mark.
+
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::logic::Mark;
+
+use super::engine::{GameEnding, GameResult};
+use super::observers::GameObserver;
+
+/// The results of a player across the games of a session.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct PlayerStats {
+    /// The number of games won.
+    pub wins: usize,
+    /// The number of games lost.
+    pub losses: usize,
+    /// The number of games without a winner.
+    pub ties: usize,
+}
+
+impl PlayerStats {
+    /// Returns the number of games played.
+    pub fn games(&self) -> usize {
+        self.wins + self.losses + self.ties
+    }
+}
+
+/// The statistics of the players of a session, fed by the engine as an observer.
+/// The interrupted games are not counted.
+#[derive(Default, Debug)]
+pub struct Stats {
+    cross: Mutex<PlayerStats>,
+    naught: Mutex<PlayerStats>,
+}
+
+impl Stats {
+    /// Creates new `Stats`, without any game.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the results of the player of the mark.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player.
+    pub fn player(&self, mark: Mark) -> PlayerStats {
+        *self.lock(mark)
+    }
+
+    /// Returns the number of games counted.
+    pub fn games(&self) -> usize {
+        self.player(Mark::Cross).games()
+    }
+
+    /// Forgets every game counted.
+    pub fn reset(&self) {
+        *self.lock(Mark::Cross) = PlayerStats::default();
+        *self.lock(Mark::Naught) = PlayerStats::default();
+    }
+
+    fn lock(&self, mark: Mark) -> MutexGuard<'_, PlayerStats> {
+        let stats = match mark {
+            Mark::Cross => &self.cross,
+            Mark::Naught => &self.naught,
+        };
+        stats.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl GameObserver for Stats {
+    fn on_game_over(&self, result: &GameResult) {
+        if result.ending == GameEnding::Interrupted {
+            return;
+        }
+        for mark in [Mark::Cross, Mark::Naught] {
+            let mut stats = self.lock(mark);
+            match result.winner {
+                Some(winner) if winner == mark => stats.wins += 1,
+                Some(_) => stats.losses += 1,
+                None => stats.ties += 1,
+            }
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    /// Writes the results of both players, e.g. `X: 2 wins, 1 losses, 0 ties | O: 1 wins, 2 losses, 0 ties`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let results: Vec<String> = [Mark::Cross, Mark::Naught]
+            .iter()
+            .map(|mark| {
+                let stats = self.player(*mark);
+                format!(
+                    "{}: {} wins, {} losses, {} ties",
+                    mark, stats.wins, stats.losses, stats.ties
+                )
+            })
+            .collect();
+        write!(f, "{}", results.join(" | "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{renderers::NoRenderer, Match, ScriptedPlayer, TicTacToe};
+
+    #[test]
+    fn test_stats_follow_the_games() {
+        let stats = Stats::new();
+        // Each player wins the game they start, the third game is a tie.
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2, 0, 1, 0, 8, 6, 5, 1]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4, 3, 4, 5, 4, 2, 3, 7]);
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .with_observer(&stats);
+        Match::new(3).play(&mut game);
+
+        let expected = PlayerStats {
+            wins: 1,
+            losses: 1,
+            ties: 1,
+        };
+        assert_eq!(stats.player(Mark::Cross), expected);
+        assert_eq!(stats.player(Mark::Naught), expected);
+        assert_eq!(stats.games(), 3);
+        assert_eq!(
+            stats.to_string(),
+            "X: 1 wins, 1 losses, 1 ties | O: 1 wins, 1 losses, 1 ties"
+        );
+
+        stats.reset();
+        assert_eq!(stats.games(), 0);
+    }
+}
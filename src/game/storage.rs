@@ -0,0 +1,371 @@
+//! The history of the finished games, stored in a local JSON-lines file.
+//! Each line is a JSON object with the time the game ended, the players, the result,
+//! the winner and how the game ended, and the record of the game, e.g.
+//!
+//! ```text
+//! {"timestamp":1700000000,"x":"Alice (Human)","o":"Minimax AI","result":"0-1","winner":"O","ending":"forfeit","record":"[X \"Alice (Human)\"]\n..."}
+//! ```
+//!
+//! The winner and the ending come from the result of the game, as a game lost on time
+//! or by forfeit is not over on its board.
+//!
+//! The games are numbered from 1 in the order they were stored.
+//! The `GameStore` records the games as a `GameObserver`, and lists and loads the past games.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::logic::Mark;
+
+use super::engine::{GameEnding, GameResult};
+use super::json::escape_json;
+use super::observers::GameObserver;
+use super::record::GameRecord;
+
+/// A game of the history.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StoredGame {
+    /// The number of the game in the history, from 1.
+    pub id: usize,
+    /// The time the game was stored, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The record of the game, with the players and the moves.
+    pub record: GameRecord,
+    /// The mark of the winner, `None` for a tie or a game stopped without a winner.
+    pub winner: Option<Mark>,
+    /// How the game ended, e.g. `forfeit`, as written by `ending_name`.
+    pub ending: String,
+}
+
+impl StoredGame {
+    /// Returns the result of the game: `1-0`, `0-1`, `1/2-1/2` or `*` if it stopped without a winner.
+    pub fn result(&self) -> &'static str {
+        result_text(self.winner, &self.ending)
+    }
+
+    /// Returns `true` if the game has a winner, even by forfeit or on time, or ended in a tie,
+    /// `false` if it was aborted or interrupted.
+    pub fn decided(&self) -> bool {
+        self.result() != "*"
+    }
+}
+
+/// The history of the games, in a JSON-lines file.
+#[derive(Debug)]
+pub struct GameStore {
+    path: PathBuf,
+    /// The result of the game being recorded as an observer, until its record arrives.
+    result: Mutex<Option<GameResult>>,
+    /// The last error met while recording a game as an observer.
+    error: Mutex<Option<io::Error>>,
+}
+
+impl GameStore {
+    /// Creates a new `GameStore` for the file, which is created with the first game stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the JSON-lines file.
+    pub fn open(path: &Path) -> Self {
+        GameStore {
+            path: path.to_path_buf(),
+            result: Mutex::new(None),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// Appends the game to the history, with the current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The record of the finished game.
+    /// * `result` - The result of the game, with its winner and how it ended.
+    pub fn append(&self, record: &GameRecord, result: &GameResult) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let ending = ending_name(&result.ending);
+        let line = format!(
+            "{{\"timestamp\":{},\"x\":\"{}\",\"o\":\"{}\",\"result\":\"{}\",\"winner\":\"{}\",\"ending\":\"{}\",\"record\":\"{}\"}}\n",
+            timestamp,
+            escape_json(record.player(Mark::Cross)),
+            escape_json(record.player(Mark::Naught)),
+            result_text(result.winner, ending),
+            result.winner.map_or(String::new(), |mark| mark.to_string()),
+            ending,
+            escape_json(&record.to_string())
+        );
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())
+    }
+
+    /// Returns the games of the history, the oldest first, or no game if the file does not exist.
+    /// Returns an error if the file cannot be read or a line is invalid.
+    pub fn list(&self) -> io::Result<Vec<StoredGame>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error),
+        };
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                parse_stored_game(i + 1, line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid game {} in the history", i + 1),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the game of the history with the number, or `None` if there is no such game.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The number of the game, from 1.
+    pub fn load(&self, id: usize) -> io::Result<Option<StoredGame>> {
+        Ok(self.list()?.into_iter().find(|game| game.id == id))
+    }
+
+    /// Returns the last error met while recording a game as an observer, if any, and forgets it.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+impl GameObserver for GameStore {
+    fn on_game_over(&self, result: &GameResult) {
+        *self.result.lock().unwrap_or_else(PoisonError::into_inner) = Some(result.clone());
+    }
+
+    fn on_game_recorded(&self, record: &GameRecord) {
+        let Some(result) = self
+            .result
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        else {
+            return;
+        };
+        if let Err(error) = self.append(record, &result) {
+            *self.error.lock().unwrap_or_else(PoisonError::into_inner) = Some(error);
+        }
+    }
+}
+
+/// Returns the name of the ending written in the history, e.g. `flag fall`.
+///
+/// # Arguments
+///
+/// * `ending` - How the game ended.
+pub fn ending_name(ending: &GameEnding) -> &'static str {
+    match ending {
+        GameEnding::Finished => "finished",
+        GameEnding::Forfeit(_) => "forfeit",
+        GameEnding::Aborted(_) => "aborted",
+        GameEnding::Interrupted => "interrupted",
+        GameEnding::FlagFall(_) => "flag fall",
+    }
+}
+
+/// Returns the result of a game: `1-0`, `0-1`, `1/2-1/2` or `*` if it stopped without a winner.
+fn result_text(winner: Option<Mark>, ending: &str) -> &'static str {
+    match winner {
+        Some(Mark::Cross) => "1-0",
+        Some(Mark::Naught) => "0-1",
+        None if ending == ending_name(&GameEnding::Finished) => "1/2-1/2",
+        None => "*",
+    }
+}
+
+/// Parses a line of the history into the game with the number.
+/// The lines written before the winner and the ending were stored take them from the record.
+fn parse_stored_game(id: usize, line: &str) -> Option<StoredGame> {
+    let fields = parse_object(line)?;
+    let record = GameRecord::parse(fields.get("record")?).ok()?;
+    let (winner, ending) = match (fields.get("winner"), fields.get("ending")) {
+        (Some(winner), Some(ending)) if winner.is_empty() => (None, ending.clone()),
+        (Some(winner), Some(ending)) => (Some(winner.parse().ok()?), ending.clone()),
+        _ => {
+            let final_state = record.final_state();
+            let ending = if final_state.game_over() {
+                GameEnding::Finished
+            } else {
+                GameEnding::Interrupted
+            };
+            (final_state.winner_mark(), ending_name(&ending).to_string())
+        }
+    };
+    Some(StoredGame {
+        id,
+        timestamp: fields.get("timestamp")?.parse().ok()?,
+        record,
+        winner,
+        ending,
+    })
+}
+
+/// Parses a flat JSON object of strings and numbers, as written by `GameStore::append`,
+/// into its values by key. Returns `None` if the object is invalid.
+fn parse_object(text: &str) -> Option<HashMap<String, String>> {
+    let mut chars = text.trim().chars().peekable();
+    let mut fields = HashMap::new();
+    if chars.next()? != '{' {
+        return None;
+    }
+    loop {
+        match chars.next()? {
+            '"' => {}
+            '}' if fields.is_empty() => break,
+            _ => return None,
+        }
+        let key = parse_string(&mut chars)?;
+        if chars.next()? != ':' {
+            return None;
+        }
+        let value = if chars.peek() == Some(&'"') {
+            chars.next();
+            parse_string(&mut chars)?
+        } else {
+            let mut number = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == '-') {
+                number.push(c);
+            }
+            number
+        };
+        fields.insert(key, value);
+        match chars.next()? {
+            ',' => {}
+            '}' => break,
+            _ => return None,
+        }
+    }
+    Some(fields)
+}
+
+/// Parses the rest of a JSON string, after its opening quote, undoing the escapes.
+fn parse_string(chars: &mut impl Iterator<Item = char>) -> Option<String> {
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                'u' => {
+                    let code: String = chars.take(4).collect();
+                    value.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                c => value.push(c),
+            },
+            c => value.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+    use crate::game::{renderers::NoRenderer, Player, PlayerCommand, ScriptedPlayer, TicTacToe};
+    use crate::logic::{GameMove, GameState};
+
+    #[test]
+    fn test_parse_object() {
+        let fields = parse_object(r#"{"timestamp":42,"x":"Alice \"A\"\n","o":"é"}"#).unwrap();
+        assert_eq!(fields["timestamp"], "42");
+        assert_eq!(fields["x"], "Alice \"A\"\n");
+        assert_eq!(fields["o"], "é");
+        assert!(parse_object(r#"{"x":"unterminated}"#).is_none());
+    }
+
+    #[test]
+    fn test_store_records_the_games() {
+        let path = env::temp_dir().join(format!("game_history_{}.jsonl", process::id()));
+        let store = GameStore::open(&path);
+        let mut cross = ScriptedPlayer::new(Mark::Cross, vec![0, 1, 2, 0, 1]);
+        let mut naught = ScriptedPlayer::new(Mark::Naught, vec![3, 4, 3, 4, 5]);
+        let mut game = TicTacToe::new(&mut cross, &mut naught, &NoRenderer, None)
+            .unwrap()
+            .with_observer(&store);
+        game.play(None);
+        game.play(Some(Mark::Naught));
+        let games = store.list();
+        let second = store.load(2);
+        let missing = store.load(3);
+        fs::remove_file(&path).unwrap();
+
+        let games = games.unwrap();
+        assert!(store.take_error().is_none());
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].record.result(), "1-0");
+        assert_eq!(games[1].record.result(), "0-1");
+        assert_eq!(games[1].record.player(Mark::Naught), "Scripted");
+        assert_eq!(second.unwrap(), Some(games[1].clone()));
+        assert_eq!(missing.unwrap(), None);
+    }
+
+    /// A player leaving the game at their first turn.
+    struct ResigningPlayer;
+
+    impl Player for ResigningPlayer {
+        fn get_mark(&self) -> Mark {
+            Mark::Cross
+        }
+
+        fn get_move(&mut self, _game_state: &GameState) -> Option<GameMove> {
+            None
+        }
+
+        fn take_command(&mut self) -> Option<PlayerCommand> {
+            Some(PlayerCommand::Resign)
+        }
+    }
+
+    #[test]
+    fn test_store_records_the_forfeits() {
+        let path = env::temp_dir().join(format!("game_history_forfeit_{}.jsonl", process::id()));
+        let store = GameStore::open(&path);
+        let naught = ScriptedPlayer::new(Mark::Naught, vec![]);
+        TicTacToe::new(ResigningPlayer, naught, &NoRenderer, None)
+            .unwrap()
+            .with_observer(&store)
+            .play(None);
+        let games = store.list();
+        fs::remove_file(&path).unwrap();
+
+        let game = &games.unwrap()[0];
+        assert_eq!(game.record.result(), "*");
+        assert_eq!(game.result(), "0-1");
+        assert_eq!(game.winner, Some(Mark::Naught));
+        assert_eq!(game.ending, "forfeit");
+        assert!(game.decided());
+    }
+
+    #[test]
+    fn test_parse_the_games_stored_without_their_ending() {
+        let line =
+            r#"{"timestamp":42,"x":"A","o":"B","result":"*","record":"[X \"A\"]\n[O \"B\"]\n"}"#;
+        let game = parse_stored_game(1, line).unwrap();
+        assert_eq!(game.winner, None);
+        assert_eq!(game.ending, "interrupted");
+        assert!(!game.decided());
+    }
+}
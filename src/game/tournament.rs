@@ -0,0 +1,254 @@
+//! Round-robin tournaments between players, to compare them quantitatively.
+//! Every entrant plays the same number of games against every other entrant, each of them
+//! playing the crosses in half of the games. A win is worth one point and a tie half a point.
+//! The entrants also get an Elo rating, starting from `INITIAL_RATING` and updated after each game.
+//!
+//! The players are created for each game by the factory of their entrant, so a game never
+//! depends on the previous ones, and a player which keeps failing to move forfeits the game.
+
+use crate::logic::{Mark, Variant};
+
+use super::engine::{InvalidMovePolicy, TicTacToe};
+use super::players::Player;
+use super::renderers::NoRenderer;
+
+/// The Elo rating of every entrant at the start of a tournament.
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// The largest change of rating after a game.
+const K_FACTOR: f64 = 32.0;
+
+type PlayerFactory = dyn Fn(Mark) -> Box<dyn Player>;
+
+/// A round-robin tournament, see `Tournament::run`.
+pub struct Tournament {
+    entrants: Vec<(String, Box<PlayerFactory>)>,
+    games: usize,
+    variant: Variant,
+}
+
+/// The results of an entrant at the end of a tournament.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Standing {
+    /// The name of the entrant.
+    pub name: String,
+    /// The number of games won.
+    pub wins: usize,
+    /// The number of games without a winner.
+    pub ties: usize,
+    /// The number of games lost.
+    pub losses: usize,
+    /// The Elo rating of the entrant.
+    pub rating: f64,
+}
+
+impl Standing {
+    /// Returns the points of the entrant: one for each win and half a point for each tie.
+    pub fn points(&self) -> f64 {
+        self.wins as f64 + self.ties as f64 / 2.0
+    }
+
+    /// Returns the number of games played.
+    pub fn games(&self) -> usize {
+        self.wins + self.ties + self.losses
+    }
+
+    /// Returns the share of the games won, from 0 to 1, or 0 without games.
+    pub fn win_rate(&self) -> f64 {
+        match self.games() {
+            0 => 0.0,
+            games => self.wins as f64 / games as f64,
+        }
+    }
+}
+
+impl Tournament {
+    /// Creates a new `Tournament` without entrants.
+    ///
+    /// # Arguments
+    ///
+    /// * `games` - The number of games between every two entrants.
+    pub fn new(games: usize) -> Self {
+        Tournament {
+            entrants: vec![],
+            games,
+            variant: Variant::Standard,
+        }
+    }
+
+    /// Sets the variant of the rules the games are played with.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The variant of the rules, `Variant::Standard` by default.
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Adds an entrant to the tournament.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the entrant in the standings.
+    /// * `factory` - Creates the player of the entrant with the mark, for each game.
+    pub fn entrant(
+        mut self,
+        name: &str,
+        factory: impl Fn(Mark) -> Box<dyn Player> + 'static,
+    ) -> Self {
+        self.entrants.push((name.to_string(), Box::new(factory)));
+        self
+    }
+
+    /// Plays every game of the tournament and returns the standings,
+    /// from the entrant with the most points to the one with the least, by rating on equal points.
+    pub fn run(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self
+            .entrants
+            .iter()
+            .map(|(name, _)| Standing {
+                name: name.clone(),
+                wins: 0,
+                ties: 0,
+                losses: 0,
+                rating: INITIAL_RATING,
+            })
+            .collect();
+
+        for first in 0..self.entrants.len() {
+            for second in first + 1..self.entrants.len() {
+                for game in 0..self.games {
+                    let (cross, naught) = if game % 2 == 0 {
+                        (first, second)
+                    } else {
+                        (second, first)
+                    };
+                    let winner = self.play_game(cross, naught);
+                    record_game(&mut standings, cross, naught, winner);
+                }
+            }
+        }
+
+        standings.sort_by(|a, b| {
+            b.points()
+                .total_cmp(&a.points())
+                .then(b.rating.total_cmp(&a.rating))
+        });
+        standings
+    }
+
+    /// Plays a game between two entrants and returns the mark of the winner, `None` for a tie.
+    fn play_game(&self, cross: usize, naught: usize) -> Option<Mark> {
+        let player_x = (self.entrants[cross].1)(Mark::Cross);
+        let player_o = (self.entrants[naught].1)(Mark::Naught);
+        TicTacToe::new(player_x, player_o, NoRenderer, None)
+            .expect("The players of a tournament game have different marks")
+            .with_variant(self.variant)
+            .with_invalid_move_policy(InvalidMovePolicy::Forfeit)
+            .play(None)
+            .winner
+    }
+}
+
+/// Adds the outcome of a game to the standings of both entrants and updates their ratings.
+///
+/// # Arguments
+///
+/// * `standings` - The standings of the entrants, in the order they entered.
+/// * `cross` - The index of the entrant who played the crosses.
+/// * `naught` - The index of the entrant who played the naughts.
+/// * `winner` - The mark of the winner, `None` for a tie.
+pub(crate) fn record_game(
+    standings: &mut [Standing],
+    cross: usize,
+    naught: usize,
+    winner: Option<Mark>,
+) {
+    let cross_score = match winner {
+        Some(Mark::Cross) => {
+            standings[cross].wins += 1;
+            standings[naught].losses += 1;
+            1.0
+        }
+        Some(Mark::Naught) => {
+            standings[naught].wins += 1;
+            standings[cross].losses += 1;
+            0.0
+        }
+        None => {
+            standings[cross].ties += 1;
+            standings[naught].ties += 1;
+            0.5
+        }
+    };
+    let change = rating_change(
+        standings[cross].rating,
+        standings[naught].rating,
+        cross_score,
+    );
+    standings[cross].rating += change;
+    standings[naught].rating -= change;
+}
+
+/// Returns the change of the Elo rating of a player after a game.
+///
+/// # Arguments
+///
+/// * `rating` - The rating of the player.
+/// * `opponent_rating` - The rating of their opponent.
+/// * `score` - The score of the player: 1 for a win, 0.5 for a tie and 0 for a loss.
+fn rating_change(rating: f64, opponent_rating: f64, score: f64) -> f64 {
+    let expected = 1.0 / (1.0 + 10f64.powf((opponent_rating - rating) / 400.0));
+    K_FACTOR * (score - expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::PerfectPlayer;
+    use crate::logic::{GameMove, GameState};
+
+    /// A player which marks the first empty cell.
+    struct FirstCellPlayer {
+        mark: Mark,
+    }
+
+    impl Player for FirstCellPlayer {
+        fn get_move(&mut self, game_state: &GameState) -> Option<GameMove> {
+            game_state.possible_moves().into_iter().next()
+        }
+
+        fn get_mark(&self) -> Mark {
+            self.mark
+        }
+    }
+
+    #[test]
+    fn test_rating_change() {
+        assert_eq!(rating_change(1500.0, 1500.0, 1.0), 16.0);
+        assert_eq!(rating_change(1500.0, 1500.0, 0.5), 0.0);
+        assert!(rating_change(1700.0, 1500.0, 1.0) < 16.0);
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let standings = Tournament::new(4)
+            .entrant("first cell", |mark| Box::new(FirstCellPlayer { mark }))
+            .entrant("perfect", |mark| Box::new(PerfectPlayer::new(mark)))
+            .entrant("first cell again", |mark| {
+                Box::new(FirstCellPlayer { mark })
+            })
+            .run();
+
+        assert_eq!(standings.len(), 3);
+        assert_eq!(standings[0].name, "perfect");
+        assert_eq!(standings[0].losses, 0);
+        assert!(standings[0].rating > INITIAL_RATING);
+        for standing in &standings {
+            assert_eq!(standing.wins + standing.ties + standing.losses, 8);
+        }
+        let total: f64 = standings.iter().map(|standing| standing.rating).sum();
+        assert!((total - 3.0 * INITIAL_RATING).abs() < 1e-9);
+    }
+}
@@ -4,7 +4,10 @@
 //!   The player who succeeds in placing three of their marks in a horizontal, vertical, or diagonal row is the winner.
 //!   The game can be played versus another human player or versus a computer player.
 //!   The computer player can be configured to play randomly or to use the minimax algorithm.
+//!   The `wasm` feature exposes the logic to JavaScript, to play in a browser.
 
 pub mod frontend;
 pub mod game;
 pub mod logic;
+#[cfg(feature = "wasm")]
+pub mod wasm;
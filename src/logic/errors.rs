@@ -1,18 +1,46 @@
+use std::io;
+
 use thiserror::Error;
 
 use super::Mark;
 
-#[derive(Error, Debug)]
+/// The errors of the public API, so callers can match on the kind of error.
+/// The move and validation errors convert into it with `?`.
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
 pub enum Error {
     #[error("Configuration Error: `{0}`")]
     ConfigError(String),
+    #[error("I/O Error: `{0}`")]
+    IoError(String),
     #[error("Move Error")]
-    MoveError(MoveError),
+    MoveError(#[from] MoveError),
     #[error("Validation Error")]
-    ValidationError(ValidationError),
+    ValidationError(#[from] ValidationError),
+}
+
+impl Error {
+    /// Creates an `Error::IoError` telling what could not be done and why.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - What could not be done, e.g. ``cannot save the game to `game.txt` ``.
+    /// * `error` - The I/O error met.
+    pub fn io(context: &str, error: &io::Error) -> Self {
+        Error::IoError(format!("{}: {}", context, error))
+    }
+
+    /// Returns a stable identifier of the kind of error, for machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::ConfigError(_) => "config_error",
+            Error::IoError(_) => "io_error",
+            Error::MoveError(error) => error.code(),
+            Error::ValidationError(error) => error.code(),
+        }
+    }
 }
 
-#[derive(Error, Debug)]
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
 pub enum MoveError {
     #[error("No more possible moves")]
     NoPossibleMoves,
@@ -20,9 +48,32 @@ pub enum MoveError {
     NotYourTurn(Mark),
     #[error("Cell `{0}`  is already marked")]
     CellAlreadyMarked(usize),
+    #[error("The mark `{0}` cannot be placed in this variant")]
+    MarkNotAllowed(Mark),
+    #[error("Cell `{0}` is not the lowest empty cell of its column")]
+    CellNotSupported(usize),
+    #[error("Column `{0}` is full")]
+    ColumnFull(usize),
+    #[error("Cell `{0}` is not on the grid")]
+    CellOutOfRange(usize),
 }
 
-#[derive(Error, Debug)]
+impl MoveError {
+    /// Returns a stable identifier of the kind of error, for machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match self {
+            MoveError::NoPossibleMoves => "no_possible_moves",
+            MoveError::NotYourTurn(_) => "not_your_turn",
+            MoveError::CellAlreadyMarked(_) => "cell_already_marked",
+            MoveError::MarkNotAllowed(_) => "mark_not_allowed",
+            MoveError::CellNotSupported(_) => "cell_not_supported",
+            MoveError::ColumnFull(_) => "column_full",
+            MoveError::CellOutOfRange(_) => "cell_out_of_range",
+        }
+    }
+}
+
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
 pub enum ValidationError {
     #[error("Wrong number of naughts and crosses `{0}` `{1}`, expected 0 or 1 difference")]
     WrongNumberOfNaughtsAndCrosses(usize, usize),
@@ -30,4 +81,40 @@ pub enum ValidationError {
     WrongStartingMark(Mark),
     #[error("Wrong winner mark `{0}`, expected the other mark")]
     WrongWinnerMark(Mark),
+    #[error("The mark `{0}` cannot be on the grid in this variant")]
+    MarkNotAllowed(Mark),
+    #[error("Cell `{0}` is marked above an empty cell")]
+    FloatingMark(usize),
+    #[error(
+        "Invalid notation `{0}`, expected the rows and the mark to play, e.g. `X.O/.X./..O X`"
+    )]
+    InvalidNotation(String),
+    #[error("The game is not over, it has no score yet")]
+    GameNotOver,
+    #[error("Invalid code `{0}`, expected a code returned by `GameState::encode`")]
+    InvalidCode(u32),
+    #[error("Invalid mark `{0}`, expected `X` or `O`")]
+    InvalidMark(String),
+    #[error("Invalid grid `{0}`, expected 9 cells `X`, `O` or `.` row by row, e.g. `XO..X...O`")]
+    InvalidGrid(String),
+}
+
+impl ValidationError {
+    /// Returns a stable identifier of the kind of error, for machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationError::WrongNumberOfNaughtsAndCrosses(_, _) => {
+                "wrong_number_of_naughts_and_crosses"
+            }
+            ValidationError::WrongStartingMark(_) => "wrong_starting_mark",
+            ValidationError::WrongWinnerMark(_) => "wrong_winner_mark",
+            ValidationError::MarkNotAllowed(_) => "mark_not_allowed_on_grid",
+            ValidationError::FloatingMark(_) => "floating_mark",
+            ValidationError::InvalidNotation(_) => "invalid_notation",
+            ValidationError::GameNotOver => "game_not_over",
+            ValidationError::InvalidCode(_) => "invalid_code",
+            ValidationError::InvalidMark(_) => "invalid_mark",
+            ValidationError::InvalidGrid(_) => "invalid_grid",
+        }
+    }
 }
@@ -4,10 +4,15 @@
 
 pub mod errors;
 pub mod models;
+pub(crate) mod solver;
 mod validators;
 
 pub use models::cell::Cell;
+pub use models::coord::CellCoord;
 pub use models::game_move::GameMove;
 pub use models::game_state::GameState;
-pub use models::grid::Grid;
+pub use models::grid::{Grid, GridBuilder};
 pub use models::mark::Mark;
+pub use models::outcome::TheoreticalOutcome;
+pub use models::phase::{GamePhase, PhaseThresholds};
+pub use models::variant::Variant;
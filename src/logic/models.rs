@@ -1,7 +1,11 @@
 //! This module contains the models used by the logic module.
 
 pub mod cell;
+pub mod coord;
 pub mod game_move;
 pub mod game_state;
 pub mod grid;
 pub mod mark;
+pub mod outcome;
+pub mod phase;
+pub mod variant;
@@ -6,6 +6,7 @@ use super::mark::Mark;
 
 /// Represents a single cell on the Tic Tac Toe game board.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cell {
     mark: Option<Mark>,
 }
@@ -20,8 +21,16 @@ impl std::fmt::Display for Cell {
 }
 
 impl Cell {
+    pub(crate) const EMPTY: Cell = Cell { mark: None };
+    pub(crate) const CROSS: Cell = Cell {
+        mark: Some(Mark::Cross),
+    };
+    pub(crate) const NAUGHT: Cell = Cell {
+        mark: Some(Mark::Naught),
+    };
+
     /// Create a new empty cell.
-    pub(crate) fn new_empty() -> Self {
+    pub fn new_empty() -> Self {
         Self { mark: None }
     }
 
@@ -31,17 +40,22 @@ impl Cell {
     ///
     /// * `mark` - The mark which will be in the cell
     ///
-    pub(crate) fn new_marked(mark: Mark) -> Self {
+    pub fn new_marked(mark: Mark) -> Self {
         Cell { mark: Some(mark) }
     }
 
+    /// Returns the mark in the cell, or `None` if the cell is empty.
+    pub fn mark(&self) -> Option<Mark> {
+        self.mark
+    }
+
     /// Returns `true` if the cell is occupied by a mark, `false` otherwise.
-    pub(super) fn is_occupied(&self) -> bool {
+    pub fn is_occupied(&self) -> bool {
         self.mark.is_some()
     }
 
     /// Returns `true` if the cell is empty, `false` if it is occupied by a mark.
-    pub(super) fn is_vacant(&self) -> bool {
+    pub fn is_vacant(&self) -> bool {
         !self.is_occupied()
     }
 
@@ -51,12 +65,8 @@ impl Cell {
     ///
     /// * `mark` - The mark to check for in the cell.
     ///
-    pub(super) fn is_occupied_by(&self, mark: Mark) -> bool {
-        if let Some(m) = self.mark {
-            m == mark
-        } else {
-            false
-        }
+    pub fn is_occupied_by(&self, mark: Mark) -> bool {
+        self.mark == Some(mark)
     }
 }
 
@@ -134,4 +144,10 @@ mod tests {
         let cell = Cell::new_marked(Mark::Naught);
         assert!(cell.is_occupied_by(Mark::Naught));
     }
+
+    #[test]
+    fn test_mark() {
+        assert_eq!(Cell::new_empty().mark(), None);
+        assert_eq!(Cell::new_marked(Mark::Naught).mark(), Some(Mark::Naught));
+    }
 }
@@ -0,0 +1,80 @@
+//! The `CellCoord` struct represents the row and the column of a cell of the grid.
+//! Its bounds are checked once when it is created, so it always designates a cell of the grid.
+//! It is displayed with the coordinates typed in the cli: the column as a letter and the row as a digit, e.g. `B2`.
+
+use crate::logic::Grid;
+
+/// Represents the row and the column of a cell of the grid, both from 0 to `Grid::WIDTH - 1`.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct CellCoord {
+    row: usize,
+    column: usize,
+}
+
+impl CellCoord {
+    /// Creates a new `CellCoord`, or returns `None` if the cell is not on the grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row of the cell, from the top.
+    /// * `column` - The column of the cell, from the left.
+    pub fn new(row: usize, column: usize) -> Option<Self> {
+        if row < Grid::WIDTH && column < Grid::WIDTH {
+            Some(CellCoord { row, column })
+        } else {
+            None
+        }
+    }
+
+    /// Creates the `CellCoord` of the cell index, or returns `None` if the cell is not on the grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the cell, row by row.
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::new(index / Grid::WIDTH, index % Grid::WIDTH)
+    }
+
+    /// Returns the row of the cell, from the top.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Returns the column of the cell, from the left.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the index of the cell, row by row.
+    pub fn index(&self) -> usize {
+        self.row * Grid::WIDTH + self.column
+    }
+}
+
+impl std::fmt::Display for CellCoord {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let column = (b'A' + self.column as u8) as char;
+        write!(f, "{}{}", column, self.row + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_coord() {
+        let coord = CellCoord::new(1, 2).unwrap();
+        assert_eq!(coord.index(), 5);
+        assert_eq!(CellCoord::from_index(5), Some(coord));
+        assert_eq!(CellCoord::new(3, 0), None);
+        assert_eq!(CellCoord::new(0, 3), None);
+        assert_eq!(CellCoord::from_index(Grid::SIZE), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(CellCoord::from_index(0).unwrap().to_string(), "A1");
+        assert_eq!(CellCoord::new(1, 2).unwrap().to_string(), "C2");
+    }
+}
@@ -1,13 +1,15 @@
 //! This module contains the `GameMove` struct.
 //! A `GameMove` represents a move in a tic-tac-toe game.
-//! It contains the mark of the move, the index of the cell where the move was made,
-//! the before_state of the game before the move was made, and the after_state of the game after the move was made.
-use crate::logic::{GameState, Mark};
+//! It contains the mark of the player who made the move, the mark placed on the grid,
+//! the index of the cell where the move was made, the before_state of the game before the move was made, and the after_state of the game after the move was made.
+use crate::logic::{CellCoord, GameState, Mark};
 
 /// Represents a move in a tic-tac-toe game.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameMove {
     mark: Mark,
+    placed_mark: Mark,
     cell_index: usize,
     before_state: GameState,
     after_state: GameState,
@@ -22,22 +24,60 @@ impl GameMove {
     ) -> Self {
         GameMove {
             mark,
+            placed_mark: mark,
             cell_index,
             before_state,
             after_state,
         }
     }
 
-    /// Returns the mark of the move.
+    /// Creates a new `GameMove` where the placed mark differs from the mark of the player,
+    /// as allowed by the `Variant::Wild` rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `mark` - The mark of the player who made the move.
+    /// * `placed_mark` - The mark placed on the grid.
+    /// * `cell_index` - The index of the cell where the move was made.
+    /// * `before_state` - The state of the game before the move.
+    /// * `after_state` - The state of the game after the move.
+    pub fn new_with_placed_mark(
+        mark: Mark,
+        placed_mark: Mark,
+        cell_index: usize,
+        before_state: GameState,
+        after_state: GameState,
+    ) -> Self {
+        GameMove {
+            mark,
+            placed_mark,
+            cell_index,
+            before_state,
+            after_state,
+        }
+    }
+
+    /// Returns the mark of the player who made the move.
     pub fn mark(&self) -> &Mark {
         &self.mark
     }
 
+    /// Returns the mark placed on the grid by the move.
+    pub fn placed_mark(&self) -> &Mark {
+        &self.placed_mark
+    }
+
     /// Returns the index of the cell where the move was made.
     pub fn cell_index(&self) -> usize {
         self.cell_index
     }
 
+    /// Returns the coordinates of the cell where the move was made,
+    /// as typed in the cli: the column as a letter and the row as a digit, e.g. `B2`.
+    pub fn coordinate(&self) -> String {
+        CellCoord::from_index(self.cell_index).map_or_else(String::new, |coord| coord.to_string())
+    }
+
     /// Returns the after_state of the move.
     pub fn before_state(&self) -> &GameState {
         &self.before_state
@@ -48,3 +88,35 @@ impl GameMove {
         &self.after_state
     }
 }
+
+/// Displays the mark of the player and the coordinates of the move, e.g. `X -> B2`,
+/// followed by the placed mark when it is not the mark of the player, e.g. `X -> B2O`.
+impl std::fmt::Display for GameMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.mark, self.coordinate())?;
+        if self.placed_mark != self.mark {
+            write!(f, "{}", self.placed_mark)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{Grid, Variant};
+
+    #[test]
+    fn test_coordinate_and_display() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        let game_move = game_state.make_move_to(4).unwrap();
+        assert_eq!(game_move.coordinate(), "B2");
+        assert_eq!(game_move.to_string(), "X -> B2");
+        let game_move = game_state.make_move_to(5).unwrap();
+        assert_eq!(game_move.coordinate(), "C2");
+
+        let game_state = GameState::new_with_variant(Grid::new(None), None, Variant::Wild).unwrap();
+        let game_move = game_state.make_move_with(6, Mark::Naught).unwrap();
+        assert_eq!(game_move.to_string(), "X -> A3O");
+    }
+}
@@ -1,19 +1,50 @@
 //! The `GameState` struct and its methods.
 //! The `GameState` struct represents the state of a Tic Tac Toe game.
-//! It contains the current state of the game board, and the mark of the player who goes first
+//! It contains the current state of the game board, the mark of the player who goes first
+//! and the variant of the rules the game is played with.
+//! A game state can be written in a compact text notation, the rows of the grid separated by `/`
+//! followed by the mark to play and, when it is not the standard one, the variant,
+//! e.g. `X.O/.X./..O X` or `X../.../... O wild`.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 use crate::logic::{
-    errors::{Error, MoveError, ValidationError},
-    validators, Cell, GameMove, Grid, Mark,
+    errors::{MoveError, ValidationError},
+    solver, validators, Cell, GameMove, GamePhase, Grid, Mark, PhaseThresholds, TheoreticalOutcome,
+    Variant,
 };
 
 /// Represents the state of a Tic Tac Toe game.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "GameStateData"))]
 pub struct GameState {
     /// The current state of the game board.
     grid: Grid,
     /// The mark of the player who goes first.
     starting_mark: Mark,
+    /// The variant of the rules the game is played with.
+    variant: Variant,
+}
+
+/// The fields of a deserialized `GameState`, validated before the game state is created.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GameStateData {
+    grid: Grid,
+    starting_mark: Mark,
+    variant: Variant,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<GameStateData> for GameState {
+    type Error = ValidationError;
+
+    fn try_from(data: GameStateData) -> Result<Self, Self::Error> {
+        GameState::new_with_variant(data.grid, Some(data.starting_mark), data.variant)
+    }
 }
 
 impl GameState {
@@ -27,18 +58,28 @@ impl GameState {
     /// * `starting_mark` - The mark of the player who goes first.
     ///
     pub fn new(grid: Grid, starting_mark: Option<Mark>) -> Result<Self, ValidationError> {
-        let game_state = {
-            if let Some(mark) = starting_mark {
-                Self {
-                    grid,
-                    starting_mark: mark,
-                }
-            } else {
-                Self {
-                    grid,
-                    starting_mark: Mark::Cross,
-                }
-            }
+        Self::new_with_variant(grid, starting_mark, Variant::Standard)
+    }
+
+    /// Creates a new `GameState` with the given `Grid`, starting `Mark` and `Variant`.
+    ///
+    /// If no starting `Mark` is provided, the default starting `Mark` is Mark::Cross.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - The game board.
+    /// * `starting_mark` - The mark of the player who goes first.
+    /// * `variant` - The variant of the rules the game is played with.
+    ///
+    pub fn new_with_variant(
+        grid: Grid,
+        starting_mark: Option<Mark>,
+        variant: Variant,
+    ) -> Result<Self, ValidationError> {
+        let game_state = Self {
+            grid,
+            starting_mark: starting_mark.unwrap_or(Mark::Cross),
+            variant,
         };
         validators::validate_game_state(&game_state)?;
         Ok(game_state)
@@ -49,7 +90,17 @@ impl GameState {
     /// The current mark is determined by checking the number of `naught`s and `cross`s in the `grid`.
     /// If the number of `naught`s is equal to the number of `cross`s, the `starting_mark` is returned.
     /// Otherwise, the other `Mark` is returned.
+    ///
+    /// In the `Variant::Wild` and `Variant::Notakto` rules the placed marks do not tell who played them,
+    /// so the current mark is determined by the number of marks placed instead.
     pub fn current_mark(&self) -> Mark {
+        if matches!(self.variant, Variant::Wild | Variant::Notakto) {
+            let placed_count = Grid::SIZE - self.grid.empty_count();
+            if placed_count.is_multiple_of(2) {
+                return self.starting_mark;
+            }
+            return self.starting_mark.other();
+        }
         if self.grid.naught_count() == self.grid.cross_count() {
             return self.starting_mark;
         }
@@ -57,98 +108,34 @@ impl GameState {
     }
 
     /// Returns the winner's `Mark`, if there is one, otherwise returns `None`.
+    ///
+    /// In the `Variant::Wild` rules the winner is the player who completed the line,
+    /// whatever the mark forming it.
+    /// In the `Variant::Notakto` rules the player who completed the line loses,
+    /// so the winner is the player whose turn it is.
     pub fn winner_mark(&self) -> Option<Mark> {
-        for mark in [Mark::Cross, Mark::Naught] {
-            // Check rows
-            for i in (0..Grid::SIZE).step_by(Grid::WIDTH) {
-                let idx = i..i + Grid::WIDTH;
-                let row = &self.grid.cells()[idx];
-                if row.iter().all(|cell| cell.is_occupied_by(mark)) {
-                    return Some(mark);
-                }
-            }
-
-            // Check columns
-            for i in 0..Grid::WIDTH {
-                let column = (i..Grid::SIZE).step_by(Grid::WIDTH);
-
-                if column
-                    .clone()
-                    .all(|j| self.grid.cells()[j].is_occupied_by(mark))
-                {
-                    return Some(mark);
-                }
-            }
-
-            // Check diagonals
-            let diagonal1 = (0..Grid::SIZE).step_by(Grid::WIDTH + 1);
-            if diagonal1
-                .clone()
-                .all(|i| self.grid.cells()[i].is_occupied_by(mark))
-            {
-                return Some(mark);
-            }
-
-            let diagonal2 = (Grid::WIDTH - 1..Grid::SIZE - 1).step_by(Grid::WIDTH - 1);
-            if diagonal2
-                .clone()
-                .all(|i| self.grid.cells()[i].is_occupied_by(mark))
-            {
-                return Some(mark);
-            }
+        let line_mark = self.line_mark()?;
+        match self.variant {
+            Variant::Standard | Variant::Gravity => Some(line_mark),
+            Variant::Wild => Some(self.current_mark().other()),
+            Variant::Notakto => Some(self.current_mark()),
         }
-        None
     }
 
-    /// Returns the indexes of the winning cells for the given `Mark`.
-    pub fn winning_indexes(&self) -> Option<Vec<usize>> {
-        for mark in [Mark::Cross, Mark::Naught] {
-            let mut winning_indexes: Vec<usize> = Vec::new();
-
-            for i in (0..Grid::SIZE).step_by(Grid::WIDTH) {
-                // Check rows
-                let row = &self.grid.cells()[i..i + Grid::WIDTH];
-                if row.iter().all(|cell| cell.is_occupied_by(mark)) {
-                    winning_indexes.extend(i..i + Grid::WIDTH);
-                    return Some(winning_indexes);
-                }
-            }
-
-            for i in 0..Grid::WIDTH {
-                // Check columns
-                let column = (i..Grid::SIZE).step_by(Grid::WIDTH);
-
-                if column
-                    .clone()
-                    .all(|j| self.grid.cells()[j].is_occupied_by(mark))
-                {
-                    winning_indexes.extend(column);
-                    return Some(winning_indexes);
-                }
-            }
-
-            // Check diagonals
-            let diagonal1 = (0..Grid::SIZE).step_by(Grid::WIDTH + 1);
-            let winning_indexes_temp = diagonal1.clone();
-            if diagonal1
-                .clone()
-                .all(|i| self.grid.cells()[i].is_occupied_by(mark))
-            {
-                winning_indexes.extend(winning_indexes_temp);
-                return Some(winning_indexes);
-            }
+    /// Returns the `Mark` forming a complete line on the grid, if there is one.
+    fn line_mark(&self) -> Option<Mark> {
+        self.grid.complete_line_mark()
+    }
 
-            let diagonal2 = (Grid::WIDTH - 1..Grid::SIZE - 1).step_by(Grid::WIDTH - 1);
-            let winning_indexes_temp = diagonal2.clone();
-            if diagonal2
-                .clone()
-                .all(|i| self.grid.cells()[i].is_occupied_by(mark))
-            {
-                winning_indexes.extend(winning_indexes_temp);
-                return Some(winning_indexes);
-            }
-        }
-        None
+    /// Returns the indexes of the cells of the complete line, if there is one.
+    pub fn winning_indexes(&self) -> Option<Vec<usize>> {
+        let cells = self.grid.cells();
+        [Mark::Cross, Mark::Naught].into_iter().find_map(|mark| {
+            Grid::WINNING_LINES
+                .iter()
+                .find(|line| line.iter().all(|&i| cells[i].is_occupied_by(mark)))
+                .map(|line| line.to_vec())
+        })
     }
 
     /// Returns `true` if the game has not started, `false` otherwise.
@@ -161,11 +148,92 @@ impl GameState {
         self.winner_mark().is_some() || self.tie()
     }
 
+    /// Returns the `GamePhase` of the game, with the thresholds of its variant.
+    pub fn phase(&self) -> GamePhase {
+        self.phase_with(&PhaseThresholds::for_variant(self.variant))
+    }
+
+    /// Returns the `GamePhase` of the game with the given thresholds.
+    ///
+    /// The game is in the endgame once it is over, a line is one mark short of completion,
+    /// or `endgame_marks` marks are placed. Otherwise, it is in the opening until
+    /// `opening_marks` marks are placed, then in the midgame.
+    ///
+    /// # Arguments
+    ///
+    /// * `thresholds` - The numbers of marks placed at which the game changes phase.
+    pub fn phase_with(&self, thresholds: &PhaseThresholds) -> GamePhase {
+        let placed_count = Grid::SIZE - self.grid.empty_count();
+        if self.game_over() || self.has_threat() || placed_count >= thresholds.endgame_marks {
+            GamePhase::Endgame
+        } else if placed_count < thresholds.opening_marks {
+            GamePhase::Opening
+        } else {
+            GamePhase::Midgame
+        }
+    }
+
+    /// Returns `true` if a line has a single empty cell and the same mark in its other cells.
+    fn has_threat(&self) -> bool {
+        let cells = self.grid.cells();
+        Grid::WINNING_LINES.iter().any(|line| {
+            let empty_count = line.iter().filter(|&&i| cells[i].is_vacant()).count();
+            empty_count == 1
+                && [Mark::Cross, Mark::Naught].iter().any(|&mark| {
+                    line.iter()
+                        .filter(|&&i| cells[i].is_occupied_by(mark))
+                        .count()
+                        == Grid::WIDTH - 1
+                })
+        })
+    }
+
     /// Returns `true` if the game is over in a tie, `false` otherwise.
     pub fn tie(&self) -> bool {
         self.grid.empty_count() == 0 && self.winner_mark().is_none()
     }
 
+    /// Returns the game states the symmetries of the grid turn this one into, itself first.
+    /// The rotations and the reflections keep the rules of every variant but `Variant::Gravity`,
+    /// where only the reflection across the vertical axis keeps the marks falling down.
+    /// The same position may be returned several times when it is symmetric.
+    pub fn symmetries(&self) -> Vec<GameState> {
+        let symmetries: &[[usize; Grid::SIZE]] = match self.variant {
+            Variant::Gravity => &[Grid::SYMMETRIES[0], Grid::SYMMETRIES[4]],
+            _ => &Grid::SYMMETRIES,
+        };
+        symmetries
+            .iter()
+            .map(|symmetry| Self {
+                grid: self.grid.transformed(symmetry),
+                ..*self
+            })
+            .collect()
+    }
+
+    /// Returns the representative of the game state among its `symmetries`:
+    /// the one whose cells, read row by row with empty before cross before naught, come first.
+    /// Symmetric game states have the same canonical form.
+    pub fn canonical_form(&self) -> GameState {
+        self.symmetries()
+            .into_iter()
+            .min_by_key(|game_state| game_state.position_key())
+            .unwrap_or(*self)
+    }
+
+    /// Returns how the game ends with perfect play from both players, whoever the players are.
+    /// Unlike the result of a finished game, it tells the outcome of any position,
+    /// with the number of plies the winner needs.
+    pub fn theoretical_outcome(&self) -> TheoreticalOutcome {
+        let value = solver::solve(self, &mut HashMap::new());
+        let plies = (solver::WIN_VALUE - value.abs()) as usize;
+        match value.signum() {
+            1 => TheoreticalOutcome::Win(self.current_mark(), plies),
+            -1 => TheoreticalOutcome::Win(self.current_mark().other(), plies),
+            _ => TheoreticalOutcome::Draw,
+        }
+    }
+
     /// Makes a move to the specified cell index and returns a new `GameMove` object.
     ///
     /// # Arguments
@@ -174,45 +242,141 @@ impl GameState {
     ///
     /// # Returns
     ///
-    /// A `Result` that contains either the `GameMove` object if the move is valid or an error message if the move is invalid.
-    pub(crate) fn make_move_to(&self, cell_index: usize) -> Result<GameMove, Error> {
-        if self.grid.cells()[cell_index].is_occupied() {
-            return Err(Error::MoveError(MoveError::CellAlreadyMarked(cell_index)));
-        }
+    /// A `Result` that contains either the `GameMove` object if the move is valid or the `MoveError` if the move is invalid.
+    pub fn make_move_to(&self, cell_index: usize) -> Result<GameMove, MoveError> {
+        let placed_mark = match self.variant {
+            Variant::Notakto => Mark::Cross,
+            Variant::Standard | Variant::Wild | Variant::Gravity => self.current_mark(),
+        };
+        self.make_move_with(cell_index, placed_mark)
+    }
 
-        let mut new_cells = [Cell::new_empty(); Grid::SIZE];
-        new_cells[..cell_index].copy_from_slice(&self.grid.cells()[..cell_index]);
-        new_cells[cell_index] = Cell::new_marked(self.current_mark());
-        new_cells[cell_index + 1..].copy_from_slice(&self.grid.cells()[cell_index + 1..]);
+    /// Plays the moves to the cell indexes one after the other, as `make_move_to` does,
+    /// and returns the game state after the last one.
+    /// Returns the error of the first move which is not possible, including a move after the end of the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_indexes` - The indexes of the cells marked, in the order they are played.
+    pub fn apply_moves(&self, cell_indexes: &[usize]) -> Result<GameState, MoveError> {
+        cell_indexes
+            .iter()
+            .try_fold(*self, |game_state, &cell_index| {
+                game_state
+                    .make_move_to(cell_index)
+                    .map(|game_move| *game_move.after_state())
+            })
+    }
 
-        let new_grid = Grid::new(Some(new_cells));
-        let new_state = match GameState::new(new_grid, Some(self.starting_mark)) {
-            Ok(state) => state,
-            Err(error) => return Err(Error::ValidationError(error)),
+    /// Makes a move placing the given mark to the specified cell index and returns a new `GameMove` object.
+    ///
+    /// The move is possible if the game is not over, the cell is on the grid and empty,
+    /// and the mark is one of the marks allowed by the variant, see `GameState::allowed_marks`.
+    /// In the `Variant::Gravity` rules, the cell must also be the lowest empty cell of its column.
+    /// A possible move always leads to a valid game state, which is not validated again.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_index` - The index of the cell where the move should be made.
+    /// * `placed_mark` - The mark placed on the cell.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that contains either the `GameMove` object if the move is valid or the `MoveError` if the move is invalid.
+    pub fn make_move_with(
+        &self,
+        cell_index: usize,
+        placed_mark: Mark,
+    ) -> Result<GameMove, MoveError> {
+        if self.game_over() {
+            return Err(MoveError::NoPossibleMoves);
+        }
+        if cell_index >= Grid::SIZE {
+            return Err(MoveError::CellOutOfRange(cell_index));
+        }
+        if !self.allowed_marks().contains(&placed_mark) {
+            return Err(MoveError::MarkNotAllowed(placed_mark));
+        }
+        if self.grid.cell(cell_index).is_occupied() {
+            return Err(MoveError::CellAlreadyMarked(cell_index));
+        }
+        if self.variant == Variant::Gravity
+            && self.grid.lowest_empty_in_column(cell_index % Grid::WIDTH) != Some(cell_index)
+        {
+            return Err(MoveError::CellNotSupported(cell_index));
+        }
+
+        let new_state = GameState {
+            grid: self.grid.with_mark(cell_index, placed_mark),
+            ..*self
         };
+        debug_assert_eq!(validators::validate_game_state(&new_state), Ok(()));
 
-        Ok(GameMove::new(
+        Ok(GameMove::new_with_placed_mark(
             self.current_mark(),
+            placed_mark,
             cell_index,
             *self,
             new_state,
         ))
     }
 
+    /// Makes a move dropping the current mark in the specified column and returns a new `GameMove` object.
+    ///
+    /// The mark falls to the lowest empty cell of the column, as in the `Variant::Gravity` rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column where the mark is dropped, from 0 to `Grid::WIDTH - 1`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that contains either the `GameMove` object if the move is valid or the `MoveError` if the column is full.
+    pub fn make_move_to_column(&self, column: usize) -> Result<GameMove, MoveError> {
+        if column >= Grid::WIDTH {
+            // The mark would land on the bottom row, outside of the grid.
+            return Err(MoveError::CellOutOfRange(Grid::SIZE - Grid::WIDTH + column));
+        }
+        match self.grid.lowest_empty_in_column(column) {
+            Some(cell_index) => self.make_move_to(cell_index),
+            None => Err(MoveError::ColumnFull(column)),
+        }
+    }
+
+    /// Returns the marks the current player is allowed to place.
+    ///
+    /// - `Variant::Standard`: only the current mark.
+    /// - `Variant::Wild`: both marks.
+    /// - `Variant::Notakto`: only the cross.
+    /// - `Variant::Gravity`: only the current mark.
+    pub fn allowed_marks(&self) -> Vec<Mark> {
+        match self.variant {
+            Variant::Standard | Variant::Gravity => vec![self.current_mark()],
+            Variant::Wild => vec![Mark::Cross, Mark::Naught],
+            Variant::Notakto => vec![Mark::Cross],
+        }
+    }
+
     /// Returns a vector of all possible moves for the current state of the game.
     ///
     /// If the game is already over, returns an empty vector.
+    /// Each allowed mark is a possible move for each empty cell, by increasing cell index,
+    /// and in the `Variant::Gravity` rules only the lowest empty cell of each column is a possible move.
+    /// Every move returned is accepted by `GameState::make_move_with`.
     ///
     /// # Returns
     ///
     /// A vector of `GameMove` structs, each representing a possible move in the game.
-    pub(crate) fn possible_moves(&self) -> Vec<GameMove> {
+    pub fn possible_moves(&self) -> Vec<GameMove> {
         let mut moves: Vec<GameMove> = Vec::new();
         if !self.game_over() {
+            let marks = self.allowed_marks();
             self.grid.cells().iter().enumerate().for_each(|(i, cell)| {
                 if cell.is_vacant() {
-                    if let Ok(possible_move) = self.make_move_to(i) {
-                        moves.push(possible_move);
+                    for mark in marks.iter() {
+                        if let Ok(possible_move) = self.make_move_with(i, *mark) {
+                            moves.push(possible_move);
+                        }
                     }
                 }
             })
@@ -228,7 +392,133 @@ impl GameState {
         &self.starting_mark
     }
 
-    pub(crate) fn score(&self, maximized_player: Mark) -> Result<i32, String> {
+    /// Returns the game state as a compact code, which `GameState::decode` turns back into it:
+    /// the position key followed by the variant, below 160,000.
+    /// Unlike the text notation, it does not allocate.
+    pub fn encode(&self) -> u32 {
+        let variant_digit = match self.variant {
+            Variant::Standard => 0,
+            Variant::Wild => 1,
+            Variant::Notakto => 2,
+            Variant::Gravity => 3,
+        };
+        self.position_key() * 4 + variant_digit
+    }
+
+    /// Creates a new `GameState` from its code, as returned by `GameState::encode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The code of the game state.
+    pub fn decode(code: u32) -> Result<Self, ValidationError> {
+        let variant = match code % 4 {
+            0 => Variant::Standard,
+            1 => Variant::Wild,
+            2 => Variant::Notakto,
+            _ => Variant::Gravity,
+        };
+        let starting_mark = if (code / 4).is_multiple_of(2) {
+            Mark::Cross
+        } else {
+            Mark::Naught
+        };
+        let mut cells_key = code / 8;
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        for cell in cells.iter_mut().rev() {
+            *cell = match cells_key % 3 {
+                0 => Cell::new_empty(),
+                1 => Cell::new_marked(Mark::Cross),
+                _ => Cell::new_marked(Mark::Naught),
+            };
+            cells_key /= 3;
+        }
+        if cells_key != 0 {
+            return Err(ValidationError::InvalidCode(code));
+        }
+        Self::new_with_variant(Grid::new(Some(cells)), Some(starting_mark), variant)
+    }
+
+    /// Returns a key identifying the game state within a variant:
+    /// the cells as a base 3 number, followed by the starting mark.
+    pub(crate) fn position_key(&self) -> u32 {
+        let cells_key = self.grid.cells().iter().fold(0, |key, cell| {
+            let digit = match cell.mark() {
+                None => 0,
+                Some(Mark::Cross) => 1,
+                Some(Mark::Naught) => 2,
+            };
+            key * 3 + digit
+        });
+        let starting_mark_digit = match self.starting_mark {
+            Mark::Cross => 0,
+            Mark::Naught => 1,
+        };
+        cells_key * 2 + starting_mark_digit
+    }
+
+    /// Returns the `Variant` of the rules the game is played with.
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Returns the game state written in the compact text notation, e.g. `X.O/.X./..O X`.
+    pub fn to_notation(&self) -> String {
+        let cells = self.grid.to_string();
+        let rows: Vec<&str> = (0..Grid::SIZE)
+            .step_by(Grid::WIDTH)
+            .map(|start| &cells[start..start + Grid::WIDTH])
+            .collect();
+        let mut notation = format!("{} {}", rows.join("/"), self.current_mark());
+        if self.variant != Variant::Standard {
+            notation.push(' ');
+            notation.push_str(self.variant.name());
+        }
+        notation
+    }
+
+    /// Creates a new `GameState` from the compact text notation, e.g. `X.O/.X./..O X`.
+    ///
+    /// The starting mark is the one which gives the turn to the mark to play.
+    /// The variant is `Variant::Standard` unless its name follows the mark to play.
+    ///
+    /// # Arguments
+    ///
+    /// * `notation` - The rows of the grid separated by `/`, with `X`, `O` or `.` for an empty cell,
+    ///   then the mark to play and optionally the name of the variant.
+    pub fn from_notation(notation: &str) -> Result<Self, ValidationError> {
+        let invalid = || ValidationError::InvalidNotation(notation.to_string());
+        let mut fields = notation.split_whitespace();
+        let rows: Vec<&str> = fields.next().ok_or_else(invalid)?.split('/').collect();
+        let current_mark = match fields.next() {
+            Some(mark @ ("X" | "O")) => mark.parse()?,
+            _ => return Err(invalid()),
+        };
+        let variant = match fields.next() {
+            Some(name) => Variant::from_name(name).ok_or_else(invalid)?,
+            None => Variant::Standard,
+        };
+        if fields.next().is_some()
+            || rows.len() != Grid::WIDTH
+            || rows.iter().any(|row| row.chars().count() != Grid::WIDTH)
+            || rows.concat().contains(['x', 'o'])
+        {
+            return Err(invalid());
+        }
+        let grid: Grid = rows.concat().parse().map_err(|_| invalid())?;
+        let unchecked = Self {
+            grid,
+            starting_mark: current_mark,
+            variant,
+        };
+        let starting_mark = if unchecked.current_mark() == current_mark {
+            current_mark
+        } else {
+            current_mark.other()
+        };
+        Self::new_with_variant(grid, Some(starting_mark), variant)
+    }
+
+    pub(crate) fn score(&self, maximized_player: Mark) -> Result<i32, ValidationError> {
         if self.game_over() {
             if self.tie() {
                 return Ok(0);
@@ -238,7 +528,41 @@ impl GameState {
                 return Ok(-1);
             }
         }
-        Err(String::from("Game is not over"))
+        Err(ValidationError::GameNotOver)
+    }
+}
+
+impl std::fmt::Display for GameState {
+    /// Writes the game state in the compact text notation, the form parsed by `FromStr`.
+    ///
+    /// With the alternate flag, `{:#}`, writes the grid as an ASCII board,
+    /// followed by the player to move or the result of the game.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if !f.alternate() {
+            return write!(f, "{}", self.to_notation());
+        }
+        write!(f, "{:#}", self.grid)?;
+        if let Some(winner) = self.winner_mark() {
+            writeln!(f, "{} wins", winner)
+        } else if self.tie() {
+            writeln!(f, "Tie")
+        } else {
+            writeln!(f, "{} to play", self.current_mark())
+        }
+    }
+}
+
+impl Hash for GameState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.encode());
+    }
+}
+
+impl FromStr for GameState {
+    type Err = ValidationError;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        GameState::from_notation(notation)
     }
 }
 
@@ -550,7 +874,13 @@ mod tests {
         ];
         let game = GameState::new(Grid::new(Some(cells)), Some(Mark::Cross)).unwrap();
         let result = game.make_move_to(0);
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), MoveError::CellAlreadyMarked(0));
+    }
+
+    #[test]
+    fn test_score_game_not_over() {
+        let game = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(game.score(Mark::Cross), Err(ValidationError::GameNotOver));
     }
 
     #[test]
@@ -595,4 +925,283 @@ mod tests {
         let moves = game.possible_moves();
         assert!(moves.is_empty());
     }
+
+    #[test]
+    fn test_wild_current_mark_follows_number_of_moves() {
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        cells[0] = Cell::new_marked(Mark::Naught);
+        cells[1] = Cell::new_marked(Mark::Naught);
+        let grid = Grid::new(Some(cells));
+        let game_state = GameState::new_with_variant(grid, None, Variant::Wild).unwrap();
+        assert_eq!(game_state.current_mark(), Mark::Cross);
+    }
+
+    #[test]
+    fn test_wild_winner_is_player_completing_the_line() {
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        cells[0] = Cell::new_marked(Mark::Naught);
+        cells[1] = Cell::new_marked(Mark::Naught);
+        cells[4] = Cell::new_marked(Mark::Cross);
+        cells[2] = Cell::new_marked(Mark::Naught);
+        let grid = Grid::new(Some(cells));
+        let game_state = GameState::new_with_variant(grid, None, Variant::Wild).unwrap();
+        assert_eq!(game_state.winner_mark(), Some(Mark::Naught));
+
+        let game_state = GameState::new_with_variant(grid, Some(Mark::Naught), Variant::Wild);
+        assert_eq!(game_state.unwrap().winner_mark(), Some(Mark::Cross));
+    }
+
+    #[test]
+    fn test_wild_make_move_with_other_mark() {
+        let game = GameState::new_with_variant(Grid::new(None), None, Variant::Wild).unwrap();
+        let mv = game.make_move_with(4, Mark::Naught).unwrap();
+        assert_eq!(mv.mark(), &Mark::Cross);
+        assert_eq!(mv.placed_mark(), &Mark::Naught);
+        assert_eq!(mv.after_state().current_mark(), Mark::Naught);
+    }
+
+    #[test]
+    fn test_standard_make_move_with_other_mark() {
+        let game = GameState::new(Grid::new(None), None).unwrap();
+        assert!(game.make_move_with(4, Mark::Naught).is_err());
+    }
+
+    #[test]
+    fn test_wild_possible_moves_empty_game() {
+        let game = GameState::new_with_variant(Grid::new(None), None, Variant::Wild).unwrap();
+        let moves = game.possible_moves();
+        assert_eq!(moves.len(), 2 * Grid::SIZE);
+    }
+
+    #[test]
+    fn test_notakto_winner_is_player_not_completing_the_line() {
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        cells[0] = Cell::new_marked(Mark::Cross);
+        cells[1] = Cell::new_marked(Mark::Cross);
+        cells[4] = Cell::new_marked(Mark::Cross);
+        cells[2] = Cell::new_marked(Mark::Cross);
+        let grid = Grid::new(Some(cells));
+        let game_state = GameState::new_with_variant(grid, None, Variant::Notakto).unwrap();
+        assert_eq!(game_state.winner_mark(), Some(Mark::Cross));
+    }
+
+    #[test]
+    fn test_notakto_make_move_to_places_cross() {
+        let game =
+            GameState::new_with_variant(Grid::new(None), Some(Mark::Naught), Variant::Notakto)
+                .unwrap();
+        let mv = game.make_move_to(4).unwrap();
+        assert_eq!(mv.mark(), &Mark::Naught);
+        assert_eq!(mv.placed_mark(), &Mark::Cross);
+        assert!(game.make_move_with(4, Mark::Naught).is_err());
+    }
+
+    #[test]
+    fn test_notakto_rejects_naughts() {
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        cells[0] = Cell::new_marked(Mark::Naught);
+        let grid = Grid::new(Some(cells));
+        assert!(GameState::new_with_variant(grid, None, Variant::Notakto).is_err());
+    }
+
+    #[test]
+    fn test_gravity_make_move_to_column() {
+        let game = GameState::new_with_variant(Grid::new(None), None, Variant::Gravity).unwrap();
+        let mv = game.make_move_to_column(1).unwrap();
+        assert_eq!(mv.cell_index(), 7);
+        let mv = mv.after_state().make_move_to_column(1).unwrap();
+        assert_eq!(mv.cell_index(), 4);
+        assert!(mv.after_state().make_move_to(0).is_err());
+    }
+
+    #[test]
+    fn test_gravity_possible_moves() {
+        let game = GameState::new_with_variant(Grid::new(None), None, Variant::Gravity).unwrap();
+        let moves = game.possible_moves();
+        let indexes: Vec<usize> = moves.iter().map(|mv| mv.cell_index()).collect();
+        assert_eq!(indexes, vec![6, 7, 8]);
+    }
+
+    #[test]
+    fn test_phase_opening_and_midgame() {
+        let game = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(game.phase(), GamePhase::Opening);
+        let game = *game.make_move_to(4).unwrap().after_state();
+        assert_eq!(game.phase(), GamePhase::Opening);
+        let game = *game.make_move_to(0).unwrap().after_state();
+        assert_eq!(game.phase(), GamePhase::Midgame);
+    }
+
+    #[test]
+    fn test_phase_endgame_on_threat() {
+        let mut game = GameState::new(Grid::new(None), None).unwrap();
+        for cell_index in [0, 4, 1] {
+            game = *game.make_move_to(cell_index).unwrap().after_state();
+        }
+        assert_eq!(game.phase(), GamePhase::Endgame);
+    }
+
+    #[test]
+    fn test_phase_with_thresholds() {
+        let mut game = GameState::new(Grid::new(None), None).unwrap();
+        for cell_index in [4, 0] {
+            game = *game.make_move_to(cell_index).unwrap().after_state();
+        }
+        let thresholds = PhaseThresholds {
+            opening_marks: 3,
+            endgame_marks: 2,
+        };
+        assert_eq!(game.phase_with(&thresholds), GamePhase::Endgame);
+        let thresholds = PhaseThresholds {
+            opening_marks: 3,
+            endgame_marks: 6,
+        };
+        assert_eq!(game.phase_with(&thresholds), GamePhase::Opening);
+    }
+
+    #[test]
+    fn test_notation_round_trip() {
+        let game_state = GameState::from_notation("X.O/.X./..O X").unwrap();
+        assert_eq!(game_state.current_mark(), Mark::Cross);
+        assert_eq!(game_state.grid().cells()[2], Cell::new_marked(Mark::Naught));
+        assert_eq!(game_state.to_notation(), "X.O/.X./..O X");
+
+        let game_state: GameState = "X../.../... O wild".parse().unwrap();
+        assert_eq!(game_state.variant(), Variant::Wild);
+        assert_eq!(game_state.starting_mark(), &Mark::Cross);
+        assert_eq!(game_state.to_notation(), "X../.../... O wild");
+    }
+
+    #[test]
+    fn test_from_notation_infers_the_starting_mark() {
+        let game_state = GameState::from_notation(".../.O./... X").unwrap();
+        assert_eq!(game_state.starting_mark(), &Mark::Naught);
+        assert_eq!(game_state.current_mark(), Mark::Cross);
+    }
+
+    #[test]
+    fn test_from_notation_invalid() {
+        for notation in [
+            "X.O/.X. X",
+            "X.O/.X./..Z X",
+            "X.O/.X./..O",
+            "X.O/.X./..O X chess",
+        ] {
+            assert!(matches!(
+                GameState::from_notation(notation),
+                Err(ValidationError::InvalidNotation(_))
+            ));
+        }
+        assert!(matches!(
+            GameState::from_notation("XXX/.../... O"),
+            Err(ValidationError::WrongNumberOfNaughtsAndCrosses(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_theoretical_outcome() {
+        let outcome = |notation: &str| {
+            GameState::from_notation(notation)
+                .unwrap()
+                .theoretical_outcome()
+        };
+        assert_eq!(outcome(".../.../... X"), TheoreticalOutcome::Draw);
+        assert_eq!(
+            outcome("XX./OO./... X"),
+            TheoreticalOutcome::Win(Mark::Cross, 1)
+        );
+        assert_eq!(
+            outcome("X../..O/... X"),
+            TheoreticalOutcome::Win(Mark::Cross, 5)
+        );
+        assert_eq!(
+            outcome("XXX/OO./... O"),
+            TheoreticalOutcome::Win(Mark::Cross, 0)
+        );
+        assert_eq!(
+            outcome(".../.../... X notakto"),
+            TheoreticalOutcome::Win(Mark::Cross, 6)
+        );
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let game_state = GameState::from_notation("X../.O./... X").unwrap();
+        let symmetries = game_state.symmetries();
+        assert_eq!(symmetries.len(), 8);
+        assert_eq!(symmetries[0], game_state);
+        assert_eq!(symmetries[1].to_notation(), "..X/.O./... X");
+        for symmetry in &symmetries {
+            assert_eq!(symmetry.canonical_form(), game_state.canonical_form());
+        }
+        assert_eq!(game_state.canonical_form().to_notation(), ".../.O./..X X");
+
+        let game_state = GameState::from_notation(".../.../X.O X gravity").unwrap();
+        let notations: Vec<String> = game_state
+            .symmetries()
+            .iter()
+            .map(|s| s.to_notation())
+            .collect();
+        assert_eq!(
+            notations,
+            vec![".../.../X.O X gravity", ".../.../O.X X gravity"]
+        );
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        for notation in [
+            ".../.../... X",
+            "X.O/.X./..O X",
+            "X../.../... O wild",
+            ".../.../X.O X gravity",
+        ] {
+            let game_state = GameState::from_notation(notation).unwrap();
+            assert_eq!(GameState::decode(game_state.encode()), Ok(game_state));
+        }
+        let codes: std::collections::HashSet<GameState> = [".../.../... X", ".../.../... O"]
+            .iter()
+            .map(|notation| GameState::from_notation(notation).unwrap())
+            .collect();
+        assert_eq!(codes.len(), 2);
+        assert_eq!(
+            GameState::decode(u32::MAX),
+            Err(ValidationError::InvalidCode(u32::MAX))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let game_state = GameState::from_notation("X../.O./... X").unwrap();
+        assert_eq!(game_state.to_string(), "X../.O./... X");
+        assert_eq!(game_state.to_string().parse(), Ok(game_state));
+        assert_eq!(
+            format!("{:#}", game_state),
+            "X| | \n-+-+-\n |O| \n-+-+-\n | | \nX to play\n"
+        );
+        let game_state = GameState::from_notation("XXX/OO./... O wild").unwrap();
+        assert_eq!(game_state.to_string().parse(), Ok(game_state));
+        assert!(format!("{:#}", game_state).ends_with("X wins\n"));
+    }
+
+    #[test]
+    fn test_apply_moves() {
+        let game_state = GameState::new(Grid::new(None), None).unwrap();
+        assert_eq!(
+            game_state
+                .apply_moves(&[0, 3, 1, 4, 2])
+                .unwrap()
+                .to_notation(),
+            "XXX/OO./... O"
+        );
+        assert_eq!(game_state.apply_moves(&[]), Ok(game_state));
+        assert_eq!(
+            game_state.apply_moves(&[0, 0]),
+            Err(MoveError::CellAlreadyMarked(0))
+        );
+        assert_eq!(
+            game_state.apply_moves(&[0, 3, 1, 4, 2, 5]),
+            Err(MoveError::NoPossibleMoves)
+        );
+    }
 }
@@ -1,18 +1,96 @@
 //! The `Grid` module contains the `Grid` struct and its methods.
 //! The `Grid` struct represents the game board grid.
-//! It contains a list of `Cell` of size `Grid::SIZE`.
-use crate::logic::{Cell, Mark};
+//! It stores the cells of each mark as a bitboard, the bit `i` being set when the cell `i` holds the mark,
+//! so counting the marks or finding a complete line takes a few bit operations.
+//! The cells are still available as a list of `Cell` of size `Grid::SIZE`,
+//! or one by one from their row and column.
+use std::ops::Index;
+use std::str::FromStr;
+
+use crate::logic::{
+    errors::{MoveError, ValidationError},
+    Cell, CellCoord, Mark,
+};
 
 /// Represents the game board grid.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "GridData", into = "GridData"))]
 pub struct Grid {
+    /// The cells holding a cross.
+    crosses: u16,
+    /// The cells holding a naught.
+    naughts: u16,
+}
+
+/// The cells of a serialized `Grid`, which keeps the format of the list of cells.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct GridData {
     cells: [Cell; Grid::SIZE],
 }
 
+#[cfg(feature = "serde")]
+impl From<GridData> for Grid {
+    fn from(data: GridData) -> Self {
+        Grid::new(Some(data.cells))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Grid> for GridData {
+    fn from(grid: Grid) -> Self {
+        GridData {
+            cells: grid.cells(),
+        }
+    }
+}
+
 impl Grid {
     pub const WIDTH: usize = 3;
     pub const SIZE: usize = Grid::WIDTH * Grid::WIDTH;
 
+    /// The 8 symmetries of the grid, as the index of the cell each cell is moved from:
+    /// the identity, the rotations by a quarter, a half and three quarters of a turn clockwise,
+    /// the reflections across the vertical and the horizontal axes, and across the two diagonals.
+    pub(crate) const SYMMETRIES: [[usize; Grid::SIZE]; 8] = [
+        [0, 1, 2, 3, 4, 5, 6, 7, 8],
+        [6, 3, 0, 7, 4, 1, 8, 5, 2],
+        [8, 7, 6, 5, 4, 3, 2, 1, 0],
+        [2, 5, 8, 1, 4, 7, 0, 3, 6],
+        [2, 1, 0, 5, 4, 3, 8, 7, 6],
+        [6, 7, 8, 3, 4, 5, 0, 1, 2],
+        [0, 3, 6, 1, 4, 7, 2, 5, 8],
+        [8, 5, 2, 7, 4, 1, 6, 3, 0],
+    ];
+
+    /// The indexes of the cells of every line of the grid: the rows, the columns and the two diagonals.
+    pub const WINNING_LINES: [[usize; Grid::WIDTH]; 8] = [
+        [0, 1, 2],
+        [3, 4, 5],
+        [6, 7, 8],
+        [0, 3, 6],
+        [1, 4, 7],
+        [2, 5, 8],
+        [0, 4, 8],
+        [2, 4, 6],
+    ];
+
+    /// The bitboards of the cells of the `WINNING_LINES`.
+    const LINE_MASKS: [u16; 8] = {
+        let mut masks = [0; 8];
+        let mut line = 0;
+        while line < masks.len() {
+            let mut i = 0;
+            while i < Grid::WIDTH {
+                masks[line] |= 1 << Grid::WINNING_LINES[line][i];
+                i += 1;
+            }
+            line += 1;
+        }
+        masks
+    };
+
     /// Creates a new `Grid` with the given list of `Cell`.
     ///
     /// If no list of `Cell` is provided, the default is a list of empty cells.
@@ -22,38 +100,278 @@ impl Grid {
     /// * `cells` - The list of cells size of Grid::SIZE.
     ///
     pub(crate) fn new(cells: Option<[Cell; Grid::SIZE]>) -> Self {
-        if let Some(cell) = cells {
-            Self { cells: cell }
-        } else {
-            Self {
-                cells: [Cell::new_empty(); Grid::SIZE],
+        let mut grid = Self {
+            crosses: 0,
+            naughts: 0,
+        };
+        for (index, cell) in cells.iter().flatten().enumerate() {
+            if let Some(mark) = cell.mark() {
+                *grid.bitboard_mut(mark) |= 1 << index;
             }
         }
+        grid
+    }
+
+    /// Returns a builder placing the marks of a new grid one by one.
+    pub fn builder() -> GridBuilder {
+        GridBuilder::default()
     }
 
     /// Returns the number of empty cells in the grid.
     pub(crate) fn empty_count(&self) -> usize {
-        self.cells.iter().filter(|&cell| cell.is_vacant()).count()
+        Grid::SIZE - (self.crosses | self.naughts).count_ones() as usize
     }
 
     /// Returns the number of cells which are naught in the grid.
     pub(crate) fn naught_count(&self) -> usize {
-        self.cells
-            .iter()
-            .filter(|&cell| cell.is_occupied_by(Mark::Naught))
-            .count()
+        self.naughts.count_ones() as usize
     }
 
     /// Returns the number of cells which are cross in the grid.
     pub(crate) fn cross_count(&self) -> usize {
-        self.cells
+        self.crosses.count_ones() as usize
+    }
+
+    /// Returns the cell at the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the cell, from 0 to `Grid::SIZE - 1`.
+    pub(crate) fn cell(&self, index: usize) -> Cell {
+        if self.crosses & (1 << index) != 0 {
+            Cell::new_marked(Mark::Cross)
+        } else if self.naughts & (1 << index) != 0 {
+            Cell::new_marked(Mark::Naught)
+        } else {
+            Cell::new_empty()
+        }
+    }
+
+    /// Returns the cell at the row and the column, or `None` if the cell is not on the grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row of the cell, from the top.
+    /// * `column` - The column of the cell, from the left.
+    pub fn get(&self, row: usize, column: usize) -> Option<Cell> {
+        CellCoord::new(row, column).map(|coord| self.cell(coord.index()))
+    }
+
+    /// Returns a copy of the grid with the mark placed on the cell, whatever the cell held.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the cell.
+    /// * `mark` - The mark placed on the cell.
+    pub(crate) fn with_mark(&self, index: usize, mark: Mark) -> Self {
+        let mut grid = Self {
+            crosses: self.crosses & !(1 << index),
+            naughts: self.naughts & !(1 << index),
+        };
+        *grid.bitboard_mut(mark) |= 1 << index;
+        grid
+    }
+
+    /// Returns the mark filling a whole line of the grid, if there is one, the crosses first.
+    pub(crate) fn complete_line_mark(&self) -> Option<Mark> {
+        [Mark::Cross, Mark::Naught].into_iter().find(|&mark| {
+            let bitboard = self.bitboard(mark);
+            Grid::LINE_MASKS.iter().any(|mask| mask & !bitboard == 0)
+        })
+    }
+
+    /// Returns the bitboard of the cells holding the mark.
+    fn bitboard(&self, mark: Mark) -> u16 {
+        match mark {
+            Mark::Cross => self.crosses,
+            Mark::Naught => self.naughts,
+        }
+    }
+
+    fn bitboard_mut(&mut self, mark: Mark) -> &mut u16 {
+        match mark {
+            Mark::Cross => &mut self.crosses,
+            Mark::Naught => &mut self.naughts,
+        }
+    }
+
+    /// Returns the index of the lowest empty cell in the given column, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to search, from 0 to `Grid::WIDTH - 1`.
+    pub(crate) fn lowest_empty_in_column(&self, column: usize) -> Option<usize> {
+        (0..Grid::WIDTH)
+            .rev()
+            .map(|row| row * Grid::WIDTH + column)
+            .find(|&index| self.cell(index).is_vacant())
+    }
+
+    /// Returns the index of the first marked cell which is above an empty cell, if there is one.
+    pub(crate) fn floating_mark_index(&self) -> Option<usize> {
+        (0..Grid::SIZE - Grid::WIDTH).find(|&index| {
+            self.cell(index).is_occupied() && self.cell(index + Grid::WIDTH).is_vacant()
+        })
+    }
+
+    /// Returns the indexes of the cells of each row, from top to bottom.
+    pub fn rows() -> impl Iterator<Item = [usize; Grid::WIDTH]> {
+        Grid::WINNING_LINES[..Grid::WIDTH].iter().copied()
+    }
+
+    /// Returns the indexes of the cells of each column, from left to right.
+    pub fn columns() -> impl Iterator<Item = [usize; Grid::WIDTH]> {
+        Grid::WINNING_LINES[Grid::WIDTH..2 * Grid::WIDTH]
             .iter()
-            .filter(|&cell| cell.is_occupied_by(Mark::Cross))
-            .count()
+            .copied()
+    }
+
+    /// Returns the indexes of the cells of the two diagonals,
+    /// from the top left corner then from the top right corner.
+    pub fn diagonals() -> impl Iterator<Item = [usize; Grid::WIDTH]> {
+        Grid::WINNING_LINES[2 * Grid::WIDTH..].iter().copied()
+    }
+
+    /// Returns the grid transformed by the symmetry.
+    ///
+    /// # Arguments
+    ///
+    /// * `symmetry` - The index of the cell each cell is moved from, one of `Grid::SYMMETRIES`.
+    pub(crate) fn transformed(&self, symmetry: &[usize; Grid::SIZE]) -> Self {
+        Self::new(Some(symmetry.map(|index| self.cell(index))))
     }
 
-    pub(crate) fn cells(&self) -> &[Cell] {
-        &self.cells
+    /// Returns the cells of the grid, row by row.
+    pub(crate) fn cells(&self) -> [Cell; Grid::SIZE] {
+        std::array::from_fn(|index| self.cell(index))
+    }
+}
+
+/// A builder of grids, see `Grid::builder`.
+/// The cells which are not marked are empty.
+#[derive(Clone, Default, Debug)]
+pub struct GridBuilder {
+    marks: Vec<(usize, Mark)>,
+}
+
+impl GridBuilder {
+    /// Places the mark on the cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_index` - The index of the cell, row by row.
+    /// * `mark` - The mark placed on the cell.
+    pub fn mark(mut self, cell_index: usize, mark: Mark) -> Self {
+        self.marks.push((cell_index, mark));
+        self
+    }
+
+    /// Creates the grid.
+    /// Returns an error if a cell is not on the grid or is marked twice.
+    pub fn build(self) -> Result<Grid, MoveError> {
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        for (cell_index, mark) in self.marks {
+            let cell = cells
+                .get_mut(cell_index)
+                .ok_or(MoveError::CellOutOfRange(cell_index))?;
+            if cell.is_occupied() {
+                return Err(MoveError::CellAlreadyMarked(cell_index));
+            }
+            *cell = Cell::new_marked(mark);
+        }
+        Ok(Grid::new(Some(cells)))
+    }
+}
+
+impl std::fmt::Display for Grid {
+    /// Writes the cells row by row, `X` or `O` for a mark and `.` for an empty cell,
+    /// e.g. `XOX.XOO.X`, the form parsed by `FromStr`.
+    ///
+    /// With the alternate flag, `{:#}`, writes the grid as an ASCII board, e.g.
+    ///
+    /// ```text
+    /// X|O|X
+    /// -+-+-
+    ///  |X|O
+    /// -+-+-
+    /// O| |X
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if !f.alternate() {
+            for cell in self.cells() {
+                match cell.mark() {
+                    Some(mark) => write!(f, "{}", mark)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            return Ok(());
+        }
+        for (i, row) in Grid::rows().enumerate() {
+            if i > 0 {
+                writeln!(f, "-+-+-")?;
+            }
+            writeln!(
+                f,
+                "{}|{}|{}",
+                self.cell(row[0]),
+                self.cell(row[1]),
+                self.cell(row[2])
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Grid {
+    type Err = ValidationError;
+
+    /// Parses the cells row by row, `X` or `O` in upper or lower case for a mark
+    /// and `.` for an empty cell, e.g. `XO..X...O`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || ValidationError::InvalidGrid(text.to_string());
+        if text.chars().count() != Grid::SIZE {
+            return Err(invalid());
+        }
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        for (cell, symbol) in cells.iter_mut().zip(text.chars()) {
+            if symbol != '.' {
+                let mark: Mark = symbol.to_string().parse().map_err(|_| invalid())?;
+                *cell = Cell::new_marked(mark);
+            }
+        }
+        Ok(Grid::new(Some(cells)))
+    }
+}
+
+impl Index<CellCoord> for Grid {
+    type Output = Cell;
+
+    fn index(&self, coord: CellCoord) -> &Cell {
+        // The cells are not stored, the reference is to the constant cell with the same content.
+        let cell = self.cell(coord.index());
+        if cell == Cell::CROSS {
+            &Cell::CROSS
+        } else if cell == Cell::NAUGHT {
+            &Cell::NAUGHT
+        } else {
+            &Cell::EMPTY
+        }
+    }
+}
+
+impl Index<(usize, usize)> for Grid {
+    type Output = Cell;
+
+    /// Returns the cell at the row and the column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not on the grid, use `Grid::get` to check it.
+    fn index(&self, (row, column): (usize, usize)) -> &Cell {
+        match CellCoord::new(row, column) {
+            Some(coord) => &self[coord],
+            None => panic!("The cell ({}, {}) is not on the grid", row, column),
+        }
     }
 }
 
@@ -62,66 +380,161 @@ mod tests {
     use super::*;
     #[test]
     fn test_empty_count_full() {
-        let grid = Grid {
-            cells: [Cell::new_empty(); Grid::SIZE],
-        };
+        let grid = Grid::new(Some([Cell::new_empty(); Grid::SIZE]));
         assert_eq!(grid.empty_count(), Grid::SIZE);
     }
 
     #[test]
     fn test_empty_count() {
-        let grid = Grid {
-            cells: [
-                Cell::new_marked(Mark::Cross),
-                Cell::new_marked(Mark::Cross),
-                Cell::new_empty(),
-                Cell::new_empty(),
-                Cell::new_empty(),
-                Cell::new_empty(),
-                Cell::new_empty(),
-                Cell::new_marked(Mark::Naught),
-                Cell::new_marked(Mark::Naught),
-            ],
-        };
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_marked(Mark::Naught),
+        ]));
         assert_eq!(grid.empty_count(), 5);
     }
 
     #[test]
     fn test_naught_count() {
-        let grid = Grid {
-            cells: [
-                Cell::new_marked(Mark::Cross),
-                Cell::new_marked(Mark::Cross),
-                Cell::new_marked(Mark::Cross),
-                Cell::new_empty(),
-                Cell::new_empty(),
-                Cell::new_empty(),
-                Cell::new_empty(),
-                Cell::new_marked(Mark::Naught),
-                Cell::new_marked(Mark::Naught),
-            ],
-        };
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_marked(Mark::Naught),
+        ]));
         assert_eq!(grid.naught_count(), 2);
     }
 
     #[test]
     fn test_cross_count() {
-        let grid = Grid {
-            cells: [
-                Cell::new_marked(Mark::Cross),
-                Cell::new_marked(Mark::Cross),
-                Cell::new_marked(Mark::Cross),
-                Cell::new_marked(Mark::Cross),
-                Cell::new_empty(),
-                Cell::new_empty(),
-                Cell::new_marked(Mark::Naught),
-                Cell::new_marked(Mark::Naught),
-                Cell::new_marked(Mark::Naught),
-            ],
-        };
+        let grid = Grid::new(Some([
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_marked(Mark::Naught),
+        ]));
         assert_eq!(grid.cross_count(), 4);
     }
 
+    #[test]
+    fn test_index_by_row_and_column() {
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        cells[5] = Cell::new_marked(Mark::Naught);
+        let grid = Grid::new(Some(cells));
+
+        assert_eq!(grid[(1, 2)], Cell::new_marked(Mark::Naught));
+        assert_eq!(grid[CellCoord::new(0, 0).unwrap()], Cell::new_empty());
+        assert_eq!(grid.get(1, 2), Some(Cell::new_marked(Mark::Naught)));
+        assert_eq!(grid.get(1, 3), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_the_grid() {
+        let _ = Grid::new(None)[(3, 0)];
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid: Grid = "XO..x...O".parse().unwrap();
+        assert_eq!(grid.cross_count(), 2);
+        assert_eq!(grid.naught_count(), 2);
+        assert_eq!(grid[(1, 1)], Cell::new_marked(Mark::Cross));
+        for text in ["XO..X...", "XO..X...O.", "XO..X..-O"] {
+            assert_eq!(
+                text.parse::<Grid>(),
+                Err(ValidationError::InvalidGrid(text.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let grid = Grid::builder()
+            .mark(0, Mark::Cross)
+            .mark(4, Mark::Naught)
+            .build()
+            .unwrap();
+        assert_eq!(grid, "X...O....".parse().unwrap());
+        assert_eq!(Grid::builder().build(), Ok(Grid::new(None)));
+        assert_eq!(
+            Grid::builder().mark(9, Mark::Cross).build(),
+            Err(MoveError::CellOutOfRange(9))
+        );
+        assert_eq!(
+            Grid::builder()
+                .mark(4, Mark::Cross)
+                .mark(4, Mark::Naught)
+                .build(),
+            Err(MoveError::CellAlreadyMarked(4))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        cells[0] = Cell::new_marked(Mark::Cross);
+        cells[5] = Cell::new_marked(Mark::Naught);
+        let grid = Grid::new(Some(cells));
+        assert_eq!(grid.to_string(), "X....O...");
+        assert_eq!(grid.to_string().parse(), Ok(grid));
+        assert_eq!(format!("{:#}", grid), "X| | \n-+-+-\n | |O\n-+-+-\n | | \n");
+    }
+
+    #[test]
+    fn test_rows_columns_diagonals() {
+        assert_eq!(Grid::rows().nth(1), Some([3, 4, 5]));
+        assert_eq!(Grid::columns().last(), Some([2, 5, 8]));
+        assert_eq!(
+            Grid::diagonals().collect::<Vec<_>>(),
+            vec![[0, 4, 8], [2, 4, 6]]
+        );
+        let lines: Vec<_> = Grid::rows()
+            .chain(Grid::columns())
+            .chain(Grid::diagonals())
+            .collect();
+        assert_eq!(lines, Grid::WINNING_LINES);
+    }
+
+    #[test]
+    fn test_winning_lines() {
+        assert_eq!(Grid::WINNING_LINES.len(), 2 * Grid::WIDTH + 2);
+        assert_eq!(Grid::LINE_MASKS[0], 0b000_000_111);
+        assert_eq!(Grid::LINE_MASKS[Grid::WIDTH], 0b001_001_001);
+        assert_eq!(Grid::LINE_MASKS[2 * Grid::WIDTH], 0b100_010_001);
+        assert_eq!(Grid::LINE_MASKS[2 * Grid::WIDTH + 1], 0b001_010_100);
+    }
+
+    #[test]
+    fn test_lowest_empty_in_column() {
+        let mut cells = [Cell::new_empty(); Grid::SIZE];
+        cells[6] = Cell::new_marked(Mark::Cross);
+        cells[3] = Cell::new_marked(Mark::Naught);
+        cells[0] = Cell::new_marked(Mark::Cross);
+        cells[7] = Cell::new_marked(Mark::Naught);
+        let grid = Grid::new(Some(cells));
+
+        assert_eq!(grid.lowest_empty_in_column(0), None);
+        assert_eq!(grid.lowest_empty_in_column(1), Some(4));
+        assert_eq!(grid.lowest_empty_in_column(2), Some(8));
+    }
+
     #[test]
     fn test_new_with_cells() {
         let cells = [
@@ -137,24 +550,24 @@ mod tests {
         ];
         let grid = Grid::new(Some(cells));
 
-        assert_eq!(grid.cells.len(), 9);
-        assert!(grid.cells[0].is_occupied_by(Mark::Cross));
-        assert!(grid.cells[1].is_occupied_by(Mark::Naught));
-        assert!(grid.cells[2].is_vacant());
-        assert!(grid.cells[3].is_occupied_by(Mark::Cross));
-        assert!(grid.cells[4].is_vacant());
-        assert!(grid.cells[5].is_vacant());
-        assert!(grid.cells[6].is_vacant());
-        assert!(grid.cells[7].is_vacant());
-        assert!(grid.cells[8].is_occupied_by(Mark::Naught));
+        assert_eq!(grid.cells().len(), 9);
+        assert!(grid.cells()[0].is_occupied_by(Mark::Cross));
+        assert!(grid.cells()[1].is_occupied_by(Mark::Naught));
+        assert!(grid.cells()[2].is_vacant());
+        assert!(grid.cells()[3].is_occupied_by(Mark::Cross));
+        assert!(grid.cells()[4].is_vacant());
+        assert!(grid.cells()[5].is_vacant());
+        assert!(grid.cells()[6].is_vacant());
+        assert!(grid.cells()[7].is_vacant());
+        assert!(grid.cells()[8].is_occupied_by(Mark::Naught));
     }
 
     #[test]
     fn test_new_without_cells() {
         let grid = Grid::new(None);
 
-        assert_eq!(grid.cells.len(), 9);
-        for cell in grid.cells.iter() {
+        assert_eq!(grid.cells().len(), 9);
+        for cell in grid.cells().iter() {
             assert!(cell.is_vacant());
         }
     }
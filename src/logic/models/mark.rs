@@ -1,8 +1,13 @@
 //! The `Mark` enum represents a mark on the board in a Tic Tac Toe game.
 //! It can be either a cross or a naught.
 
+use std::str::FromStr;
+
+use crate::logic::errors::ValidationError;
+
 /// Represents a mark on the board in a Tic Tac Toe game.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mark {
     /// The mark representing a cross, which is denoted by the string "X".
     Cross,
@@ -12,7 +17,7 @@ pub enum Mark {
 
 impl Mark {
     /// Returns a new instance of the enum with the opposite variant.
-    pub(super) fn other(&self) -> Self {
+    pub(crate) fn other(&self) -> Self {
         match self {
             Mark::Cross => Mark::Naught,
             Mark::Naught => Mark::Cross,
@@ -29,6 +34,19 @@ impl std::fmt::Display for Mark {
     }
 }
 
+impl FromStr for Mark {
+    type Err = ValidationError;
+
+    /// Parses `X` or `O`, in upper or lower case.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "X" | "x" => Ok(Mark::Cross),
+            "O" | "o" => Ok(Mark::Naught),
+            _ => Err(ValidationError::InvalidMark(text.to_string())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +64,14 @@ mod tests {
         let cross = naught.other();
         assert_eq!(cross, Mark::Cross);
     }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("X".parse(), Ok(Mark::Cross));
+        assert_eq!("o".parse(), Ok(Mark::Naught));
+        assert_eq!(
+            "0".parse::<Mark>(),
+            Err(ValidationError::InvalidMark(String::from("0")))
+        );
+    }
 }
@@ -0,0 +1,13 @@
+//! The `TheoreticalOutcome` enum represents how a game ends with perfect play from both players.
+
+use crate::logic::Mark;
+
+/// Represents how a game ends with perfect play from both players.
+/// The winner ends the game as soon as possible and the loser as late as possible.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum TheoreticalOutcome {
+    /// The mark wins in the number of plies, 0 if the game is already won.
+    Win(Mark, usize),
+    /// The game ends without a winner.
+    Draw,
+}
@@ -0,0 +1,61 @@
+//! The `GamePhase` enum represents how far a Tic Tac Toe game has progressed.
+//! The phase of a game state depends on the number of marks placed and on the threats on the grid,
+//! with thresholds which depend on the variant of the rules.
+
+use crate::logic::Variant;
+
+/// Represents how far a Tic Tac Toe game has progressed.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum GamePhase {
+    /// The first marks, no line is threatened yet.
+    Opening,
+    /// The marks are placed, no line is threatened yet.
+    Midgame,
+    /// A line is one mark short of completion, the grid is almost full, or the game is over.
+    Endgame,
+}
+
+/// The numbers of marks placed at which a game changes phase.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct PhaseThresholds {
+    /// The number of marks placed from which the game is no longer in the opening.
+    pub opening_marks: usize,
+    /// The number of marks placed from which the game is in the endgame, even without threats.
+    pub endgame_marks: usize,
+}
+
+impl PhaseThresholds {
+    /// Returns the thresholds of the given variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The variant of the rules.
+    pub fn for_variant(variant: Variant) -> Self {
+        match variant {
+            Variant::Standard => PhaseThresholds {
+                opening_marks: 2,
+                endgame_marks: 6,
+            },
+            // Either mark can complete a line, so the threats come sooner.
+            Variant::Wild => PhaseThresholds {
+                opening_marks: 2,
+                endgame_marks: 5,
+            },
+            Variant::Notakto => PhaseThresholds {
+                opening_marks: 3,
+                endgame_marks: 6,
+            },
+            // The marks pile up in the bottom row first, so the game opens for longer.
+            Variant::Gravity => PhaseThresholds {
+                opening_marks: 3,
+                endgame_marks: 7,
+            },
+        }
+    }
+}
+
+impl Default for PhaseThresholds {
+    fn default() -> Self {
+        Self::for_variant(Variant::default())
+    }
+}
@@ -0,0 +1,49 @@
+//! The `Variant` enum represents the set of rules a Tic Tac Toe game is played with.
+//! The classic rules are the default, the other variants change which marks can be placed
+//! and who wins when a line is completed.
+
+/// Represents the set of rules a Tic Tac Toe game is played with.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    /// The classic game, each player places their own mark.
+    #[default]
+    Standard,
+    /// Each player may place either a cross or a naught,
+    /// the player who completes a line of three wins.
+    Wild,
+    /// Both players place a cross,
+    /// the player who completes a line of three loses.
+    Notakto,
+    /// Each player picks a column and their mark falls
+    /// to the lowest empty cell of that column.
+    Gravity,
+}
+
+impl Variant {
+    /// Returns the name of the variant, as written in the text formats.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Variant::Standard => "standard",
+            Variant::Wild => "wild",
+            Variant::Notakto => "notakto",
+            Variant::Gravity => "gravity",
+        }
+    }
+
+    /// Returns the variant with the given name, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variant, as returned by `Variant::name`.
+    pub(crate) fn from_name(name: &str) -> Option<Variant> {
+        [
+            Variant::Standard,
+            Variant::Wild,
+            Variant::Notakto,
+            Variant::Gravity,
+        ]
+        .into_iter()
+        .find(|variant| variant.name() == name)
+    }
+}
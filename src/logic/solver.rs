@@ -0,0 +1,48 @@
+//! The solver of the positions with perfect play from both players.
+//! The value of a game state is given for the player whose turn it is: a win in `n` plies
+//! is worth `WIN_VALUE - n`, a loss in `n` plies its opposite and a tie 0,
+//! so the winner prefers the shortest win and the loser the longest loss.
+
+use std::collections::HashMap;
+
+use crate::logic::GameState;
+
+/// The value of a game state won by the player whose turn it is, without any ply left.
+pub(crate) const WIN_VALUE: i32 = 100;
+
+/// Returns the value of the game state for the player whose turn it is.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to solve.
+/// * `values` - The values of the game states already solved, by position key.
+pub(crate) fn solve(game_state: &GameState, values: &mut HashMap<u32, i32>) -> i32 {
+    let key = game_state.position_key();
+    if let Some(value) = values.get(&key) {
+        return *value;
+    }
+    let value = if game_state.game_over() {
+        game_state.score(game_state.current_mark()).unwrap() * WIN_VALUE
+    } else {
+        game_state
+            .possible_moves()
+            .iter()
+            .map(|game_move| move_value(game_move.after_state(), values))
+            .max()
+            .unwrap_or(0)
+    };
+    values.insert(key, value);
+    value
+}
+
+/// Returns the value of the move reaching the game state, for the player who made it.
+/// The value is one ply further from the end than the value of the game state.
+///
+/// # Arguments
+///
+/// * `after_state` - The game state after the move.
+/// * `values` - The values of the game states already solved, by position key.
+pub(crate) fn move_value(after_state: &GameState, values: &mut HashMap<u32, i32>) -> i32 {
+    let value = -solve(after_state, values);
+    value - value.signum()
+}
@@ -2,14 +2,25 @@
 //! The functions in this module are used to validate the game state before the game starts.
 //! And they are used to validate the game state after each move.
 
-use super::{errors::ValidationError, GameState, Grid, Mark};
+use super::{errors::ValidationError, GameState, Grid, Mark, Variant};
 
-/// Validates a game state and returns an error message if the state is invalid.
+/// Validates a game state and returns a `ValidationError` if the state is invalid.
+///
+/// In the `Variant::Wild` rules any mark can be placed by any player,
+/// so the number of marks does not constrain the state.
+/// In the `Variant::Notakto` rules only crosses can be on the grid.
+/// In the `Variant::Gravity` rules no mark can be above an empty cell.
 ///
 /// # Arguments
 ///
 /// * `game_state` - The game state to validate.
 pub(crate) fn validate_game_state(game_state: &GameState) -> Result<(), ValidationError> {
+    match game_state.variant() {
+        Variant::Wild => return Ok(()),
+        Variant::Notakto => return validate_only_crosses(game_state.grid()),
+        Variant::Gravity => validate_no_floating_marks(game_state.grid())?,
+        Variant::Standard => {}
+    }
     validate_number_of_marks(game_state.grid())?;
     validate_starting_mark(game_state.grid(), game_state.starting_mark())?;
     validate_winner(
@@ -20,7 +31,7 @@ pub(crate) fn validate_game_state(game_state: &GameState) -> Result<(), Validati
     Ok(())
 }
 
-/// Validates the number of marks in a game and returns an error message if the number is invalid.
+/// Validates the number of marks in a game and returns a `ValidationError` if the number is invalid.
 ///
 /// The number of marks is invalid if:
 /// - The number of marks of the Cross mark is less than the number of marks of the Naught mark by more than 1.
@@ -41,7 +52,32 @@ fn validate_number_of_marks(grid: &Grid) -> Result<(), ValidationError> {
     Ok(())
 }
 
-/// Validates the starting mark of a game and returns an error message if the mark is invalid.
+/// Validates that only crosses are on the grid and returns a `ValidationError` otherwise.
+///
+/// # Arguments
+///
+/// * `grid` - The grid of the game.
+fn validate_only_crosses(grid: &Grid) -> Result<(), ValidationError> {
+    if grid.naught_count() > 0 {
+        return Err(ValidationError::MarkNotAllowed(Mark::Naught));
+    }
+    Ok(())
+}
+
+/// Validates that every mark rests on the bottom row or on another mark
+/// and returns a `ValidationError` otherwise.
+///
+/// # Arguments
+///
+/// * `grid` - The grid of the game.
+fn validate_no_floating_marks(grid: &Grid) -> Result<(), ValidationError> {
+    if let Some(index) = grid.floating_mark_index() {
+        return Err(ValidationError::FloatingMark(index));
+    }
+    Ok(())
+}
+
+/// Validates the starting mark of a game and returns a `ValidationError` if the mark is invalid.
 /// The starting mark is invalid if:
 /// - The number of marks of the starting mark is greater than the number of marks of the other mark.
 /// - The number of marks of the starting mark is less than the number of marks of the other mark by more than 1.
@@ -61,7 +97,7 @@ fn validate_starting_mark(grid: &Grid, starting_mark: &Mark) -> Result<(), Valid
     Ok(())
 }
 
-/// Validates the winner of a game and returns an error message if the winner is invalid.
+/// Validates the winner of a game and returns a `ValidationError` if the winner is invalid.
 ///
 /// The winner is invalid if:
 /// - The winner is not the starting mark and the number of marks of the winner is not greater than the number of marks of the other mark.
@@ -140,6 +176,38 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_no_floating_marks() {
+        let grid = Grid::new(Some([
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Cross),
+        ]));
+        assert!(validate_no_floating_marks(&grid).is_ok());
+    }
+
+    #[test]
+    fn test_validate_no_floating_marks_fail() {
+        let grid = Grid::new(Some([
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Naught),
+            Cell::new_empty(),
+            Cell::new_marked(Mark::Cross),
+            Cell::new_empty(),
+            Cell::new_empty(),
+        ]));
+        assert!(validate_no_floating_marks(&grid).is_err());
+    }
+
     #[test]
     fn test_validate_starting_mark_valid() {
         let grid = Grid::new(Some([
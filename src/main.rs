@@ -1,20 +1,442 @@
+use std::env;
+use std::io;
+use std::net::TcpStream;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
 use clap::Parser;
-use tic_tac_toe_rust::game::engine::TicTacToe;
+#[cfg(feature = "gui")]
+use tic_tac_toe_rust::frontend::gui;
+#[cfg(feature = "tui")]
+use tic_tac_toe_rust::frontend::tui;
+#[cfg(not(target_arch = "wasm32"))]
+use tic_tac_toe_rust::game::GameControl;
+use tic_tac_toe_rust::{
+    frontend::{
+        console::errors::report_error,
+        network::{players::answer_moves, web::WebServer},
+    },
+    game::{
+        analysis::analyze,
+        bench::{bench, report_to_text},
+        engine::{GameResult, TicTacToe},
+        replay::replay,
+        GameEnding, GameObserver, GameRecord, GameStore, Leaderboard, LearningPlayer, Match,
+        SavedGame, Stats,
+    },
+    logic::{errors::Error, CellCoord, GameState, Mark, Variant},
+};
 
 mod cli;
-use cli::{parse_cli, Cli};
+use cli::{requested_error_format, Cli, Command, PlayerType};
 
-fn main() {
-    let cli = Cli::parse();
+/// The file the game is saved to when it is interrupted with Ctrl-C.
+const INTERRUPTED_GAME_PATH: &str = "interrupted_game.txt";
+
+/// Tells why a game ended early, as the board does not show it,
+/// and shows the statistics of the session between the games of a match.
+struct EndingReporter<'s> {
+    stats: &'s Stats,
+    games: usize,
+}
 
-    let game_config = parse_cli(cli);
+impl GameObserver for EndingReporter<'_> {
+    fn on_game_over(&self, result: &GameResult) {
+        match result.ending {
+            GameEnding::Forfeit(mark) => println!("{} forfeits", mark),
+            GameEnding::FlagFall(mark) => println!("{} loses on time", mark),
+            _ => {}
+        }
+        if self.games > 1 {
+            println!("\n{}", self.stats);
+        }
+    }
+}
 
-    TicTacToe::new(
-        game_config.player1.as_ref(),
-        game_config.player2.as_ref(),
-        game_config.renderer.as_ref(),
-        None,
+/// Writes the time as a UTC date and time, e.g. `2023-11-14 22:13`.
+///
+/// # Arguments
+///
+/// * `timestamp` - The time in seconds since the Unix epoch.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let minutes = timestamp % 86_400 / 60;
+    // The civil date of the days since the epoch, in the proleptic Gregorian calendar.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        minutes / 60,
+        minutes % 60
     )
-    .unwrap()
-    .play(Some(game_config.starting_mark));
+}
+
+/// Handles Ctrl-C during the game: the first one stops the game before the next turn,
+/// so it can be saved, and the second one gives the terminal back and exits right away.
+///
+/// # Arguments
+///
+/// * `control` - The control of the game.
+#[cfg(not(target_arch = "wasm32"))]
+fn handle_interrupt(control: GameControl) -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(move || {
+        // The renderer may be in the middle of a frame, reset the colors and start a new line.
+        if control.is_interrupted() {
+            #[cfg(feature = "tui")]
+            tui::restore_terminal();
+            println!("\x1b[0m");
+            std::process::exit(130);
+        }
+        control.interrupt();
+        println!("\x1b[0m");
+        println!("Game interrupted after the current move, press Ctrl-C again to quit right away");
+    })
+}
+
+/// Asks the question on the terminal, the answer is no unless the player types `y`.
+///
+/// # Arguments
+///
+/// * `question` - The question to ask.
+fn confirm(question: &str) -> bool {
+    println!("{} [y/N]", question);
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok() && matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+/// Offers to save the interrupted game so it can be resumed,
+/// an existing file is only overwritten if the player agrees.
+///
+/// # Arguments
+///
+/// * `saved_game` - The game as it was when it was interrupted.
+fn offer_to_save(saved_game: &SavedGame) {
+    if saved_game.moves().is_empty() || !confirm("Save the game to resume it later?") {
+        return;
+    }
+    let path = Path::new(INTERRUPTED_GAME_PATH);
+    if path.exists() && !confirm(&format!("`{}` exists, overwrite it?", path.display())) {
+        println!("The game is not saved");
+        return;
+    }
+    match saved_game.save(path) {
+        Ok(()) => println!("Game saved, resume it with `--resume {}`", path.display()),
+        Err(error) => println!("The game cannot be saved: {}", error),
+    }
+}
+
+/// Replays the game recorded in the file, waiting for the delay or for Enter between the moves.
+fn replay_game(cli: &Cli, path: &Path, delay: Option<Duration>) -> Result<(), Error> {
+    let record = GameRecord::load(path)
+        .map_err(|error| Error::io(&format!("cannot replay `{}`", path.display()), &error))?;
+    replay(
+        &record,
+        cli.console_renderer().as_ref(),
+        &mut || match delay {
+            Some(delay) => thread::sleep(delay),
+            None => {
+                println!("Press Enter for the next move");
+                let mut input = String::new();
+                let _ = io::stdin().read_line(&mut input);
+            }
+        },
+    );
+    println!(
+        "{} (X) - {} (O): {}",
+        record.player(Mark::Cross),
+        record.player(Mark::Naught),
+        record.result()
+    );
+    Ok(())
+}
+
+/// Shows the outcome of every move of the position written in the compact text notation.
+fn analyze_position(position: &str) -> Result<(), Error> {
+    let game_state = GameState::from_notation(position)?;
+    println!("{:<6}Outcome", "Move");
+    for (cell_index, outcome) in analyze(&game_state) {
+        let coord = CellCoord::from_index(cell_index).unwrap();
+        println!("{:<6}{}", coord.to_string(), outcome);
+    }
+    Ok(())
+}
+
+/// Plays the games between the players of the command line without showing them, and reports them.
+fn bench_players(cli: &Cli, games: usize) -> Result<(), Error> {
+    let mut player1 = cli.bench_player(Mark::Cross)?;
+    let mut player2 = cli.bench_player(Mark::Naught)?;
+    let report = bench(player1.as_mut(), player2.as_mut(), games, cli.variant())?;
+    println!("{}", report_to_text(&report));
+    Ok(())
+}
+
+/// Lists the games of the history, or shows the game with the number.
+fn show_history(store: &GameStore, show: Option<usize>) -> Result<(), Error> {
+    let shown = match show {
+        Some(id) => store.load(id).map(|game| match game {
+            Some(game) => print!("{}", game.record),
+            None => println!("No game {} in the history", id),
+        }),
+        None => store.list().map(|games| {
+            for game in games {
+                println!(
+                    "{:>4}  {}  {} - {}  {}",
+                    game.id,
+                    format_timestamp(game.timestamp),
+                    game.record.player(Mark::Cross),
+                    game.record.player(Mark::Naught),
+                    game.result()
+                );
+            }
+        }),
+    };
+    shown.map_err(|error| Error::io("cannot read the history", &error))
+}
+
+/// Shows the leaderboard of the players of the history.
+fn show_leaderboard(store: &GameStore) -> Result<(), Error> {
+    let leaderboard =
+        Leaderboard::load(store).map_err(|error| Error::io("cannot read the history", &error))?;
+    println!(
+        "{:<4}{:<20}{:>8}{:>6}{:>6}{:>6}{:>8}",
+        "", "Player", "Win %", "W", "T", "L", "Elo"
+    );
+    for (i, standing) in leaderboard.standings().iter().enumerate() {
+        println!(
+            "{:<4}{:<20}{:>8.1}{:>6}{:>6}{:>6}{:>8.0}",
+            i + 1,
+            standing.name,
+            standing.win_rate() * 100.0,
+            standing.wins,
+            standing.ties,
+            standing.losses,
+            standing.rating
+        );
+    }
+    Ok(())
+}
+
+/// Hosts the playable board on the address until the process is stopped.
+fn serve(address: &str, variant: Variant) -> Result<(), Error> {
+    let server = WebServer::bind(address)
+        .map_err(|error| Error::io(&format!("cannot listen on `{}`", address), &error))?
+        .with_variant(variant);
+    if let Ok(address) = server.local_addr() {
+        println!("Serving the board on http://{}", address);
+    }
+    server
+        .serve()
+        .map_err(|error| Error::io("cannot serve the board", &error))
+}
+
+/// Plays the tournament between the computer players and shows the standings.
+fn run_tournament(cli: &Cli, players: &[PlayerType], games: usize) -> Result<(), Error> {
+    let tournament = cli.tournament(players, games)?;
+    println!(
+        "{:<4}{:<20}{:>8}{:>6}{:>6}{:>6}{:>8}",
+        "", "Player", "Points", "W", "T", "L", "Elo"
+    );
+    for (i, standing) in tournament.run().iter().enumerate() {
+        println!(
+            "{:<4}{:<20}{:>8.1}{:>6}{:>6}{:>6}{:>8.0}",
+            i + 1,
+            standing.name,
+            standing.points(),
+            standing.wins,
+            standing.ties,
+            standing.losses,
+            standing.rating
+        );
+    }
+    Ok(())
+}
+
+/// Plays the moves of the second player in the game hosted on the address.
+fn join_game(cli: &Cli, address: &str) -> Result<(), Error> {
+    let mut seat = cli.joining_seat()?;
+    TcpStream::connect(address)
+        .and_then(|stream| answer_moves(stream, seat.player.as_mut(), seat.renderer.as_ref()))
+        .map_err(|error| Error::io(&format!("cannot play on `{}`", address), &error))
+}
+
+/// Trains the learning player against itself and saves its table.
+fn train(table: &Path, games: usize, variant: Variant) -> Result<(), Error> {
+    let mut player = LearningPlayer::load_or_new(Mark::Cross, table).map_err(|error| {
+        Error::io(
+            &format!("cannot load the learning table `{}`", table.display()),
+            &error,
+        )
+    })?;
+    player.train(games, variant);
+    player.save(table).map_err(|error| {
+        Error::io(
+            &format!("cannot save the learning table to `{}`", table.display()),
+            &error,
+        )
+    })?;
+    println!(
+        "Trained for {} games, {} states known",
+        games,
+        player.known_states()
+    );
+    Ok(())
+}
+
+/// Plays the games of the command line, or resumes the saved game.
+fn play(cli: Cli) -> Result<(), Error> {
+    let error_format = cli.error_format();
+    let game_config = cli.game_config()?;
+
+    let saved_game =
+        match &game_config.resume {
+            Some(path) => Some(SavedGame::load(path).map_err(|error| {
+                Error::io(&format!("cannot resume `{}`", path.display()), &error)
+            })?),
+            None => None,
+        };
+
+    let control = game_config.control.clone();
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Err(error) = handle_interrupt(control.clone()) {
+        report_error(&Error::ConfigError(error.to_string()), error_format);
+    }
+
+    let stats = Stats::new();
+    let reporter = EndingReporter {
+        stats: &stats,
+        games: game_config.games,
+    };
+    let mut builder = TicTacToe::builder()
+        .player_x(game_config.player1)
+        .player_o(game_config.player2)
+        .renderer(game_config.renderer)
+        .error_handler(Box::new(move |error: Error| {
+            report_error(&error, error_format)
+        }))
+        .variant(game_config.variant)
+        .starting_mark(game_config.starting_mark)
+        .invalid_move_policy(game_config.invalid_move_policy)
+        .control(control.clone())
+        .observer(&stats)
+        .observer(&reporter);
+    if let Some(store) = &game_config.history {
+        builder = builder.observer(store);
+    }
+    for learner in &game_config.learners {
+        builder = builder.observer(learner);
+    }
+    if let Some(time_control) = game_config.time_control {
+        builder = builder.time_control(time_control);
+    }
+    let mut game = builder.build()?;
+
+    let ending = match saved_game {
+        Some(saved_game) => Some(game.resume(saved_game).ending),
+        None => Match::new(game_config.games)
+            .play(&mut game)
+            .games()
+            .last()
+            .map(|result| result.ending.clone()),
+    };
+    let record = game.record();
+    // The players and the renderer give the terminal back before the game is offered to be saved.
+    drop(game);
+    if ending == Some(GameEnding::Interrupted) {
+        if let Some(saved_game) = control.snapshot() {
+            offer_to_save(&saved_game);
+        }
+    }
+    if let (Some(path), Some(record)) = (&game_config.record, record) {
+        record.save(path).map_err(|error| {
+            Error::io(
+                &format!("cannot write the record to `{}`", path.display()),
+                &error,
+            )
+        })?;
+    }
+    for learner in &game_config.learners {
+        learner.save(&game_config.table).map_err(|error| {
+            Error::io(
+                &format!(
+                    "cannot save the learning table to `{}`",
+                    game_config.table.display()
+                ),
+                &error,
+            )
+        })?;
+    }
+    if let Some(error) = game_config.history.as_ref().and_then(GameStore::take_error) {
+        return Err(Error::io("cannot store the game in the history", &error));
+    }
+    Ok(())
+}
+
+/// Joins a game, trains the learning player, opens the window or plays in the terminal,
+/// as the options of the command line ask.
+fn play_or_train(cli: Cli) -> Result<(), Error> {
+    if let Some(address) = &cli.join {
+        return join_game(&cli, address);
+    }
+    if let Some(games) = cli.train {
+        return train(&cli.table, games, cli.variant());
+    }
+    #[cfg(feature = "gui")]
+    if let Some(opponent) = cli.gui_opponent()? {
+        return gui::run(cli.variant(), opponent)
+            .map_err(|error| Error::ConfigError(format!("cannot open the window: {}", error)));
+    }
+    play(cli)
+}
+
+fn main() {
+    let mut cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        // The help and the version are not errors, clap prints them.
+        Err(error) if !error.use_stderr() => error.exit(),
+        Err(error) => {
+            // The first line tells what is wrong, the next ones are the usage.
+            let message = error.to_string();
+            let message = message.lines().next().unwrap_or_default();
+            report_error(
+                &Error::ConfigError(message.trim_start_matches("error: ").to_string()),
+                requested_error_format(env::args().skip(1)),
+            );
+            std::process::exit(error.exit_code());
+        }
+    };
+    let error_format = cli.error_format();
+
+    // Only the games and the benchmark create the players, the other commands do not need them.
+    let outcome = match cli.command.take() {
+        Some(Command::Replay { path, delay }) => {
+            replay_game(&cli, &path, delay.map(Duration::from_millis))
+        }
+        Some(Command::Analyze { position }) => analyze_position(&position),
+        Some(Command::Bench { games }) => bench_players(&cli, games),
+        Some(Command::History { path, show }) => show_history(&GameStore::open(&path), show),
+        Some(Command::Leaderboard { path }) => show_leaderboard(&GameStore::open(&path)),
+        Some(Command::Serve { address }) => serve(&address, cli.variant()),
+        Some(Command::Tournament { players, games }) => run_tournament(&cli, &players, games),
+        None => play_or_train(cli),
+    };
+    if let Err(error) = outcome {
+        report_error(&error, error_format);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,273 @@
+//! The bindings of the game logic for JavaScript, behind the `wasm` feature, to power a browser UI.
+//! The game states are immutable: a move returns the game state after it, as `GameState` does,
+//! and `bestMove` looks the moves of perfect play up in the `PolicyTable`, without a thread or a clock.
+//!
+//! ```js
+//! let state = new GameState("standard");
+//! state = state.play(4);
+//! const hint = bestMove(state);
+//! ```
+//!
+//! The crate is a library for the other targets, the WebAssembly module is built as a `cdylib` on demand:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/tic_tac_toe_rust.wasm
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    game::analysis,
+    logic::{GameMove, GameState, Grid, Mark, Variant},
+};
+
+/// A game state, as seen from JavaScript.
+#[wasm_bindgen(js_name = GameState)]
+pub struct WasmGameState {
+    game_state: GameState,
+}
+
+#[wasm_bindgen(js_class = GameState)]
+impl WasmGameState {
+    /// Creates the game state of an empty grid, with the cross to play.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The name of the variant, e.g. `wild`, the standard rules if there is none.
+    #[wasm_bindgen(constructor)]
+    pub fn new(variant: Option<String>) -> Result<WasmGameState, JsError> {
+        let variant = match variant {
+            Some(name) => Variant::from_name(&name)
+                .ok_or_else(|| JsError::new(&format!("Unknown variant `{}`", name)))?,
+            None => Variant::Standard,
+        };
+        let game_state = GameState::new_with_variant(Grid::new(None), None, variant)?;
+        Ok(WasmGameState { game_state })
+    }
+
+    /// Creates the game state written in the compact text notation, e.g. `X.O/.X./..O X`.
+    ///
+    /// # Arguments
+    ///
+    /// * `notation` - The game state in the compact text notation.
+    #[wasm_bindgen(js_name = fromNotation)]
+    pub fn from_notation(notation: &str) -> Result<WasmGameState, JsError> {
+        let game_state = GameState::from_notation(notation)?;
+        Ok(WasmGameState { game_state })
+    }
+
+    /// Returns the game state written in the compact text notation.
+    pub fn notation(&self) -> String {
+        self.game_state.to_notation()
+    }
+
+    /// Returns the marks of the cells row by row, `X`, `O` or an empty string for an empty cell.
+    pub fn cells(&self) -> Vec<String> {
+        self.game_state
+            .grid()
+            .cells()
+            .iter()
+            .map(|cell| cell.mark().map_or(String::new(), |mark| mark.to_string()))
+            .collect()
+    }
+
+    /// Returns the name of the variant of the rules.
+    pub fn variant(&self) -> String {
+        self.game_state.variant().name().to_string()
+    }
+
+    /// Returns the mark of the player whose turn it is.
+    #[wasm_bindgen(js_name = currentMark)]
+    pub fn current_mark(&self) -> String {
+        self.game_state.current_mark().to_string()
+    }
+
+    /// Returns `true` if the game is over, `false` otherwise.
+    #[wasm_bindgen(js_name = gameOver)]
+    pub fn game_over(&self) -> bool {
+        self.game_state.game_over()
+    }
+
+    /// Returns the mark of the winner, if there is one.
+    pub fn winner(&self) -> Option<String> {
+        self.game_state.winner_mark().map(|mark| mark.to_string())
+    }
+
+    /// Returns the cell indexes of the winning line, none if there is no winner.
+    #[wasm_bindgen(js_name = winningCells)]
+    pub fn winning_cells(&self) -> Vec<usize> {
+        self.game_state.winning_indexes().unwrap_or_default()
+    }
+
+    /// Returns the cell indexes the player whose turn it is can mark.
+    #[wasm_bindgen(js_name = possibleMoves)]
+    pub fn possible_moves(&self) -> Vec<usize> {
+        let mut cell_indexes: Vec<usize> = self
+            .game_state
+            .possible_moves()
+            .iter()
+            .map(GameMove::cell_index)
+            .collect();
+        // With `Variant::Wild`, each cell is reached by a move per mark.
+        cell_indexes.dedup();
+        cell_indexes
+    }
+
+    /// Plays the move to the cell and returns the game state after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_index` - The index of the cell, row by row.
+    pub fn play(&self, cell_index: usize) -> Result<WasmGameState, JsError> {
+        let game_move = self.game_state.make_move_to(cell_index)?;
+        Ok(WasmGameState {
+            game_state: *game_move.after_state(),
+        })
+    }
+
+    /// Plays the move placing the mark on the cell, as allowed by `Variant::Wild`,
+    /// and returns the game state after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_index` - The index of the cell, row by row.
+    /// * `mark` - The mark placed on the cell, `X` or `O`.
+    #[wasm_bindgen(js_name = playWith)]
+    pub fn play_with(&self, cell_index: usize, mark: &str) -> Result<WasmGameState, JsError> {
+        let mark: Mark = mark.parse()?;
+        let game_move = self.game_state.make_move_with(cell_index, mark)?;
+        Ok(WasmGameState {
+            game_state: *game_move.after_state(),
+        })
+    }
+
+    /// Drops the mark in the column, as `Variant::Gravity` requires, and returns the game state after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column where the mark is dropped, from 0 to 2.
+    #[wasm_bindgen(js_name = playColumn)]
+    pub fn play_column(&self, column: usize) -> Result<WasmGameState, JsError> {
+        let game_move = self.game_state.make_move_to_column(column)?;
+        Ok(WasmGameState {
+            game_state: *game_move.after_state(),
+        })
+    }
+}
+
+/// A move, as seen from JavaScript.
+#[wasm_bindgen(js_name = GameMove)]
+pub struct WasmGameMove {
+    game_move: GameMove,
+}
+
+#[wasm_bindgen(js_class = GameMove)]
+impl WasmGameMove {
+    /// Returns the index of the marked cell, row by row.
+    #[wasm_bindgen(getter, js_name = cellIndex)]
+    pub fn cell_index(&self) -> usize {
+        self.game_move.cell_index()
+    }
+
+    /// Returns the mark placed on the cell.
+    #[wasm_bindgen(getter)]
+    pub fn mark(&self) -> String {
+        self.game_move.placed_mark().to_string()
+    }
+
+    /// Returns the coordinate of the marked cell, e.g. `B2`.
+    #[wasm_bindgen(getter)]
+    pub fn coordinate(&self) -> String {
+        self.game_move.coordinate()
+    }
+
+    /// Returns the game state after the move.
+    #[wasm_bindgen(js_name = afterState)]
+    pub fn after_state(&self) -> WasmGameState {
+        WasmGameState {
+            game_state: *self.game_move.after_state(),
+        }
+    }
+}
+
+/// Returns a move of perfect play for the player whose turn it is, as `analysis::best_move` does,
+/// or `undefined` if the game is over.
+///
+/// # Arguments
+///
+/// * `game_state` - The game state to find the move for.
+#[wasm_bindgen(js_name = bestMove)]
+pub fn best_move(game_state: &WasmGameState) -> Option<WasmGameMove> {
+    analysis::best_move(&game_state.game_state).map(|game_move| WasmGameMove { game_move })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The errors are JavaScript values, which only exist in a JavaScript runtime,
+    // so only the moves which are played are checked natively.
+    fn from_notation(notation: &str) -> WasmGameState {
+        let Ok(game_state) = WasmGameState::from_notation(notation) else {
+            panic!("`{}` is a valid notation", notation);
+        };
+        game_state
+    }
+
+    #[test]
+    fn test_play() {
+        let Ok(game_state) = WasmGameState::new(None) else {
+            panic!("The standard game starts on an empty grid");
+        };
+        let Ok(game_state) = game_state.play(4) else {
+            panic!("The center is empty");
+        };
+        assert_eq!(game_state.notation(), ".../.X./... O");
+        assert_eq!(game_state.current_mark(), "O");
+        assert_eq!(game_state.cells()[4], "X");
+        assert_eq!(game_state.possible_moves(), vec![0, 1, 2, 3, 5, 6, 7, 8]);
+        assert!(!game_state.game_over());
+    }
+
+    #[test]
+    fn test_winner() {
+        let game_state = from_notation("XXX/OO./... O");
+        assert!(game_state.game_over());
+        assert_eq!(game_state.winner(), Some(String::from("X")));
+        assert_eq!(game_state.winning_cells(), vec![0, 1, 2]);
+        assert!(best_move(&game_state).is_none());
+    }
+
+    #[test]
+    fn test_variants() {
+        let Ok(wild) = WasmGameState::new(Some(String::from("wild"))) else {
+            panic!("`wild` is a variant");
+        };
+        assert_eq!(wild.variant(), "wild");
+        // Each cell is listed once, though either mark can be placed on it.
+        assert_eq!(wild.possible_moves().len(), 9);
+        let Ok(wild) = wild.play_with(0, "O") else {
+            panic!("Any mark can be placed with the wild variant");
+        };
+        assert_eq!(wild.cells()[0], "O");
+
+        let Ok(gravity) = WasmGameState::new(Some(String::from("gravity"))) else {
+            panic!("`gravity` is a variant");
+        };
+        let Ok(gravity) = gravity.play_column(1) else {
+            panic!("The column is empty");
+        };
+        assert_eq!(gravity.cells()[7], "X");
+    }
+
+    #[test]
+    fn test_best_move_takes_the_win() {
+        let game_state = from_notation("XX./OO./... X");
+        let best_move = best_move(&game_state).unwrap();
+        assert_eq!(best_move.cell_index(), 2);
+        assert_eq!(best_move.mark(), "X");
+        assert_eq!(best_move.coordinate(), "C1");
+        assert_eq!(best_move.after_state().winner(), Some(String::from("X")));
+    }
+}